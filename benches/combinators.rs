@@ -0,0 +1,192 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Measures the throughput of the parsers generated for representative grammars (JSON values, arithmetic expressions and CSV records) on fixed inputs.
+//! Each input is parsed, building its value, and recognized, the throughput is reported in bytes per second.
+//! Run with `cargo bench --bench combinators`, `criterion` keeps the previous results in `target/criterion` to compare them with the next run.
+
+extern crate oak;
+extern crate oak_runtime;
+#[macro_use]
+extern crate criterion;
+
+use criterion::{Criterion, Throughput};
+use oak_runtime::*;
+
+mod json {
+  use oak::oak;
+
+  oak! {
+    json = spacing value eoi
+
+    value
+      = object > Value::Object
+      / array > Value::Array
+      / string > Value::Str
+      / number > Value::Number
+      / "true" spacing > make_true
+      / "false" spacing > make_false
+      / "null" spacing > make_null
+
+    object = lbrace (member % comma) rbrace
+    member = string colon value
+    array = lbracket (value % comma) rbracket
+    string = "\"" $(!"\"" .)* "\"" spacing > to_string
+    number = $("-"? ["0-9"]+ ("." ["0-9"]+)?) spacing > to_number
+
+    lbrace = "{" spacing
+    rbrace = "}" spacing
+    lbracket = "[" spacing
+    rbracket = "]" spacing
+    comma = "," spacing
+    colon = ":" spacing
+    spacing = [" \n\t"]*:(^)
+
+    // The values are built but never read by the benchmark.
+    #[allow(dead_code)]
+    pub enum Value {
+      Object(Vec<(String, Value)>),
+      Array(Vec<Value>),
+      Str(String),
+      Number(f64),
+      Bool(bool),
+      Null
+    }
+
+    fn to_string(raw: &str) -> String {
+      raw.to_string()
+    }
+
+    fn to_number(raw: &str) -> f64 {
+      raw.parse().unwrap()
+    }
+
+    fn make_true() -> Value {
+      Value::Bool(true)
+    }
+
+    fn make_false() -> Value {
+      Value::Bool(false)
+    }
+
+    fn make_null() -> Value {
+      Value::Null
+    }
+  }
+}
+
+mod arithmetic {
+  use oak::oak;
+
+  oak! {
+    expression = spacing sum eoi
+
+    sum = product (add_op product)* > fold_left
+    product = factor (mul_op factor)* > fold_left
+    factor = number / lparen sum rparen
+
+    number = $["0-9"]+ spacing > to_number
+    add_op = "+" spacing > add / "-" spacing > sub
+    mul_op = "*" spacing > mul
+    lparen = "(" spacing
+    rparen = ")" spacing
+    spacing = [" \n\t"]*:(^)
+
+    pub enum Op {
+      Add,
+      Sub,
+      Mul
+    }
+
+    fn add() -> Op { Op::Add }
+    fn sub() -> Op { Op::Sub }
+    fn mul() -> Op { Op::Mul }
+
+    fn to_number(raw: &str) -> i64 {
+      raw.parse().unwrap()
+    }
+
+    // The operations wrap on overflow, only the parsing is measured.
+    fn fold_left(first: i64, rest: Vec<(Op, i64)>) -> i64 {
+      rest.into_iter().fold(first, |acc, (op, x)| match op {
+        Op::Add => acc.wrapping_add(x),
+        Op::Sub => acc.wrapping_sub(x),
+        Op::Mul => acc.wrapping_mul(x)
+      })
+    }
+  }
+}
+
+mod csv {
+  use oak::oak;
+
+  oak! {
+    file = record* eoi
+    record = (field % ",") "\n"
+    field = quoted / $[^",\n\""]*
+    quoted = "\"" $[^"\""]* "\""
+  }
+}
+
+fn json_input() -> String {
+  let item = r#"{"name": "oak", "version": 0.8, "tags": ["peg", "parser", true, null], "deps": {"syn": [1, 0]}}"#;
+  format!("[{}]", vec![item; 1000].join(", "))
+}
+
+fn arithmetic_input() -> String {
+  let term = "(12 + 3) * 45 - 6 * (7 + 89 * (10 - 2)) + ";
+  format!("{}0", term.repeat(1000))
+}
+
+fn csv_input() -> String {
+  let record = "1997,Ford,E350,\"ac, abs, moon\",3000.00\n";
+  record.repeat(1000)
+}
+
+fn bench_json(c: &mut Criterion) {
+  let input = json_input();
+  let mut group = c.benchmark_group("json");
+  group.throughput(Throughput::Bytes(input.len() as u64));
+  group.bench_function("parse", |b| b.iter(|| json::full_parse_json(input.as_str()).unwrap()));
+  group.bench_function("recognize", |b| b.iter(|| {
+    assert!(json::recognize_json(input.as_str().into_state()).is_successful())
+  }));
+  group.finish();
+}
+
+fn bench_arithmetic(c: &mut Criterion) {
+  let input = arithmetic_input();
+  let mut group = c.benchmark_group("arithmetic");
+  group.throughput(Throughput::Bytes(input.len() as u64));
+  group.bench_function("parse", |b| b.iter(|| arithmetic::full_parse_expression(input.as_str()).unwrap()));
+  group.bench_function("recognize", |b| b.iter(|| {
+    assert!(arithmetic::recognize_expression(input.as_str().into_state()).is_successful())
+  }));
+  group.finish();
+}
+
+fn bench_csv(c: &mut Criterion) {
+  let input = csv_input();
+  let mut group = c.benchmark_group("csv");
+  group.throughput(Throughput::Bytes(input.len() as u64));
+  group.bench_function("parse", |b| b.iter(|| csv::full_parse_file(input.as_str()).unwrap()));
+  group.bench_function("recognize", |b| b.iter(|| {
+    assert!(csv::recognize_file(input.as_str().into_state()).is_successful())
+  }));
+  group.finish();
+}
+
+criterion_group!(benches, bench_json, bench_arithmetic, bench_csv);
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares a choice compiled into a dispatch on the next character (the keywords start with distinct characters) with the same choice tried in sequence (the additional keyword `wx` overlaps with `while`).
+//! Run with `cargo bench` on a nightly compiler.
+
+#![feature(test)]
+
+extern crate test;
+extern crate oak;
+extern crate oak_runtime;
+
+use oak::oak;
+use oak_runtime::*;
+use test::Bencher;
+
+oak! {
+  disjoint = (keyword " "?)+ eoi
+
+  keyword = "break" / "const" / "else" / "fn" / "if" / "let" / "match" / "pub"
+          / "return" / "struct" / "use" / "while"
+}
+
+mod overlapping {
+  use oak::oak;
+
+  oak! {
+    overlapping = (keyword " "?)+ eoi
+
+    keyword = "break" / "const" / "else" / "fn" / "if" / "let" / "match" / "pub"
+            / "return" / "struct" / "use" / "while" / "wx"
+  }
+}
+
+fn input() -> String {
+  let keywords = "break const else fn if let match pub return struct use while ";
+  keywords.repeat(1000)
+}
+
+#[bench]
+fn bench_disjoint_choice(b: &mut Bencher) {
+  let input = input();
+  b.iter(|| {
+    let state = recognize_disjoint(input.as_str().into_state());
+    assert!(state.is_successful());
+  });
+}
+
+#[bench]
+fn bench_overlapping_choice(b: &mut Bencher) {
+  let input = input();
+  b.iter(|| {
+    let state = overlapping::recognize_overlapping(input.as_str().into_state());
+    assert!(state.is_successful());
+  });
+}
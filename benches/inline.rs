@@ -0,0 +1,97 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares a JSON-like grammar whose rule functions are marked `#[inline]` (the default) with the same grammar using `#![inline_rules]` (`#[inline(always)]`).
+//! Run with `cargo bench` on a nightly compiler, the size of the benchmark binary can be compared with and without the module `inlined`.
+
+#![feature(test)]
+
+extern crate test;
+extern crate oak;
+extern crate oak_runtime;
+
+use oak_runtime::*;
+use test::Bencher;
+
+mod default {
+  use oak::oak;
+
+  oak! {
+    json = spacing value eoi
+    value = object / array / string / number / literal
+    object = lbrace (member % comma) rbrace
+    member = string colon value
+    array = lbracket (value % comma) rbracket
+    string = "\"" (!"\"" .)* "\"" spacing
+    number = "-"? ["0-9"]+ ("." ["0-9"]+)? spacing
+    literal = ("true" / "false" / "null") spacing
+
+    lbrace = "{" spacing
+    rbrace = "}" spacing
+    lbracket = "[" spacing
+    rbracket = "]" spacing
+    comma = "," spacing
+    colon = ":" spacing
+    spacing = [" \n\t"]*:(^)
+  }
+}
+
+// Same grammar as `default`.
+mod inlined {
+  use oak::oak;
+
+  oak! {
+    #![inline_rules]
+
+    json = spacing value eoi
+    value = object / array / string / number / literal
+    object = lbrace (member % comma) rbrace
+    member = string colon value
+    array = lbracket (value % comma) rbracket
+    string = "\"" (!"\"" .)* "\"" spacing
+    number = "-"? ["0-9"]+ ("." ["0-9"]+)? spacing
+    literal = ("true" / "false" / "null") spacing
+
+    lbrace = "{" spacing
+    rbrace = "}" spacing
+    lbracket = "[" spacing
+    rbracket = "]" spacing
+    comma = "," spacing
+    colon = ":" spacing
+    spacing = [" \n\t"]*:(^)
+  }
+}
+
+fn input() -> String {
+  let item = r#"{"name": "oak", "version": 0.8, "tags": ["peg", "parser", true, null], "deps": {"syn": [1, 0]}}"#;
+  format!("[{}]", vec![item; 1000].join(", "))
+}
+
+#[bench]
+fn bench_default_inline(b: &mut Bencher) {
+  let input = input();
+  b.iter(|| {
+    let state = default::recognize_json(input.as_str().into_state());
+    assert!(state.is_successful());
+  });
+}
+
+#[bench]
+fn bench_inline_rules(b: &mut Bencher) {
+  let input = input();
+  b.iter(|| {
+    let state = inlined::recognize_json(input.as_str().into_state());
+    assert!(state.is_successful());
+  });
+}
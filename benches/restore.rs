@@ -0,0 +1,47 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Measures `ParseState::restore_from_failure` after consuming inputs of increasing length, its cost must not depend on the length of the input consumed since the mark.
+//! Run with `cargo bench --bench restore`, the times reported for each length should be the same.
+
+extern crate oak_runtime;
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BatchSize, BenchmarkId, Criterion};
+use oak_runtime::*;
+
+fn bench_restore_from_failure(c: &mut Criterion) {
+  let mut group = c.benchmark_group("restore_from_failure");
+  for &len in &[1_000, 10_000, 100_000, 1_000_000] {
+    let input = "a".repeat(len);
+    group.bench_with_input(BenchmarkId::from_parameter(len), &input, |b, input| {
+      // The state consumes the whole input and fails at its end, only the restoration is measured.
+      b.iter_batched(|| {
+          let mut state: ParseState<StrStream, ()> = input.as_str().into_state();
+          let mark = state.mark();
+          while state.next().is_some() {}
+          state.error("end of input");
+          (state, mark)
+        },
+        |(state, mark)| state.restore_from_failure(mark),
+        BatchSize::SmallInput)
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench_restore_from_failure);
+criterion_main!(benches);
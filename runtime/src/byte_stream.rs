@@ -0,0 +1,244 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of `Stream` for `&'a [u8]` type, it is used to parse binary formats.
+//! Every byte is read as the character with the same code point (from `'\u{0}'` to `'\u{ff}'`), therefore character classes such as `["\u{0}-\u{7f}"]` are byte ranges and string literals are sequences of bytes.
+//! A string literal containing a character greater than `'\u{ff}'` never matches.
+
+use stream::*;
+use std::cmp::{Ordering, min};
+pub use std::ops::Range;
+#[cfg(not(feature = "std"))]
+use alloc_prelude::*;
+
+impl<'a> Stream for &'a [u8]
+{
+  type Output = ByteStream<'a>;
+  fn stream(self) -> ByteStream<'a> {
+    ByteStream::new(self)
+  }
+}
+
+impl<'a> Stream for &'a Vec<u8>
+{
+  type Output = ByteStream<'a>;
+  fn stream(self) -> ByteStream<'a> {
+    self.as_slice().stream()
+  }
+}
+
+/// Represents a stream from a `&'a [u8]`. It implements all traits required by `CharStream`.
+#[derive(Clone, Hash, Debug)]
+pub struct ByteStream<'a>
+{
+  raw_data: &'a [u8],
+  offset: usize
+}
+
+impl<'a> ByteStream<'a>
+{
+  fn new(raw_data: &'a [u8]) -> ByteStream<'a> {
+    ByteStream {
+      raw_data: raw_data,
+      offset: 0
+    }
+  }
+
+  #[inline(always)]
+  fn assert_same_raw_data(&self, other: &ByteStream<'a>) {
+    debug_assert!(self.raw_data.as_ptr() == other.raw_data.as_ptr(),
+      "Operations between two streams are only defined when they share the same raw data.");
+  }
+
+  pub fn bytes_offset(&self) -> usize {
+    self.offset
+  }
+
+  pub fn current_byte(&self) -> Option<u8> {
+    self.raw_data.get(self.offset).cloned()
+  }
+
+  pub fn slice(&self, end: ByteStream<'a>) -> &'a [u8] {
+    &self.raw_data[self.offset..end.offset]
+  }
+
+  /// Returns the length of `prefix` if it matches the bytes from the current position, where `eq` compares two bytes.
+  fn match_prefix<F>(&self, prefix: &'static str, eq: F) -> Option<usize> where
+   F: Fn(u8, u8) -> bool
+  {
+    let mut data = self.raw_data[self.offset..].iter();
+    let mut len = 0;
+    for c in prefix.chars() {
+      if (c as u32) > 0xff {
+        return None;
+      }
+      match data.next() {
+        Some(&b) if eq(b, c as u8) => len += 1,
+        _ => return None
+      }
+    }
+    Some(len)
+  }
+}
+
+impl<'a> Iterator for ByteStream<'a>
+{
+  type Item = char;
+  fn next(&mut self) -> Option<Self::Item> {
+    let current = self.current_byte();
+    if current.is_some() {
+      self.offset += 1;
+    }
+    current.map(char::from)
+  }
+}
+
+impl<'a> PartialEq for ByteStream<'a>
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.assert_same_raw_data(other);
+    self.offset == other.offset
+  }
+}
+
+impl<'a> Eq for ByteStream<'a> {}
+
+impl<'a> PartialOrd for ByteStream<'a>
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.assert_same_raw_data(other);
+    self.offset.partial_cmp(&other.offset)
+  }
+}
+
+impl<'a> Ord for ByteStream<'a>
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.assert_same_raw_data(other);
+    self.offset.cmp(&other.offset)
+  }
+}
+
+impl<'a> Location for ByteStream<'a>
+{
+  fn location(&self) -> String {
+    format!("byte {}", self.offset)
+  }
+}
+
+impl<'a> ByteOffset for ByteStream<'a>
+{
+  fn byte_offset(&self) -> usize {
+    self.offset
+  }
+}
+
+impl<'a> CodeSnippet for ByteStream<'a>
+{
+  fn code_snippet(&self, len_hint: usize) -> String {
+    let total_len = self.raw_data.len();
+    if self.offset == total_len {
+      String::from("<end-of-file>")
+    }
+    else {
+      let len = min(total_len - self.offset, len_hint);
+      self.raw_data[self.offset..][..len].iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+  }
+}
+
+impl<'a> ConsumePrefix<&'static str> for ByteStream<'a>
+{
+  fn consume_prefix(&mut self, prefix: &'static str) -> bool {
+    match self.match_prefix(prefix, |b, c| b == c) {
+      Some(len) => {
+        self.offset += len;
+        true
+      }
+      None => false
+    }
+  }
+}
+
+impl<'a> ConsumePrefixIgnoreAsciiCase<&'static str> for ByteStream<'a>
+{
+  fn consume_prefix_ignore_ascii_case(&mut self, prefix: &'static str) -> bool {
+    match self.match_prefix(prefix, |b, c| b.eq_ignore_ascii_case(&c)) {
+      Some(len) => {
+        self.offset += len;
+        true
+      }
+      None => false
+    }
+  }
+}
+
+impl<'a> HasNext for ByteStream<'a>
+{
+  fn has_next(&self) -> bool {
+    self.offset < self.raw_data.len()
+  }
+}
+
+/// The span of a byte stream is the range of the byte offsets.
+impl<'a> StreamSpan for Range<ByteStream<'a>>
+{
+  type Output = Range<usize>;
+  fn stream_span(&self) -> Self::Output {
+    self.start.offset..self.end.offset
+  }
+}
+
+impl<'a> StreamSlice for Range<ByteStream<'a>>
+{
+  type Output = &'a [u8];
+  fn stream_slice(&self) -> Self::Output {
+    self.start.slice(self.end.clone())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_next() {
+    let data: &[u8] = &[0x61, 0xff, 0x00];
+    let mut s = data.stream();
+    assert_eq!(s.next(), Some('a'));
+    assert_eq!(s.next(), Some('\u{ff}'));
+    assert_eq!(s.bytes_offset(), 2);
+    assert_eq!(s.next(), Some('\u{0}'));
+    assert_eq!(s.next(), None);
+    assert!(!s.has_next());
+  }
+
+  #[test]
+  fn test_consume_prefix() {
+    let data: &[u8] = &[0x47, 0x49, 0x46, 0xff];
+    let s = data.stream();
+    let mut s2 = s.clone();
+    assert!(s2.consume_prefix("GIF\u{ff}"));
+    assert!(!s2.has_next());
+    let mut s2 = s.clone();
+    assert!(!s2.consume_prefix("GIFF"));
+    assert!(!s2.consume_prefix("G\u{100}"));
+    assert_eq!(s2.bytes_offset(), 0);
+    assert!(s2.consume_prefix_ignore_ascii_case("gi"));
+    assert_eq!(s2.next(), Some('F'));
+  }
+}
@@ -0,0 +1,251 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Concrete syntax tree of a parse, generated by the grammar attribute `#![cst]`.
+//!
+//! Unlike the value of a rule, the concrete syntax tree is lossless: each rule that succeeded is a node spanning the bytes it matched, and the input between the rules called by a node, such as literals, characters and whitespaces, is kept in `Token` nodes.
+//! Hence, the text of the tokens of a node, in order, is exactly the input matched by this node, which is what formatters and refactoring tools need.
+//! For example, parsing `1+2` with `sum = number "+" number` and `number = ["0-9"]` builds:
+//!
+//! ```text
+//! sum 0..3
+//!   number 0..1
+//!     token 0..1
+//!   token 1..2
+//!   number 2..3
+//!     token 2..3
+//! ```
+//!
+//! The functions of the rules call `cst_enter` and `cst_exit`, which only record the nodes inside `record_cst`.
+//! The entry function `cst_parse_r` of a start rule `r` calls `record_cst` and returns the node of `r`.
+
+use stream::*;
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter, Error};
+use std::ops::Range;
+
+thread_local! {
+  static BUILDER: RefCell<Option<Builder>> = RefCell::new(None);
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CstKind
+{
+  /// The node of a rule, with the name of the rule.
+  Rule(String),
+  /// Input matched between the rules, it has no children.
+  Token
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CstNode
+{
+  pub kind: CstKind,
+  /// The bytes of the input matched by the node.
+  pub span: Range<usize>,
+  pub children: Vec<CstNode>
+}
+
+impl CstNode
+{
+  /// The input matched by the node, `input` must be the parsed input.
+  pub fn text<'a>(&self, input: &'a str) -> &'a str {
+    &input[self.span.clone()]
+  }
+
+  /// The tokens of the node in the order of the input, their texts concatenated form the text of the node.
+  pub fn tokens(&self) -> Vec<&CstNode> {
+    match self.kind {
+      CstKind::Token => vec![self],
+      CstKind::Rule(_) => self.children.iter().flat_map(|child| child.tokens()).collect()
+    }
+  }
+
+  fn fmt_indented(&self, formatter: &mut Formatter, depth: usize) -> Result<(), Error> {
+    formatter.write_str(&"  ".repeat(depth))?;
+    match self.kind {
+      CstKind::Rule(ref rule) => writeln!(formatter, "{} {}..{}", rule, self.span.start, self.span.end)?,
+      CstKind::Token => writeln!(formatter, "token {}..{}", self.span.start, self.span.end)?
+    }
+    for child in &self.children {
+      child.fmt_indented(formatter, depth + 1)?;
+    }
+    Ok(())
+  }
+}
+
+/// Displays the nodes indented by their depth, one node per line.
+impl Display for CstNode
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    self.fmt_indented(formatter, 0)
+  }
+}
+
+/// A rule being parsed, with the nodes of the rules it called successfully.
+struct OpenNode
+{
+  rule: String,
+  start: usize,
+  children: Vec<CstNode>
+}
+
+struct Builder
+{
+  /// The rules not yet exited, the last one is the current rule.
+  open: Vec<OpenNode>,
+  roots: Vec<CstNode>
+}
+
+impl Builder
+{
+  fn enter(&mut self, rule: &str, start: usize) {
+    self.open.push(OpenNode { rule: rule.to_string(), start, children: vec![] });
+  }
+
+  fn exit(&mut self, end: Option<usize>) {
+    if let Some(node) = self.open.pop() {
+      if let Some(end) = end {
+        let node = Self::close(node, end);
+        let siblings = match self.open.last_mut() {
+          Some(parent) => &mut parent.children,
+          None => &mut self.roots
+        };
+        // The siblings reading the input of this node have been backtracked.
+        siblings.retain(|sibling| sibling.span.end <= node.span.start);
+        siblings.push(node);
+      }
+    }
+  }
+
+  /// The children read after `end` have been backtracked, and the gaps between the remaining children are filled with tokens.
+  fn close(node: OpenNode, end: usize) -> CstNode {
+    let mut children = vec![];
+    let mut cursor = node.start;
+    for child in node.children.into_iter().filter(|child| child.span.end <= end) {
+      if child.span.start > cursor {
+        children.push(Self::token(cursor, child.span.start));
+      }
+      cursor = child.span.end;
+      children.push(child);
+    }
+    if end > cursor {
+      children.push(Self::token(cursor, end));
+    }
+    CstNode { kind: CstKind::Rule(node.rule), span: node.start..end, children }
+  }
+
+  fn token(start: usize, end: usize) -> CstNode {
+    CstNode { kind: CstKind::Token, span: start..end, children: vec![] }
+  }
+}
+
+/// Called when entering the function of the rule `rule` at the position `current`.
+pub fn cst_enter<S: ByteOffset>(rule: &str, current: &S) {
+  BUILDER.with(|builder| {
+    if let Some(ref mut builder) = *builder.borrow_mut() {
+      builder.enter(rule, current.byte_offset());
+    }
+  })
+}
+
+/// Called when exiting the function of the current rule, `current` is the position reached if it succeeded.
+pub fn cst_exit<S: ByteOffset>(current: &S, success: bool) {
+  BUILDER.with(|builder| {
+    if let Some(ref mut builder) = *builder.borrow_mut() {
+      builder.exit(if success { Some(current.byte_offset()) } else { None });
+    }
+  })
+}
+
+/// Executes `f` and returns its result with the nodes of the rules called by `f` that succeeded, for example `record_cst(|| recognize_sum(input.into_state()))`.
+/// Only the grammars with the attribute `#![cst]` record their nodes.
+/// The value of a memoized rule read from the memoization table has no children.
+pub fn record_cst<R, F: FnOnce() -> R>(f: F) -> (R, Vec<CstNode>) {
+  BUILDER.with(|builder| *builder.borrow_mut() = Some(Builder { open: vec![], roots: vec![] }));
+  let result = f();
+  let builder = BUILDER.with(|builder| builder.borrow_mut().take().unwrap());
+  (result, builder.roots)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn rule(name: &str, span: Range<usize>, children: Vec<CstNode>) -> CstNode {
+    CstNode { kind: CstKind::Rule(name.to_string()), span, children }
+  }
+
+  fn token(span: Range<usize>) -> CstNode {
+    CstNode { kind: CstKind::Token, span, children: vec![] }
+  }
+
+  #[test]
+  fn test_record_cst() {
+    let input = "1+2";
+    let ((), roots) = record_cst(|| {
+      let mut stream = input.stream();
+      cst_enter("sum", &stream);
+      cst_enter("number", &stream);
+      stream.next();
+      cst_exit(&stream, true);
+      stream.next();
+      cst_enter("number", &stream);
+      stream.next();
+      cst_exit(&stream, true);
+      cst_exit(&stream, true);
+    });
+    assert_eq!(roots, vec![
+      rule("sum", 0..3, vec![
+        rule("number", 0..1, vec![token(0..1)]),
+        token(1..2),
+        rule("number", 2..3, vec![token(2..3)])])]);
+    let tokens: Vec<_> = roots[0].tokens().into_iter().map(|t| t.text(input)).collect();
+    assert_eq!(tokens.concat(), input);
+    assert_eq!(format!("{}", roots[0]),
+      "sum 0..3\n  number 0..1\n    token 0..1\n  token 1..2\n  number 2..3\n    token 2..3\n");
+  }
+
+  #[test]
+  fn test_backtracked_nodes() {
+    let ((), roots) = record_cst(|| {
+      let mut stream = "ab".stream();
+      let start = stream.clone();
+      cst_enter("choice", &stream);
+      // The first branch `a a` calls `a` which succeeds, then fails and the choice backtracks.
+      cst_enter("a", &stream);
+      stream.next();
+      cst_exit(&stream, true);
+      cst_enter("a", &stream);
+      cst_exit(&stream, false);
+      stream = start;
+      // The second branch `ab` succeeds.
+      cst_enter("ab", &stream);
+      stream.next();
+      stream.next();
+      cst_exit(&stream, true);
+      cst_exit(&stream, true);
+    });
+    assert_eq!(roots, vec![rule("choice", 0..2, vec![rule("ab", 0..2, vec![token(0..2)])])]);
+  }
+
+  #[test]
+  fn test_not_recording() {
+    let stream = "a".stream();
+    cst_enter("a", &stream);
+    cst_exit(&stream, true);
+    let ((), roots) = record_cst(|| ());
+    assert!(roots.is_empty());
+  }
+}
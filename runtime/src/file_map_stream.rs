@@ -102,6 +102,13 @@ impl<'a> Location for FileMapStream<'a>
   }
 }
 
+impl<'a> ByteOffset for FileMapStream<'a>
+{
+  fn byte_offset(&self) -> usize {
+    self.str_stream.byte_offset()
+  }
+}
+
 impl<'a> CodeSnippet for FileMapStream<'a>
 {
   fn code_snippet(&self, len_hint: usize) -> String {
@@ -116,6 +123,13 @@ impl<'a> ConsumePrefix<&'static str> for FileMapStream<'a>
   }
 }
 
+impl<'a> ConsumePrefixIgnoreAsciiCase<&'static str> for FileMapStream<'a>
+{
+  fn consume_prefix_ignore_ascii_case(&mut self, prefix: &'static str) -> bool {
+    self.str_stream.consume_prefix_ignore_ascii_case(prefix)
+  }
+}
+
 impl<'a> HasNext for FileMapStream<'a>
 {
   fn has_next(&self) -> bool {
@@ -0,0 +1,400 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of a stream over any `Iterator<Item=char>`, for example the characters read from the standard input, without reading the whole input beforehand.
+//!
+//! Since a PEG parser can backtrack to any previous position, the characters read are buffered and shared between the clones of the stream.
+//! The buffer only keeps the characters from the position of the oldest living stream, which includes the marks saved by the parser and its memoization table, so the memory used is bounded by the farthest backtracking point.
+
+use stream::*;
+use std::cmp::Ordering;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::collections::{VecDeque, BTreeMap};
+#[cfg(feature = "std")]
+use super::*;
+pub use std::ops::Range;
+#[cfg(feature = "std")]
+pub use syntex_pos::Span;
+#[cfg(not(feature = "std"))]
+use alloc_prelude::*;
+
+/// Characters read from the iterator and not yet released by the streams.
+struct Buffer<I>
+{
+  iter: I,
+  chars: VecDeque<char>,
+  /// The index of `chars[0]` since the beginning of the input.
+  first: usize,
+  /// The number of streams at each position still in use.
+  living: BTreeMap<usize, usize>
+}
+
+impl<I> Buffer<I> where
+ I: Iterator<Item=char>
+{
+  fn get(&mut self, index: usize) -> Option<char> {
+    debug_assert!(index >= self.first, "The character at this position has already been released.");
+    while index >= self.first + self.chars.len() {
+      let c = self.iter.next()?;
+      self.chars.push_back(c);
+    }
+    Some(self.chars[index - self.first])
+  }
+
+  fn acquire(&mut self, index: usize) {
+    *self.living.entry(index).or_insert(0) += 1;
+  }
+
+  fn release(&mut self, index: usize) {
+    let remove = {
+      let count = self.living.get_mut(&index)
+        .expect("Releasing a position that is not in use.");
+      *count -= 1;
+      *count == 0
+    };
+    if remove {
+      self.living.remove(&index);
+      if let Some(&oldest) = self.living.keys().next() {
+        while self.first < oldest && !self.chars.is_empty() {
+          self.chars.pop_front();
+          self.first += 1;
+        }
+      }
+    }
+  }
+}
+
+/// Represents a stream from an iterator of characters. It implements all traits required by `CharStream`.
+/// The location of the stream is given in line and column (in bytes, as `StrStream`).
+pub struct IterStream<I> where
+ I: Iterator<Item=char>
+{
+  buffer: Rc<RefCell<Buffer<I>>>,
+  index: usize,
+  bytes_offset: usize,
+  line: usize,
+  column: usize
+}
+
+impl<I> IterStream<I> where
+ I: Iterator<Item=char>
+{
+  pub fn new(iter: I) -> IterStream<I> {
+    let mut buffer = Buffer {
+      iter: iter,
+      chars: VecDeque::new(),
+      first: 0,
+      living: BTreeMap::new()
+    };
+    buffer.acquire(0);
+    IterStream {
+      buffer: Rc::new(RefCell::new(buffer)),
+      index: 0,
+      bytes_offset: 0,
+      line: 1,
+      column: 1
+    }
+  }
+
+  /// Number of characters currently buffered.
+  pub fn buffer_len(&self) -> usize {
+    self.buffer.borrow().chars.len()
+  }
+
+  pub fn line_column(&self) -> (usize, usize) {
+    (self.line, self.column)
+  }
+
+  pub fn bytes_offset(&self) -> usize {
+    self.bytes_offset
+  }
+
+  pub fn current_char(&self) -> Option<char> {
+    self.buffer.borrow_mut().get(self.index)
+  }
+
+  #[inline(always)]
+  fn assert_same_buffer(&self, other: &IterStream<I>) {
+    debug_assert!(Rc::ptr_eq(&self.buffer, &other.buffer),
+      "Operations between two streams are only defined when they share the same buffer.");
+  }
+
+  /// Moves the stream after the characters of `consumed`, which must be the next characters of the stream.
+  fn advance<C>(&mut self, consumed: C) where
+   C: Iterator<Item=char>
+  {
+    let start = self.index;
+    for c in consumed {
+      self.index += 1;
+      self.bytes_offset += c.len_utf8();
+      if c == '\n' {
+        self.line += 1;
+        self.column = 1;
+      }
+      else {
+        self.column += c.len_utf8();
+      }
+    }
+    if self.index != start {
+      let mut buffer = self.buffer.borrow_mut();
+      buffer.acquire(self.index);
+      buffer.release(start);
+    }
+  }
+
+  /// True if the characters of `prefix` are the next ones of the stream, compared with `eq`.
+  fn starts_with<F>(&self, prefix: &str, eq: F) -> bool where
+   F: Fn(char, char) -> bool
+  {
+    let mut buffer = self.buffer.borrow_mut();
+    prefix.chars().enumerate().all(|(i, p)| {
+      match buffer.get(self.index + i) {
+        Some(c) => eq(c, p),
+        None => false
+      }
+    })
+  }
+}
+
+impl<I> Clone for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn clone(&self) -> Self {
+    self.buffer.borrow_mut().acquire(self.index);
+    IterStream {
+      buffer: self.buffer.clone(),
+      index: self.index,
+      bytes_offset: self.bytes_offset,
+      line: self.line,
+      column: self.column
+    }
+  }
+}
+
+impl<I> Drop for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn drop(&mut self) {
+    self.buffer.borrow_mut().release(self.index);
+  }
+}
+
+impl<I> Stream for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  type Output = IterStream<I>;
+  fn stream(self) -> IterStream<I> {
+    self
+  }
+}
+
+impl<I> Iterator for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  type Item = char;
+  fn next(&mut self) -> Option<Self::Item> {
+    let current = self.current_char()?;
+    self.advance(Some(current).into_iter());
+    Some(current)
+  }
+}
+
+impl<I> PartialEq for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.assert_same_buffer(other);
+    self.index == other.index
+  }
+}
+
+impl<I> Eq for IterStream<I> where
+ I: Iterator<Item=char>
+{}
+
+impl<I> PartialOrd for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<I> Ord for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.assert_same_buffer(other);
+    self.index.cmp(&other.index)
+  }
+}
+
+impl<I> Location for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn location(&self) -> String {
+    format!("{}:{}", self.line, self.column)
+  }
+}
+
+impl<I> ByteOffset for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn byte_offset(&self) -> usize {
+    self.bytes_offset
+  }
+}
+
+impl<I> CodeSnippet for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn code_snippet(&self, len_hint: usize) -> String {
+    let mut buffer = self.buffer.borrow_mut();
+    let mut snippet = String::new();
+    while let Some(c) = buffer.get(self.index + snippet.chars().count()) {
+      if snippet.len() + c.len_utf8() > len_hint {
+        break;
+      }
+      snippet.push(c);
+    }
+    if snippet.is_empty() && buffer.get(self.index).is_none() {
+      String::from("<end-of-file>")
+    }
+    else {
+      snippet
+    }
+  }
+}
+
+impl<I> ConsumePrefix<&'static str> for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn consume_prefix(&mut self, prefix: &'static str) -> bool {
+    if self.starts_with(prefix, |c, p| c == p) {
+      self.advance(prefix.chars());
+      true
+    } else {
+      false
+    }
+  }
+}
+
+impl<I> ConsumePrefixIgnoreAsciiCase<&'static str> for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  // The non-ASCII characters must match exactly, so the consumed characters have the same lengths as the ones of `prefix`.
+  fn consume_prefix_ignore_ascii_case(&mut self, prefix: &'static str) -> bool {
+    if self.starts_with(prefix, |c, p| c.eq_ignore_ascii_case(&p)) {
+      self.advance(prefix.chars());
+      true
+    } else {
+      false
+    }
+  }
+}
+
+impl<I> HasNext for IterStream<I> where
+ I: Iterator<Item=char>
+{
+  fn has_next(&self) -> bool {
+    self.current_char().is_some()
+  }
+}
+
+#[cfg(feature = "std")]
+impl<I> StreamSpan for Range<IterStream<I>> where
+ I: Iterator<Item=char>
+{
+  type Output = Span;
+  fn stream_span(&self) -> Self::Output {
+    make_span(
+      self.start.bytes_offset,
+      self.end.bytes_offset)
+  }
+}
+
+/// Without `syntex_pos`, the span is the range of the bytes offsets.
+#[cfg(not(feature = "std"))]
+impl<I> StreamSpan for Range<IterStream<I>> where
+ I: Iterator<Item=char>
+{
+  type Output = Range<usize>;
+  fn stream_span(&self) -> Self::Output {
+    Range {
+      start: self.start.bytes_offset,
+      end: self.end.bytes_offset
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn stream(input: &'static str) -> IterStream<std::str::Chars<'static>> {
+    IterStream::new(input.chars())
+  }
+
+  #[test]
+  fn test_consume_prefix() {
+    let mut s = stream("SeLeCt x");
+    assert!(!s.consume_prefix("select"));
+    assert!(s.consume_prefix_ignore_ascii_case("select"));
+    assert!(!s.consume_prefix(" y"));
+    assert!(s.consume_prefix(" "));
+    assert_eq!(s.next(), Some('x'));
+    assert_eq!(s.next(), None);
+    assert!(!s.has_next());
+    assert_eq!(s.bytes_offset(), 8);
+  }
+
+  #[test]
+  fn test_backtracking() {
+    let mut s1 = stream("abc");
+    let mark = s1.clone();
+    assert_eq!(s1.next(), Some('a'));
+    assert!(s1 > mark);
+    let mut s2 = mark.clone();
+    assert!(s2.consume_prefix("ab"));
+    assert_eq!(s2.next(), Some('c'));
+    assert_eq!(s1.next(), Some('b'));
+    assert!(s2 > s1);
+    assert_eq!(mark.code_snippet(10), format!("abc"));
+    assert_eq!(s2.code_snippet(10), format!("<end-of-file>"));
+  }
+
+  #[test]
+  fn test_buffer_release() {
+    let mut s = stream("abcdef");
+    let mark = s.clone();
+    assert!(s.consume_prefix("abcd"));
+    // The characters are kept since `mark` can still be restored.
+    assert_eq!(s.buffer_len(), 4);
+    drop(mark);
+    assert_eq!(s.buffer_len(), 0);
+    assert_eq!(s.next(), Some('e'));
+    assert_eq!(s.buffer_len(), 0);
+  }
+
+  #[test]
+  fn test_location() {
+    let mut s = stream("ab\ncé\nd");
+    assert!(s.consume_prefix("ab\ncé"));
+    assert_eq!(s.location(), format!("2:4"));
+    assert_eq!(s.bytes_offset(), 6);
+    assert_eq!(s.next(), Some('\n'));
+    assert_eq!(s.line_column(), (3, 1));
+  }
+}
@@ -16,19 +16,60 @@
 //!
 //! This library is used by the generated code of Oak and is also necessary to any Oak users for interfacing with the code generated.
 //! A PEG combinator returns a `ParseState`, please consult the methods `into_result` or `unwrap_data` as they are good starting point for retrieving useful information.
+//!
+//! The runtime can be used without the standard library by disabling the default feature `std`.
+//! In this case, the spans of `StrStream` are byte ranges (`Range<usize>`) and `file_map_stream`, `trace` and `cst` are not available.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
 extern crate syntex_pos;
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
 
 pub use str_stream::*;
+pub use byte_stream::*;
+pub use iter_stream::*;
+pub use slice_stream::*;
 pub use stream::*;
 pub use parse_state::*;
+pub use memo::*;
+#[cfg(feature = "std")]
 use syntex_pos::{BytePos, mk_sp};
 
 pub mod str_stream;
+pub mod byte_stream;
+pub mod iter_stream;
+pub mod slice_stream;
 pub mod parse_state;
 pub mod stream;
+pub mod memo;
+pub mod unicode;
+#[cfg(feature = "std")]
 pub mod file_map_stream;
+#[cfg(feature = "std")]
+pub mod trace;
+#[cfg(feature = "std")]
+pub mod cst;
+
+/// Without the standard library, the paths `std::*` of the runtime refer to `core` and `alloc`.
+#[cfg(not(feature = "std"))]
+mod std {
+  pub use core::*;
+  pub use alloc::collections;
+  pub use alloc::rc;
+}
+
+/// Types of the standard prelude that are not in the prelude of `core`.
+#[cfg(not(feature = "std"))]
+mod alloc_prelude {
+  pub use alloc::string::String;
+  pub use alloc::vec::Vec;
+  pub use alloc::boxed::Box;
+}
 
+#[cfg(feature = "std")]
 pub fn make_span(lo: usize, hi: usize) -> Span {
   mk_sp(
     BytePos(lo as u32),
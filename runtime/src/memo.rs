@@ -0,0 +1,394 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memoization table of the parsing state, used by the generated code in packrat mode (`#![packrat]`) and for left-recursive rules (`#![left_recursion]`).
+//! The table is indexed by the rule identifier (see `RuleId`) and the position in the stream where the rule has been called.
+//! Recognizers memoize their full result (success with the end position, or failure) which guarantees linear time recognition.
+//! Parsers also store the value of a success, as the left-recursive parsers do for their seed.
+
+use parse_state::*;
+use stream::*;
+use std::collections::BTreeMap;
+use std::any::{Any, TypeId};
+#[cfg(not(feature = "std"))]
+use alloc_prelude::*;
+
+/// Identifies a rule in the memoization table.
+/// The state, and thus the table, is shared by the grammars calling the rules of each other, so the index of the rule in its grammar is not enough: the grammar is identified by the type `G`, a marker type generated in the module of the grammar.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct RuleId
+{
+  grammar: TypeId,
+  index: usize
+}
+
+impl RuleId
+{
+  pub fn new<G: 'static>(index: usize) -> RuleId {
+    RuleId {
+      grammar: TypeId::of::<G>(),
+      index: index
+    }
+  }
+}
+
+pub enum MemoEntry<S>
+{
+  /// The rule succeeded and the stream was moved to the given position.
+  /// The value is only stored by parsers, not by recognizers.
+  Success(S, Option<Box<dyn Any>>),
+  Failure
+}
+
+pub struct MemoTable<S>
+{
+  entries: BTreeMap<(RuleId, S), MemoEntry<S>>
+}
+
+impl<S> MemoTable<S> where
+ S: Ord
+{
+  pub fn new() -> MemoTable<S> {
+    MemoTable {
+      entries: BTreeMap::new()
+    }
+  }
+
+  pub fn get(&self, rule: RuleId, pos: S) -> Option<&MemoEntry<S>> {
+    self.entries.get(&(rule, pos))
+  }
+
+  pub fn insert(&mut self, rule: RuleId, pos: S, entry: MemoEntry<S>) {
+    self.entries.insert((rule, pos), entry);
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+}
+
+impl<S> ParseState<S, ()> where
+ S: Ord + Clone + HasNext
+{
+  /// Calls the recognizer `body` of the rule `rule` unless its result at the current position is already memoized.
+  pub fn memoize_recognizer<F>(self, rule: RuleId, body: F) -> ParseState<S, ()> where
+   F: FnOnce(ParseState<S, ()>) -> ParseState<S, ()>
+  {
+    let start = self.current.clone();
+    let end = match self.memo_entry(rule, start.clone()) {
+      Some(&MemoEntry::Success(ref end, _)) => Some(end.clone()),
+      Some(&MemoEntry::Failure) => return self.failure(),
+      None => None
+    };
+    match end {
+      Some(end) => {
+        let mut state = self;
+        state.current = end;
+        state.success(())
+      }
+      None => {
+        let mut state = body(self);
+        let entry = if state.is_successful() { MemoEntry::Success(state.current.clone(), None) }
+                    else { MemoEntry::Failure };
+        state.memo_mut().insert(rule, start, entry);
+        state
+      }
+    }
+  }
+
+  /// Calls the parser `body` of the rule `rule` unless its result at the current position is already memoized.
+  /// The value is stored in the table as well, this is why its type must be `Clone` and `'static`.
+  /// A success memoized by the recognizer of the same rule has no value, so the parser is called again and the entry is replaced.
+  pub fn memoize_parser<T, F>(self, rule: RuleId, body: F) -> ParseState<S, T> where
+   T: Clone + 'static,
+   F: FnOnce(ParseState<S, ()>) -> ParseState<S, T>
+  {
+    let start = self.current.clone();
+    if self.has_memoized_value::<T>(rule, start.clone()) {
+      return self.memoized_parser(rule, start);
+    }
+    let state = body(self);
+    if state.is_successful() {
+      let (mut state, data) = state.extract_data();
+      let end = state.current.clone();
+      state.memo_mut().insert(rule, start, MemoEntry::Success(end, Some(Box::new(data.clone()))));
+      state.success(data)
+    }
+    else {
+      let mut state = state;
+      state.memo_mut().insert(rule, start, MemoEntry::Failure);
+      state
+    }
+  }
+}
+
+impl<S> ParseState<S, ()> where
+ S: Ord + Clone + HasNext
+{
+  /// Seed-growing algorithm of Warth et al. (2008) for the left-recursive recognizer `body` of the rule `rule`.
+  /// The memoized result starts as a failure (the seed), and `body` is called again as long as it consumes more input than the previous result; the recursive calls to `rule` at the same position read the previous result from the table.
+  pub fn grow_left_recursion_recognizer<F>(self, rule: RuleId, body: F) -> ParseState<S, ()> where
+   F: Fn(ParseState<S, ()>) -> ParseState<S, ()>
+  {
+    let start = self.current.clone();
+    if self.memo_entry(rule, start.clone()).is_some() {
+      return self.memoized_recognizer(rule, start);
+    }
+    let mut state = self;
+    state.memo_mut().insert(rule, start.clone(), MemoEntry::Failure);
+    loop {
+      let mut result = body(state);
+      result.discard_data();
+      let grown = result.is_successful() && result.consumes_more(rule, start.clone());
+      if grown {
+        let end = result.current.clone();
+        result.memo_mut().insert(rule, start.clone(), MemoEntry::Success(end, None));
+        state = result.restore(start.clone());
+      }
+      else {
+        return result.restore(start.clone()).memoized_recognizer(rule, start);
+      }
+    }
+  }
+
+  /// Same as `grow_left_recursion_recognizer` but the value of the seed is stored in the table as well.
+  /// This is why the type of the value must be `Clone` and `'static`.
+  pub fn grow_left_recursion_parser<T, F>(self, rule: RuleId, body: F) -> ParseState<S, T> where
+   T: Clone + 'static,
+   F: Fn(ParseState<S, ()>) -> ParseState<S, T>
+  {
+    let start = self.current.clone();
+    if self.has_memoized_value::<T>(rule, start.clone()) {
+      return self.memoized_parser(rule, start);
+    }
+    let mut state = self;
+    state.memo_mut().insert(rule, start.clone(), MemoEntry::Failure);
+    loop {
+      let result = body(state);
+      let grown = result.is_successful() && result.consumes_more(rule, start.clone());
+      if grown {
+        let (mut result, data) = result.extract_data();
+        let end = result.current.clone();
+        result.memo_mut().insert(rule, start.clone(), MemoEntry::Success(end, Some(Box::new(data))));
+        state = result.restore(start.clone());
+      }
+      else {
+        let result: ParseState<S, ()> = if result.is_successful() { result.extract_data().0 } else { result.failure() };
+        return result.restore(start.clone()).memoized_parser(rule, start);
+      }
+    }
+  }
+
+  fn memoized_recognizer(self, rule: RuleId, start: S) -> ParseState<S, ()> {
+    let end = match self.memo_entry(rule, start) {
+      Some(&MemoEntry::Success(ref end, _)) => end.clone(),
+      _ => return self.failure()
+    };
+    let mut state = self;
+    state.current = end;
+    state.success(())
+  }
+
+  fn memoized_parser<T>(self, rule: RuleId, start: S) -> ParseState<S, T> where
+   T: Clone + 'static
+  {
+    let (end, data) = match self.memo_entry(rule, start) {
+      Some(&MemoEntry::Success(ref end, Some(ref data))) => {
+        match data.downcast_ref::<T>() {
+          Some(data) => (end.clone(), data.clone()),
+          None => return self.failure()
+        }
+      }
+      _ => return self.failure()
+    };
+    let mut state = self;
+    state.current = end;
+    state.success(data)
+  }
+}
+
+impl<S, T> ParseState<S, T> where
+ S: Ord + Clone + HasNext
+{
+  fn memo_entry(&self, rule: RuleId, pos: S) -> Option<&MemoEntry<S>> {
+    self.memo().and_then(|memo| memo.get(rule, pos))
+  }
+
+  /// A failure or a success carrying a value of type `U` can be reused by the parser of `rule`.
+  /// Any other entry, such as a success recorded by the recognizer, is computed again.
+  fn has_memoized_value<U: 'static>(&self, rule: RuleId, pos: S) -> bool {
+    match self.memo_entry(rule, pos) {
+      Some(&MemoEntry::Success(_, Some(ref data))) => data.is::<U>(),
+      Some(&MemoEntry::Failure) => true,
+      _ => false
+    }
+  }
+
+  fn consumes_more(&self, rule: RuleId, start: S) -> bool {
+    match self.memo_entry(rule, start) {
+      Some(&MemoEntry::Success(ref end, _)) => self.current > *end,
+      _ => true
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use str_stream::*;
+
+  struct Grammar;
+  struct OtherGrammar;
+
+  fn rule(index: usize) -> RuleId {
+    RuleId::new::<Grammar>(index)
+  }
+
+  #[test]
+  fn test_memoize_recognizer() {
+    let state: ParseState<StrStream, ()> = "abc".into_state();
+    let start = state.mark();
+    let mut calls = 0;
+    let mut state = state.memoize_recognizer(rule(0), |mut s| {
+      calls += 1;
+      if s.consume_prefix("ab") { s.success(()) } else { s.failure() }
+    });
+    assert!(state.is_successful());
+    state.discard_data();
+    let state = state.restore(start);
+    let mut state = state.memoize_recognizer(rule(0), |s| { calls += 1; s.failure() });
+    assert!(state.is_successful());
+    assert_eq!(calls, 1);
+    assert_eq!(state.memo().map_or(0, |memo| memo.len()), 1);
+    assert!(state.consume_prefix("c"));
+  }
+
+  #[test]
+  fn test_memoize_parser_failure() {
+    let state: ParseState<StrStream, ()> = "abc".into_state();
+    let start = state.mark();
+    let mut calls = 0;
+    let state: ParseState<StrStream, char> = state.memoize_parser(rule(1), |s| { calls += 1; s.failure() });
+    assert!(state.is_failed());
+    let state = state.restore_from_failure(start);
+    let state: ParseState<StrStream, char> = state.memoize_parser(rule(1), |s| { calls += 1; s.success('a') });
+    assert!(state.is_failed());
+    assert_eq!(calls, 1);
+  }
+
+  #[test]
+  fn test_memoize_parser_success() {
+    let state: ParseState<StrStream, ()> = "abc".into_state();
+    let start = state.mark();
+    let mut calls = 0;
+    let state: ParseState<StrStream, String> = state.memoize_parser(rule(1), |mut s| {
+      calls += 1;
+      if s.consume_prefix("ab") { s.success(format!("ab")) } else { s.failure() }
+    });
+    let (state, data) = state.extract_data();
+    assert_eq!(data, "ab");
+    let state = state.restore(start);
+    let state: ParseState<StrStream, String> = state.memoize_parser(rule(1), |s| { calls += 1; s.failure() });
+    assert_eq!(calls, 1);
+    let (mut state, data) = state.extract_data();
+    assert_eq!(data, "ab");
+    assert!(state.consume_prefix("c"));
+  }
+
+  // expr = expr "+" "1" / "1"
+  fn recognize_expr<'a>(state: ParseState<StrStream<'a>, ()>) -> ParseState<StrStream<'a>, ()> {
+    state.grow_left_recursion_recognizer(rule(0), |state| {
+      let mark = state.mark();
+      let mut state = recognize_expr(state);
+      if state.is_successful() {
+        state.discard_data();
+        if state.consume_prefix("+") && state.consume_prefix("1") {
+          return state.success(());
+        }
+        state.failed = true;
+      }
+      let mut state = state.restore_from_failure(mark);
+      if state.consume_prefix("1") { state.success(()) } else { state.failure() }
+    })
+  }
+
+  // expr = expr "+" "1" > add / "1" > one
+  fn parse_expr<'a>(state: ParseState<StrStream<'a>, ()>) -> ParseState<StrStream<'a>, usize> {
+    state.grow_left_recursion_parser(rule(1), |state| {
+      let mark = state.mark();
+      let state = parse_expr(state);
+      if state.is_successful() {
+        let (mut state, value) = state.extract_data();
+        if state.consume_prefix("+") && state.consume_prefix("1") {
+          return state.success(value + 1);
+        }
+        state.failed = true;
+        let mut state = state.restore_from_failure(mark);
+        return if state.consume_prefix("1") { state.success(1) } else { state.failure() };
+      }
+      let mut state = state.restore_from_failure(mark);
+      if state.consume_prefix("1") { state.success(1) } else { state.failure() }
+    })
+  }
+
+  #[test]
+  fn test_grow_left_recursion_recognizer() {
+    let state = recognize_expr("1+1+1".into_state());
+    assert!(state.is_successful());
+    assert!(!state.current.has_next());
+    let state = recognize_expr("+1".into_state());
+    assert!(state.is_failed());
+  }
+
+  #[test]
+  fn test_grow_left_recursion_parser() {
+    let state = parse_expr("1+1+1+1".into_state());
+    assert!(!state.current.has_next());
+    assert_eq!(state.unwrap_data(), 4);
+  }
+
+  #[test]
+  fn test_memoize_rule_of_other_grammar() {
+    let state: ParseState<StrStream, ()> = "abc".into_state();
+    let start = state.mark();
+    let state: ParseState<StrStream, char> = state.memoize_parser(rule(0), |mut s| {
+      if s.consume_prefix("a") { s.success('a') } else { s.failure() }
+    });
+    let state = state.extract_data().0.restore(start);
+    let mut calls = 0;
+    let state: ParseState<StrStream, String> = state.memoize_parser(RuleId::new::<OtherGrammar>(0), |mut s| {
+      calls += 1;
+      if s.consume_prefix("ab") { s.success(format!("ab")) } else { s.failure() }
+    });
+    assert_eq!(calls, 1);
+    assert_eq!(state.unwrap_data(), "ab");
+  }
+
+  #[test]
+  fn test_memoize_parser_other_type() {
+    let state: ParseState<StrStream, ()> = "abc".into_state();
+    let start = state.mark();
+    let state: ParseState<StrStream, char> = state.memoize_parser(rule(0), |mut s| {
+      if s.consume_prefix("a") { s.success('a') } else { s.failure() }
+    });
+    let state = state.extract_data().0.restore(start);
+    let mut calls = 0;
+    let state: ParseState<StrStream, usize> = state.memoize_parser(rule(0), |mut s| {
+      calls += 1;
+      if s.consume_prefix("a") { s.success(1) } else { s.failure() }
+    });
+    assert_eq!(calls, 1);
+    assert_eq!(state.unwrap_data(), 1);
+  }
+}
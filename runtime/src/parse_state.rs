@@ -15,10 +15,13 @@
 //! A parsing state indicates the current status of the parsing. It is mainly used by compiled PEG combinators.
 
 use stream::*;
+use memo::*;
 use self::ParseResult::*;
-use std::collections::hash_set::HashSet;
-use std::cmp::Ord;
-use std::fmt::{Formatter, Debug, Error};
+use std::collections::BTreeSet;
+use std::cmp::{Ord, min};
+use std::fmt::{Formatter, Debug, Display, Error};
+#[cfg(not(feature = "std"))]
+use alloc_prelude::*;
 
 pub trait IntoState<S, T>
 {
@@ -34,10 +37,13 @@ impl<S, T, R> IntoState<S, T> for R where
   }
 }
 
+#[derive(Clone)]
 pub struct ParseExpectation<S>
 {
-  expected: HashSet<&'static str>,
-  farthest_read: S
+  expected: BTreeSet<&'static str>,
+  farthest_read: S,
+  /// The errors returned by the semantic actions that failed at `farthest_read`.
+  action_errors: Vec<String>
 }
 
 impl<S> ParseExpectation<S>
@@ -45,9 +51,14 @@ impl<S> ParseExpectation<S>
   pub fn new(farthest_read: S, expected: Vec<&'static str>) -> ParseExpectation<S> {
     ParseExpectation {
       expected: expected.into_iter().collect(),
-      farthest_read: farthest_read
+      farthest_read: farthest_read,
+      action_errors: vec![]
     }
   }
+
+  fn is_empty(&self) -> bool {
+    self.expected.is_empty() && self.action_errors.is_empty()
+  }
 }
 
 impl<S> ParseExpectation<S> where
@@ -66,6 +77,34 @@ impl<S> ParseExpectation<S> where
     }
     desc
   }
+
+  /// The errors of the semantic actions come first, for example ```division by zero, unexpected `)`, expecting `["0-9"]`.```
+  fn description(&self) -> String {
+    let unexpected = format!("unexpected `{}`, expecting {}.",
+      self.farthest_read.code_snippet(10usize), self.expected_items());
+    if self.action_errors.is_empty() {
+      unexpected
+    }
+    else if self.expected.is_empty() {
+      format!("{}.", self.action_errors.join(", "))
+    }
+    else {
+      format!("{}, {}", self.action_errors.join(", "), unexpected)
+    }
+  }
+}
+
+impl<S> ParseExpectation<S> where
+ S: Location + CodeSnippet + ByteOffset
+{
+  pub fn into_error(self) -> ParseError {
+    let message = self.description();
+    ParseError {
+      location: self.farthest_read.location(),
+      offset: self.farthest_read.byte_offset(),
+      message: message
+    }
+  }
 }
 
 /// Prints an error message of the form: ```1:1: unexpected `a+1`, expecting `(` or `["0-9"]`.``` where `1:1` is the line and the column where the error occurred.
@@ -74,13 +113,99 @@ impl<S> Debug for ParseExpectation<S> where
 {
   fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
     let location = self.farthest_read.location();
-    let expected = self.expected_items();
-    let snippet = self.farthest_read.code_snippet(10usize);
     formatter.write_fmt(
-      format_args!("{}: unexpected `{}`, expecting {}.", location, snippet, expected))
+      format_args!("{}: {}", location, self.description()))
+  }
+}
+
+/// Error of a parsing that failed or that did not consume the full input.
+/// It is returned by the implementations of `FromStr` generated with `#![derive_from_str(r)]`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError
+{
+  /// Position of the error in the stream, such as `1:3` for the line 1 and column 3.
+  pub location: String,
+  /// Number of bytes from the beginning of the input to the position of the error.
+  pub offset: usize,
+  /// Description of the error, such as ```unexpected `a`, expecting `["0-9"]`.```
+  pub message: String
+}
+
+impl ParseError
+{
+  /// Computes the line and the column (both starting at 1) of the error in `input`, which must be the parsed input.
+  /// Columns are counted in characters, and an error at the end of the input is located just after its last character.
+  pub fn line_col(&self, input: &str) -> (usize, usize) {
+    let offset = self.offset_in(input);
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+  }
+
+  /// Displays the error followed by the line of `input` where it occurred and a caret `^` under its column, `input` must be the parsed input:
+  ///
+  /// ```text
+  /// 2:5: unexpected `;`, expecting `["0-9"]`.
+  ///   |
+  /// 2 | x = ;
+  ///   |     ^
+  /// ```
+  pub fn excerpt<'a>(&'a self, input: &'a str) -> ErrorExcerpt<'a> {
+    ErrorExcerpt { error: self, input }
+  }
+
+  /// The offset of the error in `input`, at the start of the character if it is in the middle of one.
+  fn offset_in(&self, input: &str) -> usize {
+    let mut offset = min(self.offset, input.len());
+    while !input.is_char_boundary(offset) {
+      offset -= 1;
+    }
+    offset
   }
 }
 
+/// An error displayed with the line of the input where it occurred, see `ParseError::excerpt`.
+pub struct ErrorExcerpt<'a>
+{
+  error: &'a ParseError,
+  input: &'a str
+}
+
+impl<'a> Display for ErrorExcerpt<'a>
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    let (line, column) = self.error.line_col(self.input);
+    let offset = self.error.offset_in(self.input);
+    let line_start = self.input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = self.input[offset..].find('\n').map_or(self.input.len(), |i| offset + i);
+    let text = self.input[line_start..line_end].trim_end_matches('\r');
+    let mut width = 1;
+    let mut n = line;
+    while n >= 10 {
+      n /= 10;
+      width += 1;
+    }
+    formatter.write_fmt(format_args!("{}\n{:width$} |\n{} | {}\n{:width$} | ", self.error, "", line, text, "", width = width))?;
+    // The tabulations are kept so the caret is aligned with the column.
+    for c in text.chars().take(column - 1) {
+      formatter.write_str(if c == '\t' { "\t" } else { " " })?;
+    }
+    formatter.write_str("^")
+  }
+}
+
+impl Display for ParseError
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    formatter.write_fmt(format_args!("{}: {}", self.location, self.message))
+  }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ParseError {}
+
 pub enum ParseResult<S, T>
 {
   Success(T),
@@ -124,7 +249,39 @@ pub struct ParseState<S, T>
   /// The current stream that can be partially or fully consumed.
   pub current: S,
   /// Contains the AST if the current state is successful and `None` if it is erroneous.
-  pub data: Option<T>
+  pub data: Option<T>,
+  /// The parts of the state only used by some attributes of the grammar, see `StateExtensions`.
+  extensions: Option<Box<StateExtensions<S>>>
+}
+
+/// The parts of the state that are only used by some attributes of the grammar, such as the memoization table of `#![packrat]`.
+/// They are allocated the first time they are needed, so the other grammars only pay for an empty pointer.
+struct StateExtensions<S>
+{
+  /// Results of the rules already called, only filled by memoized rules.
+  memo: MemoTable<S>,
+  /// True if a cut operator `~` has been crossed in the current branch of the enclosing choice, which must not try its next branches.
+  cut: bool,
+  /// Number of rules being called, only counted with `#![depth_limit]`.
+  depth: usize,
+  /// The errors returned by the semantic actions that failed at `farthest_read`, they are reported along with `expected`.
+  action_errors: Vec<String>,
+  /// The errors of the expressions recovered by `recover(e, sync)`, in the order they occurred, along with the position where the expression started.
+  recovered: Vec<(S, ParseExpectation<S>)>
+}
+
+impl<S> StateExtensions<S> where
+ S: Ord
+{
+  fn new() -> StateExtensions<S> {
+    StateExtensions {
+      memo: MemoTable::new(),
+      cut: false,
+      depth: 0,
+      action_errors: vec![],
+      recovered: vec![]
+    }
+  }
 }
 
 impl<S, T> ParseState<S, T> where
@@ -137,10 +294,38 @@ impl<S, T> ParseState<S, T> where
       expected: vec![],
       failed: false,
       current: stream,
-      data: None
+      data: None,
+      extensions: None
     }
   }
 
+  /// The extensions of the state, allocated on the first call.
+  fn extensions(&mut self) -> &mut StateExtensions<S> {
+    self.extensions.get_or_insert_with(|| Box::new(StateExtensions::new()))
+  }
+
+  /// The memoization table, `None` if no rule has been memoized yet.
+  pub fn memo(&self) -> Option<&MemoTable<S>> {
+    self.extensions.as_ref().map(|extensions| &extensions.memo)
+  }
+
+  pub(crate) fn memo_mut(&mut self) -> &mut MemoTable<S> {
+    &mut self.extensions().memo
+  }
+
+  /// True if a cut `~` has been crossed in the current branch of the enclosing choice.
+  pub fn is_cut(&self) -> bool {
+    self.extensions.as_ref().map_or(false, |extensions| extensions.cut)
+  }
+
+  /// Grammars without cut never allocate the extensions, since they only reset the flag.
+  pub fn set_cut(&mut self, cut: bool) {
+    if cut || self.extensions.is_some() {
+      self.extensions().cut = cut;
+    }
+  }
+
+  /// True if the last expression failed, the state must then be restored with `restore_from_failure` before parsing further.
   pub fn is_failed(&self) -> bool {
     self.failed
   }
@@ -149,18 +334,128 @@ impl<S, T> ParseState<S, T> where
     !self.is_failed()
   }
 
+  /// Fails the state at the current position, `expect` describes what was expected there and is reported if no error occurs farther in the input.
   #[inline(always)]
   pub fn error(&mut self, expect: &'static str) {
     self.failed = true;
     if self.current > self.farthest_read {
       self.farthest_read = self.current.clone();
-      self.expected = vec![expect];
+      // Reuse the buffer of the previous expected items to avoid an allocation.
+      self.expected.clear();
+      self.expected.push(expect);
+      self.clear_action_errors();
     }
     else if self.current == self.farthest_read {
       self.expected.push(expect);
     }
   }
 
+  /// Number of items expected at the current position, the items expected by an expression starting at this position are added after them.
+  pub fn expected_count(&self) -> usize {
+    if self.current == self.farthest_read { self.expected.len() } else { 0 }
+  }
+
+  /// Labels the failure of an expression started at `start`, used by `e^"label"`.
+  /// If the expression did not read further than `start`, the items it expected (at the index `expected_from` and after) are replaced by `label`, otherwise its errors are kept since they are farther.
+  pub fn label_failure(&mut self, start: S, expected_from: usize, label: &'static str) {
+    self.failed = true;
+    if start > self.farthest_read {
+      self.farthest_read = start;
+      self.expected.clear();
+      self.expected.push(label);
+      self.clear_action_errors();
+    }
+    else if start == self.farthest_read {
+      self.expected.truncate(expected_from);
+      self.expected.push(label);
+    }
+  }
+
+  /// Fails the expression whose semantic action returned an error, the items expected so far are kept.
+  pub fn action_failure(&mut self) {
+    self.failed = true;
+  }
+
+  /// Fails the expression whose semantic action returned `Err(error)`, the error is reported as the items expected at the current position.
+  pub fn action_error<E: Display>(&mut self, error: E) {
+    self.failed = true;
+    if self.current > self.farthest_read {
+      self.farthest_read = self.current.clone();
+      self.expected.clear();
+      self.clear_action_errors();
+    }
+    if self.current == self.farthest_read {
+      self.extensions().action_errors.push(format!("{}", error));
+    }
+  }
+
+  fn clear_action_errors(&mut self) {
+    if let Some(ref mut extensions) = self.extensions {
+      extensions.action_errors.clear();
+    }
+  }
+
+  /// The errors encountered so far, saved by `recover(e, sync)` when `e` fails since the items expected while skipping the input are not relevant.
+  pub fn expectation(&self) -> ParseExpectation<S> {
+    let mut expectation = ParseExpectation::new(self.farthest_read.clone(), self.expected.clone());
+    if let Some(ref extensions) = self.extensions {
+      expectation.action_errors = extensions.action_errors.clone();
+    }
+    expectation
+  }
+
+  /// Enters a rule with `#![depth_limit]`, it fails the state and returns `false` if `limit` rules are already being called.
+  /// It prevents the stack from overflowing on deeply nested inputs.
+  pub fn enter_rule(&mut self, limit: usize) -> bool {
+    if self.extensions().depth >= limit {
+      self.error("a shallower nesting (recursion limit exceeded)");
+      false
+    }
+    else {
+      self.extensions().depth += 1;
+      true
+    }
+  }
+
+  /// Exits a rule entered with `enter_rule`.
+  pub fn exit_rule(&mut self) {
+    self.extensions().depth -= 1;
+  }
+
+  /// Replaces the errors of the state by `expectation`.
+  fn set_expectation(&mut self, expectation: ParseExpectation<S>) {
+    self.farthest_read = expectation.farthest_read;
+    self.expected = expectation.expected.into_iter().collect();
+    if !expectation.action_errors.is_empty() || self.extensions.is_some() {
+      self.extensions().action_errors = expectation.action_errors;
+    }
+  }
+
+  /// Records the error `expectation` of the expression started at `start` and recovered by `recover(e, sync)`.
+  /// The errors of the expression and of the skipped input are replaced by the errors `outer` encountered before `start`, so the next errors are merged with them as if the recovered input had been successfully parsed.
+  /// The error is discarded if the state is restored to a position before `start`, for instance when an enclosing choice backtracks.
+  pub fn recover(&mut self, start: S, outer: ParseExpectation<S>, expectation: ParseExpectation<S>) {
+    self.extensions().recovered.push((start, expectation));
+    self.set_expectation(outer);
+  }
+
+  /// Fails with the errors of `expectation`, used by `recover(e, sync)` when no input can be skipped after the failure of `e`.
+  pub fn expectation_failure(&mut self, expectation: ParseExpectation<S>) {
+    self.failed = true;
+    self.set_expectation(expectation);
+  }
+
+  /// The errors recovered by `recover(e, sync)` during the parsing, even if the parsing eventually succeeded.
+  pub fn recovered_errors(&self) -> Vec<ParseError> where
+   S: Location + CodeSnippet + ByteOffset
+  {
+    self.extensions.iter()
+      .flat_map(|extensions| extensions.recovered.iter())
+      .map(|&(_, ref expectation)| expectation.clone().into_error())
+      .collect()
+  }
+
+  /// Succeeds with the value `data` at the current position.
   // TODO: find a way to specialize success when U = T.
   #[inline]
   pub fn success<U>(self, data: U) -> ParseState<S, U> {
@@ -169,10 +464,12 @@ impl<S, T> ParseState<S, T> where
       expected: self.expected,
       failed: false,
       current: self.current,
-      data: Some(data)
+      data: Some(data),
+      extensions: self.extensions
     }
   }
 
+  /// Changes the type of a state after a failure, the position and the errors are kept.
   #[inline]
   pub fn failure<U>(self) -> ParseState<S, U> {
     ParseState {
@@ -180,34 +477,60 @@ impl<S, T> ParseState<S, T> where
       expected: self.expected,
       failed: true,
       current: self.current,
-      data: None
+      data: None,
+      extensions: self.extensions
     }
   }
 
+  /// Returns a savepoint at the current position of a successful state, which can be given to `restore` or `restore_from_failure` to go back to this position.
+  /// The stream is cheap to clone (it is only a position in the input), and it can also be compared to another mark to know how far the parsing went.
   pub fn mark(&self) -> S {
     assert!(!self.failed, "Marking a failed ParseState is not allowed.");
     self.current.clone()
   }
 
+  /// True if the current position is after the savepoint `mark`, even if the state failed.
+  /// It tells whether an expression started at `mark` consumed input before failing, which is used by `committed(e)`.
+  pub fn consumed_since(&self, mark: &S) -> bool {
+    self.current > *mark
+  }
+
+  /// Goes back to the savepoint `mark` after a failure, the state becomes successful.
+  /// The errors encountered are kept to be reported if the parsing fails later.
+  /// It takes a constant time whatever the input consumed since `mark`: the values of the failed branch are not stored in the state but in the variables of the generated code, which are dropped with the branch (`benches/restore.rs`).
   pub fn restore_from_failure(self, mark: S) -> ParseState<S, ()> {
     assert!(self.failed, "Restoring a successful ParseState is not allowed.");
     self.restore(mark)
   }
 
-  pub fn restore(self, mark: S) -> ParseState<S, ()> {
+  /// Goes back to the savepoint `mark`, the state must not contain data (see `discard_data`).
+  /// It is useful for lookahead: reading the input and then going back to the position before it.
+  pub fn restore(mut self, mark: S) -> ParseState<S, ()> {
     assert!(self.data.is_none(), "Restoring a ParseState with data is not allowed.");
+    self.discard_recovered_since(&mark);
     ParseState {
       farthest_read: self.farthest_read,
       expected: self.expected,
       failed: false,
       current: mark,
-      data: None
+      data: None,
+      extensions: self.extensions
+    }
+  }
+
+  /// The errors recovered after `mark` belong to the input parsed again from `mark`.
+  /// Recovered expressions consume input and do not overlap, so their starting positions are increasing.
+  fn discard_recovered_since(&mut self, mark: &S) {
+    if let Some(ref mut extensions) = self.extensions {
+      while extensions.recovered.last().map_or(false, |&(ref start, _)| start >= mark) {
+        extensions.recovered.pop();
+      }
     }
   }
 
   /// Transforms `self` into a more usable `ParseResult` value. It is useful when the state is terminal or if the state will not be further transformed.
   pub fn into_result(self) -> ParseResult<S, T> {
-    let expectation = ParseExpectation::new(self.farthest_read, self.expected);
+    let expectation = self.expectation();
     match self.data {
       Some(data) => {
         if self.current.has_next() {
@@ -224,6 +547,42 @@ impl<S, T> ParseState<S, T> where
     }
   }
 
+  /// Returns the data if the full input has been consumed, and an error otherwise.
+  pub fn into_full_result(self) -> Result<T, ParseError> where
+   S: Location + CodeSnippet + ByteOffset
+  {
+    let current = self.current.clone();
+    match self.into_result() {
+      Success(data) => Ok(data),
+      Partial(_, expectation) => {
+        // The errors before the current position are not relevant since the data has been successfully parsed until there.
+        if expectation.is_empty() || expectation.farthest_read < current {
+          Err(ParseError {
+            location: current.location(),
+            offset: current.byte_offset(),
+            message: format!("unexpected `{}`, expecting the end of input.", current.code_snippet(10usize))
+          })
+        }
+        else {
+          Err(expectation.into_error())
+        }
+      }
+      Failure(expectation) => Err(expectation.into_error())
+    }
+  }
+
+  /// Returns the data with the number of bytes consumed if the state is successful, even if the input is not entirely consumed, and an error otherwise.
+  pub fn into_partial_result(self) -> Result<(T, usize), ParseError> where
+   S: Location + CodeSnippet + ByteOffset
+  {
+    let consumed = self.current.byte_offset();
+    match self.into_result() {
+      Success(data)
+    | Partial(data, _) => Ok((data, consumed)),
+      Failure(expectation) => Err(expectation.into_error())
+    }
+  }
+
   pub fn extract_data(self) -> (ParseState<S, ()>, T) {
     assert!(self.is_successful() && self.data.is_some(),
       "Data extraction is only possible if the state is successful and contains data.");
@@ -233,7 +592,8 @@ impl<S, T> ParseState<S, T> where
       expected: self.expected,
       failed: self.failed,
       current: self.current,
-      data: None
+      data: None,
+      extensions: self.extensions
     };
     (state, data)
   }
@@ -245,12 +605,57 @@ impl<S, T> ParseState<S, T> where
 
 impl<S> ParseState<S, ()>
 {
-  // This is specific to recognizer where unit data does not need to be extracted. We also want to preserve the "no-data" precondition of `restore`.
+  /// This is specific to recognizer where unit data does not need to be extracted. We also want to preserve the "no-data" precondition of `restore`.
   pub fn discard_data(&mut self) {
     self.data = None;
   }
 }
 
+/// Iterator over the values of a rule matched one after another from the start of the input, generated as `iter_parse_r` for each start rule `r`.
+/// Each call to `next` parses one more record from the position reached by the previous one, so a large input (e.g. a log with a record per line) is processed lazily.
+/// The iteration ends at the end of input, or after yielding the error of a record that failed, since the position of the next record is then unknown.
+pub struct ParseAll<S, T>
+{
+  /// The position of the next record, `None` once the iteration ended.
+  current: Option<S>,
+  parser: fn(ParseState<S, ()>) -> ParseState<S, T>
+}
+
+impl<S, T> ParseAll<S, T>
+{
+  pub fn new(state: ParseState<S, ()>, parser: fn(ParseState<S, ()>) -> ParseState<S, T>) -> ParseAll<S, T> {
+    ParseAll { current: Some(state.current), parser }
+  }
+}
+
+impl<S, T> Iterator for ParseAll<S, T> where
+ S: Ord + Clone + HasNext + Location + CodeSnippet + ByteOffset
+{
+  type Item = Result<T, ParseError>;
+  fn next(&mut self) -> Option<Self::Item> {
+    let start = self.current.take()?;
+    if !start.has_next() {
+      return None;
+    }
+    let state = (self.parser)(ParseState::new(start.clone()));
+    if state.is_failed() {
+      Some(Err(state.expectation().into_error()))
+    }
+    // A record matching the empty input would be matched again forever.
+    else if state.current == start {
+      Some(Err(ParseError {
+        location: start.location(),
+        offset: start.byte_offset(),
+        message: format!("unexpected `{}`, the record matched no input.", start.code_snippet(10usize))
+      }))
+    }
+    else {
+      self.current = Some(state.current.clone());
+      Some(Ok(state.unwrap_data()))
+    }
+  }
+}
+
 impl<S, T, I> Iterator for ParseState<S, T> where
  S: Iterator<Item=I>
 {
@@ -267,3 +672,191 @@ impl<S, T, P> ConsumePrefix<P> for ParseState<S, T> where
     self.current.consume_prefix(prefix)
   }
 }
+
+impl<S, T, P> ConsumePrefixIgnoreAsciiCase<P> for ParseState<S, T> where
+  S: ConsumePrefixIgnoreAsciiCase<P>
+{
+  fn consume_prefix_ignore_ascii_case(&mut self, prefix: P) -> bool {
+    self.current.consume_prefix_ignore_ascii_case(prefix)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use str_stream::*;
+
+  fn error_at(offset: usize) -> ParseError {
+    ParseError {
+      location: String::new(),
+      offset: offset,
+      message: String::new()
+    }
+  }
+
+  #[test]
+  fn test_line_col() {
+    let input = "ab\nc\n\nd";
+    assert_eq!(error_at(0).line_col(input), (1, 1));
+    assert_eq!(error_at(2).line_col(input), (1, 3));
+    assert_eq!(error_at(3).line_col(input), (2, 1));
+    assert_eq!(error_at(6).line_col(input), (4, 1));
+    // At the end of input.
+    assert_eq!(error_at(7).line_col(input), (4, 2));
+    assert_eq!(error_at(7).line_col("ab\n"), (2, 1));
+    assert_eq!(error_at(0).line_col(""), (1, 1));
+  }
+
+  #[test]
+  fn test_excerpt() {
+    let mut error = error_at(11);
+    error.location = format!("2:5");
+    error.message = format!("unexpected `;`.");
+    assert_eq!(format!("{}", error.excerpt("let y;\nx = ;\n")),
+      "2:5: unexpected `;`.\n  |\n2 | x = ;\n  |     ^");
+    // At the end of input, on an empty line and after a tabulation.
+    assert_eq!(format!("{}", error_at(2).excerpt("ab")), ": \n  |\n1 | ab\n  |   ^");
+    assert_eq!(format!("{}", error_at(3).excerpt("ab\n")), ": \n  |\n2 | \n  | ^");
+    assert_eq!(format!("{}", error_at(3).excerpt("a\n\n\nb")), ": \n  |\n3 | \n  | ^");
+    assert_eq!(format!("{}", error_at(2).excerpt("\tab\r\n")), ": \n  |\n1 | \tab\n  | \t ^");
+    let input = "\n".repeat(11);
+    assert!(format!("{}", error_at(10).excerpt(&input)).ends_with("\n   |\n11 | \n   | ^"));
+  }
+
+  #[test]
+  fn test_line_col_multibyte() {
+    // Columns are counted in characters and not in bytes.
+    let input = "é\nxé=";
+    assert_eq!(error_at(6).line_col(input), (2, 3));
+  }
+
+  #[test]
+  fn test_into_full_result_offset() {
+    let state: ParseState<StrStream, ()> = "a\nb".into_state();
+    let mut state = state.success(());
+    state.consume_prefix("a\n");
+    let error = state.into_full_result().unwrap_err();
+    assert_eq!(error.offset, 2);
+    assert_eq!(error.location, format!("2:1"));
+    assert_eq!(error.line_col("a\nb"), (2, 1));
+  }
+
+  fn digit(mut state: ParseState<StrStream<'static>, ()>) -> ParseState<StrStream<'static>, char> {
+    match state.current.clone().next() {
+      Some(c) if c.is_digit(10) => {
+        state.next();
+        state.success(c)
+      }
+      _ => {
+        state.error("a digit");
+        state.failure()
+      }
+    }
+  }
+
+  fn nothing(state: ParseState<StrStream<'static>, ()>) -> ParseState<StrStream<'static>, ()> {
+    state.success(())
+  }
+
+  #[test]
+  fn test_parse_all() {
+    let records: Vec<_> = ParseAll::new("123".into_state(), digit).collect();
+    assert_eq!(records, vec![Ok('1'), Ok('2'), Ok('3')]);
+    assert_eq!(ParseAll::new("".into_state(), digit).next(), None);
+    let mut records = ParseAll::new("1a2".into_state(), digit);
+    assert_eq!(records.next(), Some(Ok('1')));
+    let error = records.next().unwrap().unwrap_err();
+    assert_eq!(error.offset, 1);
+    assert_eq!(records.next(), None);
+    let mut records = ParseAll::new("a".into_state(), nothing);
+    assert_eq!(records.next().unwrap().unwrap_err().offset, 0);
+    assert_eq!(records.next(), None);
+  }
+
+  #[test]
+  fn test_consumed_since() {
+    let mut state: ParseState<StrStream, ()> = "ab".into_state();
+    let mark = state.mark();
+    state.error("`b`");
+    assert!(!state.consumed_since(&mark));
+    let mut state = state.restore_from_failure(mark.clone());
+    state.consume_prefix("a");
+    state.error("`c`");
+    assert!(state.consumed_since(&mark));
+  }
+
+  #[test]
+  fn test_action_error() {
+    let mut state: ParseState<StrStream, ()> = "1/0;".into_state();
+    state.consume_prefix("1/0");
+    let end = state.mark();
+    state.error("+");
+    let mut state = state.restore_from_failure(end.clone());
+    state.action_error("division by zero");
+    assert_eq!(state.expectation().into_error().message,
+      format!("division by zero, unexpected `;`, expecting `+`."));
+    // A farther error replaces the error of the action.
+    let mut state = state.restore_from_failure(end);
+    state.consume_prefix(";");
+    state.error("x");
+    assert_eq!(state.expectation().into_error().message,
+      format!("unexpected `<end-of-file>`, expecting `x`."));
+  }
+
+  #[test]
+  fn test_recover() {
+    let mut state: ParseState<StrStream, ()> = "x;y".into_state();
+    let start = state.mark();
+    let outer = state.expectation();
+    state.error("a statement");
+    let expectation = state.expectation();
+    let mut state = state.restore_from_failure(start.clone());
+    state.consume_prefix("x;");
+    state.recover(start, outer, expectation);
+    assert!(state.expected.is_empty());
+    let errors = state.recovered_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].offset, 0);
+    assert_eq!(errors[0].message, format!("unexpected `x;y`, expecting `a statement`."));
+  }
+
+  #[test]
+  fn test_recover_keeps_outer_errors() {
+    let mut state: ParseState<StrStream, ()> = "x;y".into_state();
+    let start = state.mark();
+    state.error("`z`");
+    let mut state = state.restore_from_failure(start.clone());
+    let outer = state.expectation();
+    state.error("a statement");
+    let expectation = state.expectation();
+    let mut state = state.restore_from_failure(start.clone());
+    state.consume_prefix("x;");
+    state.recover(start, outer, expectation);
+    assert_eq!(state.expected, vec!["`z`"]);
+    state.error("`w`");
+    assert_eq!(state.expected, vec!["`w`"]);
+  }
+
+  #[test]
+  fn test_recover_in_backtracked_branch() {
+    let mut state: ParseState<StrStream, ()> = "x;y;".into_state();
+    let choice = state.mark();
+    state.consume_prefix("x;");
+    let start = state.mark();
+    let outer = state.expectation();
+    state.error("a statement");
+    let expectation = state.expectation();
+    let mut state = state.restore_from_failure(start.clone());
+    state.consume_prefix("y;");
+    state.recover(start, outer, expectation);
+    // Backtracking after the recovered expression keeps its error.
+    let end = state.mark();
+    state.error("`!`");
+    let mut state = state.restore_from_failure(end);
+    assert_eq!(state.recovered_errors().len(), 1);
+    // Backtracking before it discards the error.
+    state.error("`!`");
+    let state = state.restore_from_failure(choice);
+    assert!(state.recovered_errors().is_empty());
+  }
+}
@@ -0,0 +1,246 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of a stream over a slice `&'a [T]` of atoms that are not characters, for example the tokens produced by a lexer.
+//! The grammar declares the type of the atoms with `type Atom = T;`, a string literal matches exactly one atom (see `MatchLiteral`) and `token(pattern)` matches an atom with a Rust pattern.
+
+use stream::*;
+use std::cmp::{Ordering, min};
+use std::fmt::Debug;
+pub use std::ops::Range;
+#[cfg(not(feature = "std"))]
+use alloc_prelude::*;
+
+/// An atom matched by the string literals of a grammar, for example the literal `"+"` can match the token `Token::Plus`.
+pub trait MatchLiteral
+{
+  fn match_literal(&self, literal: &str) -> bool;
+}
+
+/// Represents a stream from a `&'a [T]`, the atoms are cloned when they are read.
+#[derive(Debug)]
+pub struct SliceStream<'a, T: 'a>
+{
+  atoms: &'a [T],
+  offset: usize
+}
+
+impl<'a, T> SliceStream<'a, T>
+{
+  pub fn new(atoms: &'a [T]) -> SliceStream<'a, T> {
+    SliceStream {
+      atoms: atoms,
+      offset: 0
+    }
+  }
+
+  #[inline(always)]
+  fn assert_same_atoms(&self, other: &SliceStream<'a, T>) {
+    debug_assert!(self.atoms.as_ptr() == other.atoms.as_ptr(),
+      "Operations between two streams are only defined when they share the same atoms.");
+  }
+
+  /// The index of the current atom in the slice.
+  pub fn atoms_offset(&self) -> usize {
+    self.offset
+  }
+
+  pub fn current_atom(&self) -> Option<&'a T> {
+    self.atoms.get(self.offset)
+  }
+
+  pub fn slice(&self, end: SliceStream<'a, T>) -> &'a [T] {
+    &self.atoms[self.offset..end.offset]
+  }
+}
+
+impl<'a, T> Clone for SliceStream<'a, T>
+{
+  fn clone(&self) -> Self {
+    SliceStream {
+      atoms: self.atoms,
+      offset: self.offset
+    }
+  }
+}
+
+impl<'a, T> Stream for SliceStream<'a, T>
+{
+  type Output = SliceStream<'a, T>;
+  fn stream(self) -> SliceStream<'a, T> {
+    self
+  }
+}
+
+impl<'a, T> Iterator for SliceStream<'a, T> where
+ T: Clone
+{
+  type Item = T;
+  fn next(&mut self) -> Option<Self::Item> {
+    let current = self.current_atom().cloned();
+    if current.is_some() {
+      self.offset += 1;
+    }
+    current
+  }
+}
+
+impl<'a, T> PartialEq for SliceStream<'a, T>
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.assert_same_atoms(other);
+    self.offset == other.offset
+  }
+}
+
+impl<'a, T> Eq for SliceStream<'a, T> {}
+
+impl<'a, T> PartialOrd for SliceStream<'a, T>
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<'a, T> Ord for SliceStream<'a, T>
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.assert_same_atoms(other);
+    self.offset.cmp(&other.offset)
+  }
+}
+
+impl<'a, T> Location for SliceStream<'a, T>
+{
+  fn location(&self) -> String {
+    format!("token {}", self.offset)
+  }
+}
+
+/// There is no byte in a slice of atoms, the offset is the index of the current atom.
+impl<'a, T> ByteOffset for SliceStream<'a, T>
+{
+  fn byte_offset(&self) -> usize {
+    self.offset
+  }
+}
+
+/// The snippet contains at most `len_hint` atoms.
+impl<'a, T> CodeSnippet for SliceStream<'a, T> where
+ T: Debug
+{
+  fn code_snippet(&self, len_hint: usize) -> String {
+    let total_len = self.atoms.len();
+    if self.offset == total_len {
+      String::from("<end-of-file>")
+    }
+    else {
+      let len = min(total_len - self.offset, len_hint);
+      self.atoms[self.offset..][..len].iter()
+        .map(|atom| format!("{:?}", atom))
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+  }
+}
+
+/// A non-empty literal matches the current atom, the empty literal always matches without consuming an atom.
+impl<'a, T> ConsumePrefix<&'static str> for SliceStream<'a, T> where
+ T: MatchLiteral
+{
+  fn consume_prefix(&mut self, prefix: &'static str) -> bool {
+    if prefix.is_empty() {
+      true
+    }
+    else if self.current_atom().map_or(false, |atom| atom.match_literal(prefix)) {
+      self.offset += 1;
+      true
+    }
+    else {
+      false
+    }
+  }
+}
+
+impl<'a, T> HasNext for SliceStream<'a, T>
+{
+  fn has_next(&self) -> bool {
+    self.offset < self.atoms.len()
+  }
+}
+
+/// The span of a slice stream is the range of the indexes of the atoms.
+impl<'a, T> StreamSpan for Range<SliceStream<'a, T>>
+{
+  type Output = Range<usize>;
+  fn stream_span(&self) -> Self::Output {
+    self.start.offset..self.end.offset
+  }
+}
+
+impl<'a, T> StreamSlice for Range<SliceStream<'a, T>>
+{
+  type Output = &'a [T];
+  fn stream_slice(&self) -> Self::Output {
+    self.start.slice(self.end.clone())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[derive(Clone, Debug, PartialEq)]
+  enum Token {
+    Num(u32),
+    Plus
+  }
+
+  impl MatchLiteral for Token {
+    fn match_literal(&self, literal: &str) -> bool {
+      match (self, literal) {
+        (&Token::Plus, "+") => true,
+        _ => false
+      }
+    }
+  }
+
+  #[test]
+  fn test_next() {
+    let tokens = vec![Token::Num(1), Token::Plus, Token::Num(2)];
+    let mut s = SliceStream::new(&tokens);
+    assert_eq!(s.next(), Some(Token::Num(1)));
+    assert_eq!(s.byte_offset(), 1);
+    assert_eq!(s.code_snippet(5), format!("Plus Num(2)"));
+    assert_eq!(s.next(), Some(Token::Plus));
+    assert_eq!(s.next(), Some(Token::Num(2)));
+    assert_eq!(s.next(), None);
+    assert!(!s.has_next());
+    assert_eq!(s.code_snippet(5), format!("<end-of-file>"));
+  }
+
+  #[test]
+  fn test_consume_prefix() {
+    let tokens = vec![Token::Plus, Token::Num(1)];
+    let s = SliceStream::new(&tokens);
+    let mut s2 = s.clone();
+    assert!(!s2.consume_prefix("-"));
+    assert!(s2.consume_prefix(""));
+    assert_eq!(s2.byte_offset(), 0);
+    assert!(s2.consume_prefix("+"));
+    assert!(!s2.consume_prefix("+"));
+    assert_eq!(s2.next(), Some(Token::Num(1)));
+    assert_eq!((s..s2).stream_slice(), &tokens[..]);
+  }
+}
@@ -16,9 +16,13 @@
 
 use stream::*;
 use std::cmp::{Ordering, min};
+#[cfg(feature = "std")]
 use super::*;
 pub use std::ops::Range;
+#[cfg(feature = "std")]
 pub use syntex_pos::Span;
+#[cfg(not(feature = "std"))]
+use alloc_prelude::*;
 
 impl<'a> Stream for &'a str
 {
@@ -59,19 +63,13 @@ impl<'a> StrStream<'a>
       "Operations between two streams are only defined when they share the same raw data.");
   }
 
-  // Partially taken from https://github.com/kevinmehall/rust-peg/blob/master/src/translate.rs
+  /// The line and the column (in bytes) of the current position, both starting at 1.
+  /// Only the characters before the current position are read, and a line ending with `\r\n` is still a single line.
   pub fn line_column(&self) -> (usize, usize) {
-    let mut remaining = self.bytes_offset;
-    let mut line_no = 1usize;
-    for line in self.raw_data.lines() {
-      let line_len = line.len() + 1;
-      if remaining < line_len {
-        break;
-      }
-      remaining -= line_len;
-      line_no += 1;
-    }
-    (line_no, remaining + 1)
+    let before = &self.raw_data.as_bytes()[..self.bytes_offset];
+    let line_no = before.iter().filter(|&&b| b == b'\n').count() + 1;
+    let line_start = before.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    (line_no, self.bytes_offset - line_start + 1)
   }
 
   pub fn bytes_offset(&self) -> usize {
@@ -83,6 +81,8 @@ impl<'a> StrStream<'a>
   }
 
   pub fn slice(&self, end: StrStream<'a>) -> &'a str {
+    debug_assert!(self.bytes_offset <= end.bytes_offset,
+      "The end of a slice must not be before its start.");
     unsafe {
       self.raw_data.get_unchecked(self.bytes_offset..end.bytes_offset)
     }
@@ -137,6 +137,13 @@ impl<'a> Location for StrStream<'a>
   }
 }
 
+impl<'a> ByteOffset for StrStream<'a>
+{
+  fn byte_offset(&self) -> usize {
+    self.bytes_offset
+  }
+}
+
 impl<'a> CodeSnippet for StrStream<'a>
 {
   fn code_snippet(&self, len_hint: usize) -> String {
@@ -145,9 +152,13 @@ impl<'a> CodeSnippet for StrStream<'a>
     if current_offset == total_len {
       String::from("<end-of-file>")
     }
+    // The snippet must not end in the middle of a character.
     else {
-      let len = min(total_len - current_offset, len_hint);
-      String::from(&self.raw_data[current_offset..][..len])
+      let mut end = current_offset + min(total_len - current_offset, len_hint);
+      while !self.raw_data.is_char_boundary(end) {
+        end -= 1;
+      }
+      String::from(&self.raw_data[current_offset..end])
     }
   }
 }
@@ -155,12 +166,23 @@ impl<'a> CodeSnippet for StrStream<'a>
 impl<'a> ConsumePrefix<&'static str> for StrStream<'a>
 {
   fn consume_prefix(&mut self, prefix: &'static str) -> bool {
-    let current_offset = self.bytes_offset;
-    let end_offset = current_offset + prefix.len();
-    if end_offset <= self.raw_data.len()
-     && &self.raw_data.as_bytes()[current_offset..end_offset] == prefix.as_bytes()
+    if self.raw_data.as_bytes()[self.bytes_offset..].starts_with(prefix.as_bytes()) {
+      self.bytes_offset += prefix.len();
+      true
+    } else {
+      false
+    }
+  }
+}
+
+impl<'a> ConsumePrefixIgnoreAsciiCase<&'static str> for StrStream<'a>
+{
+  fn consume_prefix_ignore_ascii_case(&mut self, prefix: &'static str) -> bool {
+    let remaining = &self.raw_data.as_bytes()[self.bytes_offset..];
+    if prefix.len() <= remaining.len()
+     && remaining[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
     {
-      self.bytes_offset = end_offset;
+      self.bytes_offset += prefix.len();
       true
     } else {
       false
@@ -175,6 +197,7 @@ impl<'a> HasNext for StrStream<'a>
   }
 }
 
+#[cfg(feature = "std")]
 impl<'a> StreamSpan for Range<StrStream<'a>>
 {
   type Output = Span;
@@ -185,6 +208,27 @@ impl<'a> StreamSpan for Range<StrStream<'a>>
   }
 }
 
+/// Without `syntex_pos`, the span is the range of the bytes offsets.
+#[cfg(not(feature = "std"))]
+impl<'a> StreamSpan for Range<StrStream<'a>>
+{
+  type Output = Range<usize>;
+  fn stream_span(&self) -> Self::Output {
+    Range {
+      start: self.start.bytes_offset,
+      end: self.end.bytes_offset
+    }
+  }
+}
+
+impl<'a> StreamSlice for Range<StrStream<'a>>
+{
+  type Output = &'a str;
+  fn stream_slice(&self) -> Self::Output {
+    self.start.slice(self.end.clone())
+  }
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -207,6 +251,81 @@ mod test {
     consume_prefix_test(s1, "z", false, Some('a'));
   }
 
+  fn consume_prefix_ignore_case_test<'a>(stream: &StrStream<'a>, prefix: &'static str,
+    prefix_match: bool, next_char: Option<char>)
+  {
+    let mut s2 = stream.clone();
+    assert_eq!(s2.consume_prefix_ignore_ascii_case(prefix), prefix_match);
+    assert!(s2.next() == next_char);
+  }
+
+  #[test]
+  fn test_consume_prefix_ignore_ascii_case() {
+    let s1 = &"SeLeCt x".stream();
+    consume_prefix_ignore_case_test(s1, "select", true, Some(' '));
+    consume_prefix_ignore_case_test(s1, "SELECT", true, Some(' '));
+    consume_prefix_ignore_case_test(s1, "", true, Some('S'));
+    consume_prefix_ignore_case_test(s1, "selects", false, Some('S'));
+  }
+
+  // Case folding of non-ASCII characters is not supported: they must match exactly.
+  #[test]
+  fn test_consume_prefix_ignore_ascii_case_multibyte() {
+    let s1 = &"éTé".stream();
+    consume_prefix_ignore_case_test(s1, "été", true, None);
+    consume_prefix_ignore_case_test(s1, "ÉTÉ", false, Some('é'));
+    consume_prefix_ignore_case_test(s1, "Ét", false, Some('é'));
+    consume_prefix_ignore_case_test(s1, "é", true, Some('T'));
+  }
+
+  #[test]
+  fn test_end_of_input() {
+    let mut s1 = "ab".stream();
+    assert!(s1.consume_prefix("ab"));
+    let end = s1.clone();
+    assert!(!s1.has_next());
+    assert_eq!(s1.next(), None);
+    assert_eq!(s1.current_char(), None);
+    assert!(!s1.consume_prefix("a"));
+    assert!(!s1.consume_prefix_ignore_ascii_case("A"));
+    assert!(s1.consume_prefix(""));
+    assert!(s1 == end);
+    assert_eq!(s1.bytes_offset(), 2);
+    assert_eq!(s1.code_snippet(10), String::from("<end-of-file>"));
+    assert_eq!(s1.line_column(), (1, 3));
+    assert_eq!((end.clone()..s1).stream_slice(), "");
+  }
+
+  // The prefix is longer than the remaining input, which must not be read out of bounds.
+  #[test]
+  fn test_consume_prefix_longer_than_input() {
+    let mut s1 = "ab".stream();
+    s1.next();
+    assert!(!s1.consume_prefix("bcd"));
+    assert!(!s1.consume_prefix_ignore_ascii_case("BCD"));
+    assert_eq!(s1.bytes_offset(), 1);
+  }
+
+  #[test]
+  fn test_code_snippet_multibyte() {
+    let s1 = "aéé".stream();
+    assert_eq!(s1.code_snippet(2), String::from("a"));
+    assert_eq!(s1.code_snippet(3), String::from("aé"));
+    assert_eq!(s1.code_snippet(10), String::from("aéé"));
+  }
+
+  #[test]
+  fn test_line_column() {
+    let mut s1 = "a\r\nb\n".stream();
+    assert_eq!(s1.line_column(), (1, 1));
+    assert!(s1.consume_prefix("a\r\n"));
+    assert_eq!(s1.line_column(), (2, 1));
+    assert!(s1.consume_prefix("b"));
+    assert_eq!(s1.line_column(), (2, 2));
+    assert!(s1.consume_prefix("\n"));
+    assert_eq!(s1.line_column(), (3, 1));
+  }
+
   fn test_str_stream<'a, I>(mut s1: StrStream<'a>, chars: I) where
    I: Iterator<Item=char>
   {
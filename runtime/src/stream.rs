@@ -16,6 +16,9 @@
 //!
 //! A stream produces a sequence of items (characters, bytes, etc.) while retaining information on the underlying data traversed. For example, a couple `(File, Iterator<char>)` could represent a stream of characters from a file.
 
+#[cfg(not(feature = "std"))]
+use alloc_prelude::*;
+
 /// Transforms a value into a stream of type `Output`.
 pub trait Stream
 {
@@ -29,6 +32,12 @@ pub trait Location
   fn location(&self) -> String;
 }
 
+/// Returns the number of bytes read from the beginning of the input until the current position in the stream.
+pub trait ByteOffset
+{
+  fn byte_offset(&self) -> usize;
+}
+
 /// Produces a code snippet of size `len_hint` or less starting from the current position in the stream.
 pub trait CodeSnippet
 {
@@ -41,6 +50,12 @@ pub trait ConsumePrefix<P>
   fn consume_prefix(&mut self, prefix: P) -> bool;
 }
 
+/// Consumes `prefix` if it fully matches from the current position in the stream when ignoring the case of ASCII characters. Non-ASCII characters must match exactly (no Unicode case folding is performed). If it does not match, the stream is not altered and `false` is returned.
+pub trait ConsumePrefixIgnoreAsciiCase<P>
+{
+  fn consume_prefix_ignore_ascii_case(&mut self, prefix: P) -> bool;
+}
+
 /// Returns `true` if an item can be read from the stream with `Iterator::next`.
 pub trait HasNext
 {
@@ -52,3 +67,10 @@ pub trait StreamSpan
   type Output;
   fn stream_span(&self) -> Self::Output;
 }
+
+/// Returns the part of the input between the two streams of a range, borrowed from the input.
+pub trait StreamSlice
+{
+  type Output;
+  fn stream_slice(&self) -> Self::Output;
+}
@@ -0,0 +1,225 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trace of the rules called during parsing, generated by the grammar attribute `#![trace]`.
+//!
+//! The functions of the rules call `trace_enter` and `trace_exit`, which print on the standard error when the environment variable `OAK_TRACE` is set. The lines are indented by the depth of the call.
+//! For example, parsing `1+2` with `sum = number "+" number` prints:
+//!
+//! ```text
+//! parse_sum at 1:1
+//!   parse_number at 1:1
+//!   parse_number succeeded at 1:2
+//!   parse_number at 1:3
+//!   parse_number succeeded at 1:4
+//! parse_sum succeeded at 1:4
+//! ```
+//!
+//! Instead of printing the calls, `record_trace` builds the tree of the calls (`TraceNode`) of a parse, which is useful to understand why a parse failed.
+
+use stream::*;
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::fmt::{Display, Formatter, Error};
+
+/// Name of the environment variable enabling the trace.
+pub const TRACE_VAR: &'static str = "OAK_TRACE";
+
+thread_local! {
+  static DEPTH: Cell<usize> = Cell::new(0);
+  static ENABLED: Cell<Option<bool>> = Cell::new(None);
+  static CAPTURE: RefCell<Option<String>> = RefCell::new(None);
+  static RECORDER: RefCell<Option<Recorder>> = RefCell::new(None);
+}
+
+/// A call of a rule recorded by `record_trace`, with the calls made by this rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceNode
+{
+  /// The name of the function of the rule, such as `parse_sum`.
+  pub rule: String,
+  /// The location at which the rule is called.
+  pub start: String,
+  /// The location reached if the rule succeeded, `None` if it failed.
+  pub end: Option<String>,
+  pub children: Vec<TraceNode>
+}
+
+impl TraceNode
+{
+  pub fn is_successful(&self) -> bool {
+    self.end.is_some()
+  }
+
+  fn fmt_indented(&self, formatter: &mut Formatter, depth: usize) -> Result<(), Error> {
+    formatter.write_str(&"  ".repeat(depth))?;
+    match self.end {
+      Some(ref end) => writeln!(formatter, "{} at {}: succeeded at {}", self.rule, self.start, end)?,
+      None => writeln!(formatter, "{} at {}: failed", self.rule, self.start)?
+    }
+    for child in &self.children {
+      child.fmt_indented(formatter, depth + 1)?;
+    }
+    Ok(())
+  }
+}
+
+/// Displays the calls indented by their depth, one call per line.
+impl Display for TraceNode
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    self.fmt_indented(formatter, 0)
+  }
+}
+
+struct Recorder
+{
+  /// The calls not yet exited, the last one is the current call.
+  calls: Vec<TraceNode>,
+  roots: Vec<TraceNode>
+}
+
+impl Recorder
+{
+  fn enter(&mut self, rule: &str, start: String) {
+    self.calls.push(TraceNode {
+      rule: rule.to_string(),
+      start: start,
+      end: None,
+      children: vec![]
+    });
+  }
+
+  fn exit(&mut self, end: Option<String>) {
+    if let Some(mut call) = self.calls.pop() {
+      call.end = end;
+      match self.calls.last_mut() {
+        Some(parent) => parent.children.push(call),
+        None => self.roots.push(call)
+      }
+    }
+  }
+}
+
+fn is_recording() -> bool {
+  RECORDER.with(|recorder| recorder.borrow().is_some())
+}
+
+fn is_enabled() -> bool {
+  if CAPTURE.with(|capture| capture.borrow().is_some()) {
+    return true;
+  }
+  ENABLED.with(|enabled| {
+    match enabled.get() {
+      Some(enabled) => enabled,
+      None => {
+        let value = env::var_os(TRACE_VAR).is_some();
+        enabled.set(Some(value));
+        value
+      }
+    }
+  })
+}
+
+fn print_line(depth: usize, line: String) {
+  let line = format!("{}{}", "  ".repeat(depth), line);
+  CAPTURE.with(|capture| {
+    match *capture.borrow_mut() {
+      Some(ref mut trace) => {
+        trace.push_str(&line);
+        trace.push('\n');
+      }
+      None => eprintln!("{}", line)
+    }
+  })
+}
+
+/// Called when entering the function `rule` at the position `current`.
+pub fn trace_enter<S: Location>(rule: &str, current: &S) {
+  if is_recording() {
+    RECORDER.with(|recorder| recorder.borrow_mut().as_mut().unwrap().enter(rule, current.location()));
+  }
+  else if is_enabled() {
+    let depth = DEPTH.with(|depth| { let d = depth.get(); depth.set(d + 1); d });
+    print_line(depth, format!("{} at {}", rule, current.location()));
+  }
+}
+
+/// Called when exiting the function `rule`, `current` is the position reached if it succeeded.
+pub fn trace_exit<S: Location>(rule: &str, current: &S, success: bool) {
+  if is_recording() {
+    let end = if success { Some(current.location()) } else { None };
+    RECORDER.with(|recorder| recorder.borrow_mut().as_mut().unwrap().exit(end));
+  }
+  else if is_enabled() {
+    let depth = DEPTH.with(|depth| { let d = depth.get().saturating_sub(1); depth.set(d); d });
+    if success {
+      print_line(depth, format!("{} succeeded at {}", rule, current.location()));
+    }
+    else {
+      print_line(depth, format!("{} failed", rule));
+    }
+  }
+}
+
+/// Executes `f` with the trace enabled and returns the trace instead of printing it on the standard error.
+pub fn capture_trace<F: FnOnce()>(f: F) -> String {
+  CAPTURE.with(|capture| *capture.borrow_mut() = Some(String::new()));
+  f();
+  CAPTURE.with(|capture| capture.borrow_mut().take().unwrap_or_default())
+}
+
+/// Executes `f` and returns its result with the tree of the calls of the rules made by `f`, for example `record_trace(|| parse_sum(input.into_state()))`.
+/// Only the grammars with the attribute `#![trace]` record their calls, the trace is not printed meanwhile.
+pub fn record_trace<R, F: FnOnce() -> R>(f: F) -> (R, Vec<TraceNode>) {
+  RECORDER.with(|recorder| *recorder.borrow_mut() = Some(Recorder { calls: vec![], roots: vec![] }));
+  let result = f();
+  let recorder = RECORDER.with(|recorder| recorder.borrow_mut().take().unwrap());
+  (result, recorder.roots)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_capture_trace() {
+    let trace = capture_trace(|| {
+      let input = "ab".stream();
+      trace_enter("parse_a", &input);
+      trace_enter("parse_b", &input);
+      trace_exit("parse_b", &input, false);
+      trace_exit("parse_a", &input, true);
+    });
+    assert_eq!(trace, "parse_a at 1:1\n  parse_b at 1:1\n  parse_b failed\nparse_a succeeded at 1:1\n");
+  }
+
+  #[test]
+  fn test_record_trace() {
+    let ((), roots) = record_trace(|| {
+      let input = "ab".stream();
+      trace_enter("parse_a", &input);
+      trace_enter("parse_b", &input);
+      trace_exit("parse_b", &input, false);
+      trace_exit("parse_a", &input, true);
+      trace_enter("parse_c", &input);
+      trace_exit("parse_c", &input, false);
+    });
+    assert_eq!(roots.len(), 2);
+    assert!(roots[0].is_successful());
+    assert_eq!(roots[0].children[0].rule, "parse_b");
+    assert!(!roots[1].is_successful());
+    assert_eq!(format!("{}", roots[0]), "parse_a at 1:1: succeeded at 1:1\n  parse_b at 1:1: failed\n");
+  }
+}
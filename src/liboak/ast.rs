@@ -24,14 +24,45 @@ pub use partial::Partial;
 pub use middle::typing::ast::IType;
 pub use middle::typing::ast::Type;
 
-use middle::analysis::ast::GrammarAttributes;
+use middle::analysis::ast::{GrammarAttributes, FirstSet};
 
 use std::collections::HashMap;
 use std::default::Default;
 use std::ops::{Index, IndexMut};
+use std::path::PathBuf;
 
 use syn::parse_quote;
 
+/// The file `path` given to an attribute of the grammar, such as `#![include("path")]` or `#![diagnostics_json = "path"]`.
+/// A relative path is relative to the directory of the crate (`CARGO_MANIFEST_DIR`), and to the current directory of the compiler if this variable is not set.
+pub fn crate_relative_path(path: &str) -> PathBuf {
+  let base = std::env::var("CARGO_MANIFEST_DIR").map(PathBuf::from)
+    .or_else(|_| std::env::current_dir())
+    .unwrap_or_default();
+  base.join(path)
+}
+
+/// Given a type of the form `Result<T, E>` (possibly with a path such as `std::result::Result<T, E>`), returns `T`.
+pub fn result_ok_type(ty: &syn::Type) -> Option<syn::Type> {
+  if let syn::Type::Path(ty_path) = ty {
+    let last = ty_path.path.segments.last()?;
+    if last.ident == "Result" {
+      if let syn::PathArguments::AngleBracketed(ref args) = last.arguments {
+        let tys: Vec<_> = args.args.iter()
+          .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None
+          })
+          .collect();
+        if tys.len() == 2 {
+          return Some(tys[0].clone());
+        }
+      }
+    }
+  }
+  None
+}
+
 pub trait ExprByIndex
 {
   fn expr_by_index(&self, index: usize) -> Expression;
@@ -46,7 +77,9 @@ pub struct Grammar<ExprInfo>
   pub stream_alias: syn::ItemType,
   pub rust_functions: HashMap<Ident, syn::ItemFn>,
   pub rust_items: Vec<syn::Item>,
-  pub attributes: GrammarAttributes
+  pub attributes: GrammarAttributes,
+  /// The FIRST set of each expression, computed by `middle::analysis::first_set`.
+  pub first_sets: HashMap<usize, FirstSet>
 }
 
 impl<ExprInfo> Grammar<ExprInfo>
@@ -62,7 +95,8 @@ impl<ExprInfo> Grammar<ExprInfo>
       stream_alias: parse_quote!(pub type Stream<'a> = StrStream<'a>;),
       rust_functions: HashMap::new(),
       rust_items: vec![],
-      attributes: GrammarAttributes::default()
+      attributes: GrammarAttributes::default(),
+      first_sets: HashMap::new()
     }
   }
 
@@ -77,6 +111,87 @@ impl<ExprInfo> Grammar<ExprInfo>
     self.find_rule_by_ident(id).expr_idx
   }
 
+  /// The rules from which the grammar is used, given by `#![start(r1, r2)]` or the first rule by default.
+  pub fn start_rules(&self) -> Vec<Ident> {
+    if self.attributes.start_rules.is_empty() {
+      self.rules.iter().take(1).map(|r| r.ident()).collect()
+    }
+    else {
+      self.attributes.start_rules.clone()
+    }
+  }
+
+  /// The return type of the semantic action if it is known: the one of a function of the grammar, or the one declared by a closure such as `|x| -> u32 { x * 2 }`.
+  pub fn action_output(&self, action: &syn::Expr) -> Option<syn::ReturnType> {
+    match action {
+      syn::Expr::Path(expr_path) => {
+        let fun = expr_path.path.get_ident()
+          .and_then(|ident| self.rust_functions.get(ident))?;
+        Some(fun.sig.output.clone())
+      }
+      syn::Expr::Closure(closure) => match closure.output {
+        syn::ReturnType::Type(_, _) => Some(closure.output.clone()),
+        syn::ReturnType::Default => None
+      },
+      _ => None
+    }
+  }
+
+  /// The type `T` if the semantic action returns `Result<T, E>` (see `action_output`).
+  /// Such an action fails the expression when it returns an error.
+  pub fn fallible_action_type(&self, action: &syn::Expr) -> Option<syn::Type> {
+    match self.action_output(action)? {
+      syn::ReturnType::Type(_, ref ty) => result_ok_type(ty),
+      syn::ReturnType::Default => None
+    }
+  }
+
+  /// True if `id` is the rule given by `#![spacing(r)]`.
+  pub fn is_spacing_rule(&self, id: &Ident) -> bool {
+    match self.attributes.spacing_rule {
+      Some(ref spacing) => spacing == id,
+      None => false
+    }
+  }
+
+  /// The index of the rule in `rules`, it identifies the rule in the memoization table at runtime.
+  pub fn rule_index(&self, id: &Ident) -> usize {
+    self.rules.iter()
+      .position(|r| r.ident() == *id)
+      .expect("Rule ident not registered in the known rules.")
+  }
+
+  /// True if the expression contains a cut `~` that applies to it, i.e. which is not in a nested choice or in another rule.
+  pub fn contains_cut(&self, expr_idx: usize) -> bool {
+    use ast::Expression::*;
+    match self.expr_by_index(expr_idx) {
+      Cut => true,
+      Sequence(children) => children.into_iter().any(|c| self.contains_cut(c)),
+      ZeroOrMore(child)
+    | OneOrMore(child)
+    | ZeroOrOne(child)
+    | CommittedOptional(child)
+    | BoundedRepeat(child, _, _)
+    | RepeatUntilEoi(child)
+    | Count(child)
+    | NotPredicate(child)
+    | AndPredicate(child)
+    | SemanticAction(child, _, _)
+    | TypeAscription(child, _)
+    | SpannedExpr(child)
+    | RangeExpr(child)
+    | ByteSpanned(child)
+    | Capture(child)
+    | ErrorLabel(child, _)
+    | Strict(child)
+    | Guard(child, _)
+    | Named(child, _) => self.contains_cut(child),
+      SeparatedList(child, sep, _, _)
+    | Recover(child, sep) => self.contains_cut(child) || self.contains_cut(sep),
+      _ => false
+    }
+  }
+
   pub fn stream_generics(&self) -> syn::Generics {
     self.stream_alias.generics.clone()
   }
@@ -94,6 +209,59 @@ impl<ExprInfo> Grammar<ExprInfo>
     stream_ty
   }
 
+  /// The type alias `type Atom = Token;` declared in the grammar, if the stream does not produce characters.
+  pub fn atom_alias(&self) -> Option<&syn::ItemType> {
+    self.rust_items.iter().find_map(|item| {
+      match item {
+        syn::Item::Type(ty) if ty.ident == "Atom" => Some(ty),
+        _ => None
+      }
+    })
+  }
+
+  /// True if the stream resolved by `extract_stream_type` is `ByteStream`, possibly through the type aliases declared in the grammar (e.g. `type Bytes<'a> = ByteStream<'a>;`).
+  /// The path must be the one of the runtime (`ByteStream` as imported in the generated module, or `oak_runtime::ByteStream`) so a user type with the same name is not mistaken for it.
+  pub fn is_byte_stream(&self) -> bool {
+    let mut ty = self.stream_alias.ty.clone();
+    let mut aliases_followed = 0;
+    loop {
+      let path: Vec<String> = match *ty {
+        syn::Type::Path(ref ty_path) if ty_path.qself.is_none() =>
+          ty_path.path.segments.iter().map(|s| s.ident.to_string()).collect(),
+        _ => return false
+      };
+      let path: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+      match path.as_slice() {
+        ["ByteStream"]
+      | ["oak_runtime", "ByteStream"]
+      | ["oak_runtime", "byte_stream", "ByteStream"] => return true,
+        [name] if aliases_followed < self.rust_items.len() => {
+          let alias = self.rust_items.iter().find_map(|item| match item {
+            syn::Item::Type(alias) if alias.ident == name && alias.ident != "Stream" => Some(alias.ty.clone()),
+            _ => None
+          });
+          match alias {
+            Some(alias) => { ty = alias; aliases_followed += 1; }
+            None => return false
+          }
+        }
+        _ => return false
+      }
+    }
+  }
+
+  /// The type of the atoms produced by the stream, `char` if the grammar does not declare the type `Atom`.
+  pub fn atom_type(&self) -> syn::Type {
+    match self.atom_alias() {
+      Some(alias) => {
+        let name = alias.ident.clone();
+        let (_, ty_generics, _) = alias.generics.split_for_impl();
+        parse_quote!(#name #ty_generics)
+      }
+      None => parse_quote!(char)
+    }
+  }
+
   /// The span type of the underlying type is given by the trait's associated type `StreamSpan::Output`.
   pub fn span_type(&self) -> syn::Type {
     let range_ty: syn::Type = self.range_type();
@@ -104,6 +272,11 @@ impl<ExprInfo> Grammar<ExprInfo>
     let stream_ty = self.stream_type();
     parse_quote!(Range<#stream_ty>)
   }
+
+  pub fn slice_type(&self) -> syn::Type {
+    let range_ty: syn::Type = self.range_type();
+    parse_quote!(<#range_ty as StreamSlice>::Output)
+  }
 }
 
 impl<ExprInfo> Index<usize> for Grammar<ExprInfo>
@@ -134,12 +307,26 @@ pub struct Rule
 {
   pub name: Ident,
   pub expr_idx: usize,
+  /// True if the results of this rule are stored in the memoization table of the parsing state (packrat mode).
+  pub memoized: bool,
+  /// True if this rule is the head of a left-recursive cycle, it is compiled with the seed-growing algorithm.
+  pub left_recursive: bool,
+  /// True if only the recognizer of this rule is generated (`#![recognizer]`), its type is then the unit type.
+  pub recognizer_only: bool,
+  /// The doc comments `/// ...` of the rule, they are forwarded to its generated functions.
+  pub doc: Vec<syn::Attribute>,
+  /// True if the rule is generated for a binding `let name = e in body`, its functions are private to the grammar.
+  pub hidden: bool,
+  /// True if the rule is declared as the entry rule of the grammar with `#[start]`, the function `parse` is generated for it.
+  pub entry: bool,
+  /// The path of the grammar included with `#![include("path")]` in which the rule is declared.
+  pub included_from: Option<String>
 }
 
 impl Rule
 {
   pub fn new(name: Ident, expr_idx: usize) -> Rule {
-    Rule { name, expr_idx }
+    Rule { name, expr_idx, memoized: false, left_recursive: false, recognizer_only: false, doc: vec![], hidden: false, entry: false, included_from: None }
   }
 }
 
@@ -160,35 +347,63 @@ impl Spanned for Rule
 #[derive(Clone, Debug)]
 pub enum Expression
 {
-  StrLiteral(String), // "match me"
+  StrLiteral(String, bool), // "match me" or "match me"i, the boolean is true if case-insensitive.
   AnySingleChar, // .
-  CharacterClass(CharacterClassExpr), // [0-9]
+  EndOfInput, // eoi
+  Cut, // ~
+  CharacterClass(CharacterClassExpr), // ["0-9"] or [^"0-9"]
   NonTerminalSymbol(Ident), // a_rule
   ExternalNonTerminalSymbol(syn::Path), // RustItem
   Sequence(Vec<usize>), // a_rule next_rule
   Choice(Vec<usize>), // try_this / or_try_this_one
+  LongestChoice(Vec<usize>), // longest(try_this / and_this_one)
   ZeroOrMore(usize), // expr*
   OneOrMore(usize), // expr+
   ZeroOrOne(usize), // expr?
+  CommittedOptional(usize), // committed(expr), same as expr? but it fails if expr fails after consuming input.
+  BoundedRepeat(usize, usize, Option<usize>), // expr{n}, expr{n,} or expr{n,m}
+  SeparatedList(usize, usize, bool, bool), // expr % sep, expr %% sep or separated_nonempty(expr, sep), the first boolean is true if at least one element is required and the second if a separator must be followed by an element.
   NotPredicate(usize), // !expr
   AndPredicate(usize), // &expr
   SemanticAction(usize, bool, syn::Expr), // expr > function, the boolean is true if boxed.
   TypeAscription(usize, IType), // expr:() or expr:(^) or expr:<rust-ty>
   SpannedExpr(usize), // .. expr
   RangeExpr(usize), // ... expr
+  ByteSpanned(usize), // spanned(expr)
+  Capture(usize), // $expr or capture(expr)
+  ErrorLabel(usize, String), // expr^"label"
+  Strict(usize), // strict(expr)
+  Guard(usize, syn::Expr), // expr && { condition }
+  Named(usize, Ident), // name@expr
+  Recover(usize, usize), // recover(expr, sync)
+  RepeatUntilEoi(usize), // repeat_until_eoi(expr)
+  Count(usize), // count(expr)
+  TokenPattern(syn::Pat), // token(Token::Plus | Token::Minus)
+  Let(Ident, usize, usize), // let name = expr in body, replaced by `body` calling a hidden rule `expr` before the analyses.
 }
 
 #[derive(Clone, Debug)]
 pub struct CharacterClassExpr
 {
-  pub intervals: Vec<CharacterInterval>
+  pub intervals: Vec<CharacterInterval>,
+  /// Unicode general categories `\p{L}`, the boolean is true for the negated categories `\P{L}`.
+  pub categories: Vec<(UnicodeCategory, bool)>,
+  /// `true` if the class matches any character that is not in `intervals` and `categories`.
+  pub negated: bool,
+  /// `true` if the bounds are byte values such as in `[0x00-0x1F]`, only available with `ByteStream` which reads each byte as the character of the same value.
+  pub bytes: bool
 }
 
 impl CharacterClassExpr
 {
-  pub fn new(intervals: Vec<CharacterInterval>) -> CharacterClassExpr {
+  pub fn new(intervals: Vec<CharacterInterval>, categories: Vec<(UnicodeCategory, bool)>,
+    negated: bool) -> CharacterClassExpr
+  {
     CharacterClassExpr {
-      intervals: intervals
+      intervals,
+      categories,
+      negated,
+      bytes: false
     }
   }
 }
@@ -196,14 +411,96 @@ impl CharacterClassExpr
 impl Display for CharacterClassExpr
 {
   fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
-    formatter.write_str("[\"")?;
+    if self.bytes {
+      formatter.write_str(if self.negated { "[^" } else { "[" })?;
+      for (i, interval) in self.intervals.iter().enumerate() {
+        if i > 0 {
+          formatter.write_str(", ")?;
+        }
+        formatter.write_fmt(format_args!("0x{:02X}", interval.lo as u32))?;
+        if interval.lo != interval.hi {
+          formatter.write_fmt(format_args!("-0x{:02X}", interval.hi as u32))?;
+        }
+      }
+      return formatter.write_str("]");
+    }
+    if self.negated {
+      formatter.write_str("[^\"")?;
+    }
+    else {
+      formatter.write_str("[\"")?;
+    }
     for interval in &self.intervals {
       interval.fmt(formatter)?;
     }
+    for &(category, negated) in &self.categories {
+      let p = if negated { "P" } else { "p" };
+      formatter.write_fmt(format_args!("\\\\{}{{{}}}", p, category.abbreviation()))?;
+    }
     formatter.write_str("\"]")
   }
 }
 
+/// Unicode general categories supported in character classes, they are matched by the functions of `oak_runtime::unicode`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnicodeCategory
+{
+  Letter,
+  UppercaseLetter,
+  LowercaseLetter,
+  Number,
+  DecimalNumber,
+  Punctuation,
+  Separator,
+  Other
+}
+
+impl UnicodeCategory
+{
+  const ALL: [UnicodeCategory; 8] = [
+    UnicodeCategory::Letter, UnicodeCategory::UppercaseLetter, UnicodeCategory::LowercaseLetter,
+    UnicodeCategory::Number, UnicodeCategory::DecimalNumber, UnicodeCategory::Punctuation,
+    UnicodeCategory::Separator, UnicodeCategory::Other];
+
+  pub fn from_abbreviation(abbreviation: &str) -> Option<UnicodeCategory> {
+    UnicodeCategory::ALL.iter().cloned()
+      .find(|category| category.abbreviation() == abbreviation)
+  }
+
+  pub fn abbreviation(&self) -> &'static str {
+    use self::UnicodeCategory::*;
+    match *self {
+      Letter => "L",
+      UppercaseLetter => "Lu",
+      LowercaseLetter => "Ll",
+      Number => "N",
+      DecimalNumber => "Nd",
+      Punctuation => "P",
+      Separator => "Z",
+      Other => "C"
+    }
+  }
+
+  /// Name of the function of `oak_runtime::unicode` testing if a character is in this category.
+  pub fn predicate_name(&self) -> &'static str {
+    use self::UnicodeCategory::*;
+    match *self {
+      Letter => "is_letter",
+      UppercaseLetter => "is_uppercase_letter",
+      LowercaseLetter => "is_lowercase_letter",
+      Number => "is_number",
+      DecimalNumber => "is_decimal_number",
+      Punctuation => "is_punctuation",
+      Separator => "is_separator",
+      Other => "is_other"
+    }
+  }
+
+  pub fn abbreviations() -> Vec<&'static str> {
+    UnicodeCategory::ALL.iter().map(|c| c.abbreviation()).collect()
+  }
+}
+
 #[derive(Clone, Debug)]
 pub struct CharacterInterval
 {
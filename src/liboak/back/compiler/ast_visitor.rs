@@ -0,0 +1,246 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! With `#![visitor]`, generates a trait `Visitor` for the types built by the grammar, similar to the visitor of the expressions of Oak.
+//! The visited types are the structures and enumerations declared in the grammar that are the type of a rule or of a field of another visited type, possibly in a `Box`, a `Vec` or an `Option`.
+//! Each type `T` has a method `visit_t` in the trait, calling by default the function `walk_t` which visits the fields of `T` with a visited type.
+//! The generic types are not visited.
+
+use middle::typing::ast::*;
+use back::compiler::rtype::*;
+use back::name_factory::*;
+
+use quote::{quote, format_ident};
+use syn::parse_quote;
+use std::collections::HashSet;
+
+pub struct AstVisitorCompiler<'a>
+{
+  /// The visited types in their order of declaration.
+  types: Vec<&'a syn::Item>,
+  names: HashSet<String>
+}
+
+impl<'a> AstVisitorCompiler<'a>
+{
+  pub fn compile(grammar: &'a TGrammar) -> Vec<syn::Item> {
+    let compiler = AstVisitorCompiler::new(grammar);
+    if compiler.types.is_empty() {
+      return vec![];
+    }
+    let mut items = vec![compiler.compile_trait()];
+    items.extend(compiler.types.iter().map(|item| compiler.compile_walk_fn(item)));
+    items
+  }
+
+  fn new(grammar: &'a TGrammar) -> AstVisitorCompiler<'a> {
+    let declared: Vec<&syn::Item> = grammar.rust_items.iter()
+      .filter(|item| match *item {
+        syn::Item::Struct(s) => s.generics.params.is_empty(),
+        syn::Item::Enum(e) => e.generics.params.is_empty(),
+        _ => false
+      })
+      .collect();
+    let declared_names: HashSet<String> = declared.iter().map(|item| type_name(item).to_string()).collect();
+    let mut names = HashSet::new();
+    let mut worklist: Vec<String> = grammar.rules.iter()
+      .filter(|rule| !rule.recognizer_only)
+      .filter_map(|rule| inner_type(&TypeCompiler::compile(grammar, rule.expr_idx)))
+      .map(|id| id.to_string())
+      .collect();
+    while let Some(name) = worklist.pop() {
+      if !declared_names.contains(&name) || !names.insert(name.clone()) {
+        continue;
+      }
+      let item = declared.iter().find(|item| type_name(item) == name.as_str()).unwrap();
+      worklist.extend(field_types(item).iter()
+        .filter_map(inner_type)
+        .map(|id| id.to_string()));
+    }
+    let types = declared.into_iter()
+      .filter(|item| names.contains(&type_name(item).to_string()))
+      .collect();
+    AstVisitorCompiler { types, names }
+  }
+
+  fn compile_trait(&self) -> syn::Item {
+    let methods: Vec<syn::TraitItem> = self.types.iter()
+      .map(|item| {
+        let ty = type_name(item);
+        let visit_fn = visit_type_id(ty);
+        let walk_fn = walk_type_id(ty);
+        parse_quote!(
+          fn #visit_fn(&mut self, node: &#ty) {
+            #walk_fn(self, node)
+          }
+        )
+      })
+      .collect();
+    parse_quote!(
+      pub trait Visitor {
+        #(#methods)*
+      }
+    )
+  }
+
+  fn compile_walk_fn(&self, item: &syn::Item) -> syn::Item {
+    let ty = type_name(item);
+    let walk_fn = walk_type_id(ty);
+    let body = match item {
+      syn::Item::Struct(s) => {
+        let stmts: Vec<_> = s.fields.iter().enumerate()
+          .filter_map(|(i, field)| {
+            let member: syn::Member = match field.ident {
+              Some(ref id) => parse_quote!(#id),
+              None => syn::Member::Unnamed(i.into())
+            };
+            self.visit_value(&field.ty, quote!(&node.#member), 0)
+          })
+          .collect();
+        quote!(#(#stmts)*)
+      }
+      syn::Item::Enum(e) => {
+        let arms: Vec<_> = e.variants.iter()
+          .map(|variant| self.compile_variant_arm(ty, variant))
+          .collect();
+        quote!(match *node { #(#arms)* })
+      }
+      _ => unreachable!("BUG: only structures and enumerations are visited.")
+    };
+    parse_quote!(
+      #[allow(unused_variables)]
+      pub fn #walk_fn<V: Visitor + ?Sized>(visitor: &mut V, node: &#ty) {
+        #body
+      }
+    )
+  }
+
+  fn compile_variant_arm(&self, ty: &syn::Ident, variant: &syn::Variant) -> proc_macro2::TokenStream {
+    let name = &variant.ident;
+    let mut stmts = vec![];
+    // The patterns of the fields, `_` for the fields that are not visited.
+    let mut patterns = vec![];
+    for (i, field) in variant.fields.iter().enumerate() {
+      let var = match field.ident {
+        Some(ref id) => id.clone(),
+        None => format_ident!("field{}", i)
+      };
+      match self.visit_value(&field.ty, quote!(#var), 0) {
+        Some(stmt) => {
+          stmts.push(stmt);
+          patterns.push(Some(var));
+        }
+        None => patterns.push(None)
+      }
+    }
+    match variant.fields {
+      syn::Fields::Unit => quote!(#ty::#name => {}),
+      syn::Fields::Unnamed(_) => {
+        let patterns = patterns.into_iter()
+          .map(|var| match var { Some(var) => quote!(ref #var), None => quote!(_) });
+        quote!(#ty::#name(#(#patterns),*) => { #(#stmts)* })
+      }
+      syn::Fields::Named(_) => {
+        let bindings = patterns.into_iter().flatten();
+        quote!(#ty::#name { #(ref #bindings,)* .. } => { #(#stmts)* })
+      }
+    }
+  }
+
+  /// The statement visiting `value`, a reference to a value of type `ty`, if `ty` contains a visited type.
+  /// The elements of `Vec` and `Option` are bound to references by the default binding mode.
+  fn visit_value(&self, ty: &syn::Type, value: proc_macro2::TokenStream, depth: usize) -> Option<proc_macro2::TokenStream> {
+    let segment = single_segment(ty)?;
+    let name = segment.ident.to_string();
+    match generic_argument(segment) {
+      None if self.names.contains(&name) => {
+        let visit_fn = visit_type_id(&segment.ident);
+        Some(quote!(visitor.#visit_fn(#value);))
+      }
+      Some(inner) if name == "Box" => {
+        self.visit_value(inner, quote!(&**#value), depth)
+      }
+      Some(inner) if name == "Vec" => {
+        let elem = format_ident!("elem{}", depth);
+        let stmt = self.visit_value(inner, quote!(#elem), depth + 1)?;
+        Some(quote!(for #elem in #value { #stmt }))
+      }
+      Some(inner) if name == "Option" => {
+        let elem = format_ident!("elem{}", depth);
+        let stmt = self.visit_value(inner, quote!(#elem), depth + 1)?;
+        Some(quote!(if let Some(#elem) = #value { #stmt }))
+      }
+      _ => None
+    }
+  }
+}
+
+fn type_name(item: &syn::Item) -> &syn::Ident {
+  match item {
+    syn::Item::Struct(s) => &s.ident,
+    syn::Item::Enum(e) => &e.ident,
+    _ => unreachable!("BUG: only structures and enumerations are visited.")
+  }
+}
+
+fn field_types(item: &syn::Item) -> Vec<syn::Type> {
+  match item {
+    syn::Item::Struct(s) => s.fields.iter().map(|f| f.ty.clone()).collect(),
+    syn::Item::Enum(e) => e.variants.iter()
+      .flat_map(|v| v.fields.iter().map(|f| f.ty.clone()))
+      .collect(),
+    _ => vec![]
+  }
+}
+
+fn single_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+  match ty {
+    syn::Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+      Some(&path.path.segments[0])
+    }
+    _ => None
+  }
+}
+
+/// The type `T` of `Box<T>`, `Vec<T>` or `Option<T>`.
+fn generic_argument(segment: &syn::PathSegment) -> Option<&syn::Type> {
+  match segment.arguments {
+    syn::PathArguments::AngleBracketed(ref generics) if generics.args.len() == 1 => {
+      match generics.args[0] {
+        syn::GenericArgument::Type(ref ty) => Some(ty),
+        _ => None
+      }
+    }
+    _ => None
+  }
+}
+
+/// The name of `ty` if it is a type without generic arguments such as `Expr`.
+fn type_ident(ty: &syn::Type) -> Option<syn::Ident> {
+  let segment = single_segment(ty)?;
+  match segment.arguments {
+    syn::PathArguments::None => Some(segment.ident.clone()),
+    _ => None
+  }
+}
+
+/// The type without generic arguments in `ty` looking through `Box`, `Vec` and `Option`, e.g. `Expr` in `Vec<Box<Expr>>`.
+fn inner_type(ty: &syn::Type) -> Option<syn::Ident> {
+  let segment = single_segment(ty)?;
+  match generic_argument(segment) {
+    None => type_ident(ty),
+    Some(inner) if ["Box", "Vec", "Option"].iter().any(|c| segment.ident == *c) => inner_type(inner),
+    Some(_) => None
+  }
+}
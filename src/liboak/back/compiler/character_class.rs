@@ -17,6 +17,9 @@ use quote::format_ident;
 
 type VarInPatternFn = for <'a> fn(&mut Context<'a>) -> Ident;
 
+/// Above this number of intervals, they are looked up by a binary search in their sorted array (`oak_runtime::unicode::in_table`) instead of being compared one after the other.
+const BINARY_SEARCH_THRESHOLD: usize = 8;
+
 fn bind_x_var<'a>(_context: &mut Context<'a>) -> Ident {
   format_ident!("x")
 }
@@ -58,17 +61,63 @@ impl CharacterClassCompiler
     }
   }
 
+  fn compile_category(&self, category: UnicodeCategory, negated: bool, x: Ident) -> syn::Expr
+  {
+    let predicate = format_ident!("{}", category.predicate_name());
+    if negated {
+      parse_quote!(!oak_runtime::unicode::#predicate(#x))
+    }
+    else {
+      parse_quote!(oak_runtime::unicode::#predicate(#x))
+    }
+  }
+
+  fn compile_intervals(&self, x: Ident) -> Vec<syn::Expr> {
+    if self.classes.intervals.len() > BINARY_SEARCH_THRESHOLD {
+      let bounds = merge_intervals(self.classes.intervals.clone()).into_iter()
+        .map(|CharacterInterval{lo, hi}| quote!((#lo, #hi)));
+      vec![parse_quote!(oak_runtime::unicode::in_table(#x, &[#(#bounds),*]))]
+    }
+    else {
+      self.classes.intervals.iter().cloned()
+        .map(|char_interval| self.compile_interval(char_interval, x.clone()))
+        .collect()
+    }
+  }
+
   fn compile_condition(&self, x: Ident) -> syn::Expr {
-    let mut intervals = self.classes.intervals.iter().cloned();
-    let first_interval = intervals.next()
-      .expect("Empty character intervals should be forbidden at the parsing stage.");
-    intervals
-      .map(|char_interval| self.compile_interval(char_interval, x.clone()))
-      .fold(
-        self.compile_interval(first_interval, x.clone()),
-        |accu, interval| parse_quote!(#accu || #interval)
-      )
+    let mut conditions = self.compile_intervals(x.clone()).into_iter()
+      .chain(self.classes.categories.iter()
+        .map(|&(category, negated)| self.compile_category(category, negated, x.clone())));
+    let first_condition = conditions.next()
+      .expect("Empty character classes should be forbidden at the parsing stage.");
+    let condition = conditions
+      .fold(first_condition, |accu, cond| parse_quote!(#accu || #cond));
+    if self.classes.negated {
+      parse_quote!(!(#condition))
+    }
+    else {
+      condition
+    }
+  }
+}
+
+/// Sorts the intervals and merges those overlapping or adjacent, so they can be searched by dichotomy.
+fn merge_intervals(mut intervals: Vec<CharacterInterval>) -> Vec<CharacterInterval> {
+  intervals.sort_by_key(|interval| interval.lo);
+  let mut merged: Vec<CharacterInterval> = vec![];
+  for interval in intervals {
+    if let Some(last) = merged.last_mut() {
+      if interval.lo as u32 <= last.hi as u32 + 1 {
+        if interval.hi > last.hi {
+          last.hi = interval.hi;
+        }
+        continue;
+      }
+    }
+    merged.push(interval);
   }
+  merged
 }
 
 impl CompileExpr for CharacterClassCompiler
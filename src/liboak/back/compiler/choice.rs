@@ -13,70 +13,141 @@
 // limitations under the License.
 
 use back::compiler::*;
+use middle::analysis::ast::FirstSet;
+use quote::format_ident;
 
 pub struct ChoiceCompiler
 {
   choices: Vec<usize>,
-  compiler: ExprCompilerFn
+  compiler: ExprCompilerFn,
+  /// True if a branch contains a cut `~`.
+  has_cut: bool
 }
 
 impl ChoiceCompiler
 {
-  pub fn recognizer(choices: Vec<usize>) -> ChoiceCompiler {
-    ChoiceCompiler {
-      choices: choices,
-      compiler: recognizer_compiler
-    }
+  pub fn recognizer(grammar: &TGrammar, choices: Vec<usize>) -> ChoiceCompiler {
+    ChoiceCompiler::new(grammar, choices, recognizer_compiler)
   }
 
-  pub fn parser(choices: Vec<usize>) -> ChoiceCompiler {
+  pub fn parser(grammar: &TGrammar, choices: Vec<usize>) -> ChoiceCompiler {
+    ChoiceCompiler::new(grammar, choices, parser_compiler)
+  }
+
+  fn new(grammar: &TGrammar, choices: Vec<usize>, compiler: ExprCompilerFn) -> ChoiceCompiler {
+    let has_cut = choices.iter().any(|&c| grammar.contains_cut(c));
     ChoiceCompiler {
-      choices: choices,
-      compiler: parser_compiler
+      choices,
+      compiler,
+      has_cut
     }
   }
 }
 
-impl CompileExpr for ChoiceCompiler
+impl ChoiceCompiler
 {
-  fn compile_expr<'a>(&self,  context: &mut Context<'a>,
-    mut continuation: Continuation) -> syn::Expr
+  /// If the FIRST sets of the branches are non-nullable and pairwise disjoint, the next character of the input determines the only branch that can succeed.
+  /// It returns the condition on this character for each branch.
+  fn dispatch_conditions<'a>(&self, context: &Context<'a>, c: &Ident) -> Option<Vec<syn::Expr>> {
+    let mut firsts: Vec<FirstSet> = vec![];
+    for &idx in &self.choices {
+      let first = context.first_set(idx)?;
+      if first.nullable || first.is_empty()
+       || firsts.iter().any(|f| !f.is_disjoint(&first))
+      {
+        return None;
+      }
+      firsts.push(first);
+    }
+    Some(firsts.into_iter()
+      .map(|first| {
+        let mut intervals = first.intervals.into_iter().map(|(lo, hi)| -> syn::Expr {
+          if lo == hi { parse_quote!(#c == #lo) }
+          else { parse_quote!((#c >= #lo && #c <= #hi)) }
+        });
+        let first_interval = intervals.next().unwrap();
+        intervals.fold(first_interval, |accu, i| parse_quote!(#accu || #i))
+      })
+      .collect())
+  }
+
+  /// Compiles the choice as a `match` on the next character, the branches are tried in sequence only if no FIRST set contains this character, in order to report the same errors as a choice without dispatch.
+  fn compile_dispatch<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation, c: Ident, conditions: Vec<syn::Expr>) -> syn::Expr
   {
-    // Since we copy the success continuation for each branch, to avoid code explosion, we can extract it into a closure shared by all branches under criterion maintained by the context.
-    continuation = context.success_as_closure(continuation);
+    let scope = context.save_scope();
+    let arms: Vec<syn::Arm> = self.choices.iter().zip(conditions)
+      .map(|(&idx, condition)| {
+        context.restore_scope(scope.clone());
+        let branch = continuation.compile(context, self.compiler, idx);
+        parse_quote!(Some(#c) if #condition => #branch,)
+      })
+      .collect();
+    context.restore_scope(scope);
+    let sequential = self.compile_sequential(context, continuation);
+    parse_quote!(
+      match state.current.clone().next() {
+        #(#arms)*
+        _ => #sequential
+      }
+    )
+  }
 
+  fn compile_sequential<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
     let mark = context.next_mark_name();
+    // `branch_failed` is a `&mut bool` in the choice as in the success closures, where it is a parameter, so it is assigned the same way in both.
     let branch_failed = context.next_branch_failed_name();
-    context.push_mut_ref_fv(branch_failed.clone(), parse_quote!(bool));
+    let branch_failed_value = format_ident!("{}_value", branch_failed);
+    let cut_saved = context.next_cut_saved_name();
+    context.push_mut_ref_fv_by_ref(branch_failed.clone(), parse_quote!(bool));
+    if self.has_cut {
+      context.push_mark_of_type(cut_saved.clone(), parse_quote!(bool));
+    }
 
     // Each branch of the choice must be compiled in the same variable names environment (they share names of the variables they are building) and with a fresh success continuation size (each branch might create independent success continuation).
     let scope = context.save_scope();
 
+    // With a cut, the last branch must also assign `false` to `branch_failed` to know if the choice itself failed.
     let mut choices = self.choices.clone();
-    let last = choices.pop().unwrap();
+    let last = if self.has_cut { None } else { choices.pop() };
     let mut branches: Vec<_> = choices.into_iter()
       .map(|idx| {
         context.restore_scope(scope.clone());
         let b = branch_failed.clone();
-        let success: syn::Stmt = parse_quote!(#b = false;);
+        let success: syn::Stmt =
+          if self.has_cut { parse_quote!({ *#b = false; state.set_cut(#cut_saved); }) }
+          else { parse_quote!(*#b = false;) };
         continuation.compile_and_wrap(context, self.compiler, idx, success)
       })
       .collect();
 
-    // The last branch does not need to assign `false` to the variable `branch_failed`.
-    context.restore_scope(scope.clone());
-    context.pop_mut_ref_fv();
-    let (success, failure) = continuation.unwrap();
-    branches.push(context.compile(self.compiler, last, success, failure));
+    if self.has_cut {
+      context.pop_mark();
+    }
+    if let Some(last) = last {
+      // The last branch does not need to assign `false` to the variable `branch_failed`.
+      context.restore_scope(scope.clone());
+      context.pop_mut_ref_fv();
+      let (success, failure) = continuation.unwrap();
+      branches.push(context.compile(self.compiler, last, success, failure));
+    }
+    else {
+      context.pop_mut_ref_fv();
+    }
 
     let mut branches_iter = branches.into_iter();
     let first = branches_iter.next().unwrap();
 
+    let try_next: syn::Expr =
+      if self.has_cut { parse_quote!(*#branch_failed && !state.is_cut()) }
+      else { parse_quote!(*#branch_failed) };
     let choice = branches_iter
       .rev()
       .fold(parse_quote!(state), |accu: syn::Expr, branch|
         parse_quote!({
-          if #branch_failed {
+          if #try_next {
             let mut state = state.restore_from_failure(#mark.clone());
             let state = #branch;
             #accu
@@ -84,11 +155,49 @@ impl CompileExpr for ChoiceCompiler
           else { state }
         }));
 
-    parse_quote!({
-      let #mark = state.mark();
-      let mut #branch_failed = true;
-      let state = #first;
-      #choice
-    })
+    if self.has_cut {
+      // The cut flag of the enclosing choice is restored when leaving this choice.
+      parse_quote!({
+        let mut state = state;
+        let #mark = state.mark();
+        let #cut_saved = state.is_cut();
+        state.set_cut(false);
+        let mut #branch_failed_value = true;
+        let #branch_failed = &mut #branch_failed_value;
+        let state = #first;
+        let mut state = #choice;
+        if *#branch_failed {
+          state.set_cut(#cut_saved);
+        }
+        state
+      })
+    }
+    else {
+      parse_quote!({
+        let #mark = state.mark();
+        let mut #branch_failed_value = true;
+        let #branch_failed = &mut #branch_failed_value;
+        let state = #first;
+        #choice
+      })
+    }
+  }
+}
+
+impl CompileExpr for ChoiceCompiler
+{
+  fn compile_expr<'a>(&self,  context: &mut Context<'a>,
+    mut continuation: Continuation) -> syn::Expr
+  {
+    // Since we copy the success continuation for each branch, to avoid code explosion, we can extract it into a closure shared by all branches under criterion maintained by the context.
+    continuation = context.success_as_closure(continuation);
+
+    // A cut only makes sense when the branches are tried in sequence.
+    let c = format_ident!("dispatch_char");
+    let conditions = if self.has_cut { None } else { self.dispatch_conditions(context, &c) };
+    match conditions {
+      Some(conditions) => self.compile_dispatch(context, continuation, c, conditions),
+      None => self.compile_sequential(context, continuation)
+    }
   }
 }
@@ -0,0 +1,36 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+
+pub struct EndOfInputCompiler;
+
+impl CompileExpr for EndOfInputCompiler
+{
+  fn compile_expr<'a>(&self, _context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    continuation
+      .map_success(|success, failure| parse_quote!(
+        if state.current.has_next() {
+          state.error("<end of input>");
+          #failure
+        }
+        else {
+          #success
+        }
+      ))
+     .unwrap_success()
+  }
+}
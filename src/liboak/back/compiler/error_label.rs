@@ -0,0 +1,66 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+
+pub struct ErrorLabelCompiler
+{
+  expr_idx: usize,
+  label: String,
+  compiler: ExprCompilerFn
+}
+
+impl ErrorLabelCompiler
+{
+  pub fn recognizer(expr_idx: usize, label: String) -> ErrorLabelCompiler {
+    ErrorLabelCompiler {
+      expr_idx, label,
+      compiler: recognizer_compiler
+    }
+  }
+
+  pub fn parser(expr_idx: usize, label: String) -> ErrorLabelCompiler {
+    ErrorLabelCompiler {
+      expr_idx, label,
+      compiler: parser_compiler
+    }
+  }
+}
+
+impl CompileExpr for ErrorLabelCompiler
+{
+  fn compile_expr<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let mark = context.next_mark_name();
+    let expected_count = context.next_counter_name();
+    let label = self.label.as_str();
+    // The failure continuation might be moved into a closure by a sub-expression, so the marks must be passed to it.
+    context.push_mark(mark.clone());
+    context.push_mark_of_type(expected_count.clone(), parse_quote!(usize));
+    let (success, failure) = continuation.unwrap();
+    let failure = parse_quote!({
+      state.label_failure(#mark.clone(), #expected_count, #label);
+      #failure
+    });
+    let expr = context.compile_success(self.compiler, self.expr_idx, success, failure);
+    context.pop_mark();
+    context.pop_mark();
+    parse_quote!({
+      let #mark = state.mark();
+      let #expected_count = state.expected_count();
+      #expr
+    })
+  }
+}
@@ -14,8 +14,12 @@
 
 pub use middle::typing::ast::*;
 use back::compiler::rule::*;
+use back::compiler::rtype::*;
+use back::compiler::ast_visitor::*;
+use back::name_factory::*;
 
 use quote::quote;
+use syn::parse_quote;
 
 pub struct GrammarCompiler
 {
@@ -38,22 +42,80 @@ impl GrammarCompiler
   }
 
   fn compile_grammar_module(&self, module_content: Vec<syn::Item>) -> proc_macro2::TokenStream {
-    quote!(
+    let std_imports = self.compile_std_imports();
+    let grammar_marker = self.compile_grammar_marker();
+    let items = quote!(
       // #![allow(unused_mut)]
       #[allow(unused_imports)]
       use oak_runtime::stream::*;
       #[allow(unused_imports)]
       use oak_runtime::str_stream::StrStream;
       #[allow(unused_imports)]
-      use std::ops::Range;
+      use oak_runtime::byte_stream::ByteStream;
+      #std_imports
+      #grammar_marker
 
       #(#module_content)*
-    )
+    );
+    self.compile_named_module(items)
+  }
+
+  /// With `#![module(name)]`, the generated items are declared in the module `name` so several grammars can be declared in the same module.
+  /// The items of the enclosing module are imported since the Rust code of the grammar can refer to them.
+  fn compile_named_module(&self, items: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match self.grammar.attributes.module {
+      Some(ref name) => quote!(
+        pub mod #name {
+          #[allow(unused_imports)]
+          use super::*;
+
+          #items
+        }
+      ),
+      None => items
+    }
+  }
+
+  /// The memoized rules are identified in the memoization table by their index and the marker type of their grammar.
+  fn compile_grammar_marker(&self) -> proc_macro2::TokenStream {
+    if self.grammar.rules.iter().any(|rule| rule.memoized || rule.left_recursive) {
+      let marker = grammar_marker_id();
+      quote!(struct #marker;)
+    }
+    else {
+      quote!()
+    }
+  }
+
+  /// With `#![no_std]`, the types used by the generated code are imported from `core` and `alloc`, the crate using the grammar must declare `extern crate alloc`.
+  fn compile_std_imports(&self) -> proc_macro2::TokenStream {
+    if self.grammar.attributes.no_std {
+      quote!(
+        #[allow(unused_imports)]
+        use core::ops::Range;
+        #[allow(unused_imports)]
+        use alloc::vec::Vec;
+        #[allow(unused_imports)]
+        use alloc::boxed::Box;
+      )
+    }
+    else {
+      quote!(
+        #[allow(unused_imports)]
+        use std::ops::Range;
+      )
+    }
   }
 
   fn compile_mod_content(&self) -> Vec<syn::Item> {
     let mut mod_content = self.grammar.rust_items.clone();
-    mod_content.extend(self.compile_rules().into_iter());
+    mod_content.extend(self.compile_rules());
+    mod_content.extend(self.compile_entry_functions());
+    mod_content.extend(self.compile_from_str_impls());
+    mod_content.extend(self.compile_type_aliases());
+    if self.grammar.attributes.visitor {
+      mod_content.extend(AstVisitorCompiler::compile(&self.grammar));
+    }
     mod_content.extend(self.grammar.rust_functions.values().cloned()
       .map(syn::Item::Fn));
     mod_content
@@ -64,4 +126,196 @@ impl GrammarCompiler
       .flat_map(|rule| RuleCompiler::compile(&self.grammar, rule.clone()).into_iter())
       .collect()
   }
+
+  /// Generates the functions `full_parse_r`, `partial_parse_r` and `iter_parse_r` of each start rule `r`.
+  /// The first fails if the input is not entirely consumed, the second returns the number of bytes consumed along with the value, and the third returns an iterator over the values of `r` matched one after another.
+  /// They call the recognizer of the rule if it only has a recognizer.
+  fn compile_entry_functions(&self) -> Vec<syn::Item> {
+    let stream_ty = self.grammar.stream_type();
+    let generics = self.grammar.stream_generics();
+    self.grammar.start_rules().into_iter()
+      .flat_map(|id| {
+        let rule = self.grammar.find_rule_by_ident(&id);
+        let (rule_fn, ty) =
+          if rule.recognizer_only { (recognizer_id(rule.ident()), TypeCompiler::unit_type()) }
+          else { (parser_id(rule.ident()), TypeCompiler::compile(&self.grammar, rule.expr_idx)) };
+        let full_fn = full_parser_id(rule.ident());
+        let partial_fn = partial_parser_id(rule.ident());
+        let all_fn = all_parser_id(rule.ident());
+        vec![
+          parse_quote!(
+            #[inline]
+            pub fn #full_fn #generics (input: impl oak_runtime::IntoState<#stream_ty, ()>)
+              -> Result<#ty, oak_runtime::ParseError>
+            {
+              #rule_fn(oak_runtime::IntoState::into_state(input)).into_full_result()
+            }
+          ),
+          parse_quote!(
+            #[inline]
+            pub fn #partial_fn #generics (input: impl oak_runtime::IntoState<#stream_ty, ()>)
+              -> Result<(#ty, usize), oak_runtime::ParseError>
+            {
+              #rule_fn(oak_runtime::IntoState::into_state(input)).into_partial_result()
+            }
+          ),
+          parse_quote!(
+            #[inline]
+            pub fn #all_fn #generics (input: impl oak_runtime::IntoState<#stream_ty, ()>)
+              -> oak_runtime::ParseAll<#stream_ty, #ty>
+            {
+              oak_runtime::ParseAll::new(oak_runtime::IntoState::into_state(input), #rule_fn)
+            }
+          )
+        ]
+      })
+      .chain(self.compile_parse_function())
+      .chain(self.compile_cst_functions())
+      .collect()
+  }
+
+  /// With `#![cst]`, generates the function `cst_parse_r` of each start rule `r`, returning the concrete syntax tree of the input entirely consumed by `r`.
+  /// The tree is built by the recognizers, the values of the rules are not needed.
+  fn compile_cst_functions(&self) -> Vec<syn::Item> {
+    if !self.grammar.attributes.cst {
+      return vec![];
+    }
+    let stream_ty = self.grammar.stream_type();
+    let generics = self.grammar.stream_generics();
+    self.grammar.start_rules().into_iter()
+      .map(|id| {
+        let recognizer_fn = recognizer_id(id.clone());
+        let cst_fn = cst_parser_id(id);
+        parse_quote!(
+          #[inline]
+          pub fn #cst_fn #generics (input: impl oak_runtime::IntoState<#stream_ty, ()>)
+            -> Result<oak_runtime::cst::CstNode, oak_runtime::ParseError>
+          {
+            let (result, mut roots) = oak_runtime::cst::record_cst(||
+              #recognizer_fn(oak_runtime::IntoState::into_state(input)).into_full_result());
+            result.map(|()| roots.pop().unwrap())
+          }
+        )
+      })
+      .collect()
+  }
+
+  /// Generates the functions `parse` and `parse_all` of the entry rule `r` declared with `#[start]`, same as `full_parse_r` and `iter_parse_r`.
+  fn compile_parse_function(&self) -> Vec<syn::Item> {
+    let id = match self.grammar.attributes.entry_rule {
+      Some(ref id) => id,
+      None => return vec![]
+    };
+    let rule = self.grammar.find_rule_by_ident(id);
+    let ty =
+      if rule.recognizer_only { TypeCompiler::unit_type() }
+      else { TypeCompiler::compile(&self.grammar, rule.expr_idx) };
+    let stream_ty = self.grammar.stream_type();
+    let generics = self.grammar.stream_generics();
+    let full_fn = full_parser_id(rule.ident());
+    let all_fn = all_parser_id(rule.ident());
+    let doc = &rule.doc;
+    vec![
+      parse_quote!(
+        #(#doc)*
+        #[inline]
+        pub fn parse #generics (input: impl oak_runtime::IntoState<#stream_ty, ()>)
+          -> Result<#ty, oak_runtime::ParseError>
+        {
+          #full_fn(input)
+        }
+      ),
+      parse_quote!(
+        #(#doc)*
+        #[inline]
+        pub fn parse_all #generics (input: impl oak_runtime::IntoState<#stream_ty, ()>)
+          -> oak_runtime::ParseAll<#stream_ty, #ty>
+        {
+          #all_fn(input)
+        }
+      )
+    ]
+  }
+
+  /// With `#![type_aliases]`, generates the alias `RuleOutput` of the type of each rule `rule`.
+  /// The alias only takes the generic parameters of the stream appearing in the type, and no alias is generated if the type is not fully known (e.g. the result of an external parser).
+  /// The hidden rules of the let bindings have no alias.
+  fn compile_type_aliases(&self) -> Vec<syn::Item> {
+    if !self.grammar.attributes.type_aliases {
+      return vec![];
+    }
+    self.grammar.rules.iter()
+      .filter(|rule| !rule.hidden)
+      .filter_map(|rule| {
+        let ty =
+          if rule.recognizer_only { TypeCompiler::unit_type() }
+          else { TypeCompiler::compile(&self.grammar, rule.expr_idx) };
+        let mut tokens = vec![];
+        type_tokens(quote!(#ty), &mut tokens);
+        if tokens.iter().any(|t| t == "_") {
+          return None;
+        }
+        let params: Vec<_> = self.grammar.stream_generics().params.into_iter()
+          .filter_map(|param| match param {
+            syn::GenericParam::Lifetime(def) => {
+              let lifetime = def.lifetime;
+              if tokens.contains(&lifetime.to_string()) { Some(quote!(#lifetime)) } else { None }
+            }
+            syn::GenericParam::Type(ty_param) => {
+              let id = ty_param.ident;
+              if tokens.contains(&id.to_string()) { Some(quote!(#id)) } else { None }
+            }
+            syn::GenericParam::Const(_) => None
+          })
+          .collect();
+        let alias = type_alias_id(rule.ident());
+        let generics = if params.is_empty() { quote!() } else { quote!(<#(#params),*>) };
+        Some(parse_quote!(pub type #alias #generics = #ty;))
+      })
+      .collect()
+  }
+
+  /// Implements `FromStr` for the type of each rule listed in `#![derive_from_str(..)]`.
+  /// The string must be entirely consumed by the rule, otherwise an error is returned.
+  fn compile_from_str_impls(&self) -> Vec<syn::Item> {
+    self.grammar.attributes.from_str_rules.iter()
+      .map(|id| {
+        let rule = self.grammar.find_rule_by_ident(id);
+        let ty = TypeCompiler::compile(&self.grammar, rule.expr_idx);
+        let parser_fn = parser_id(rule.ident());
+        let from_str: syn::Path =
+          if self.grammar.attributes.no_std { parse_quote!(::core::str::FromStr) }
+          else { parse_quote!(::std::str::FromStr) };
+        parse_quote!(
+          impl #from_str for #ty {
+            type Err = oak_runtime::ParseError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+              #parser_fn(oak_runtime::IntoState::into_state(s)).into_full_result()
+            }
+          }
+        )
+      })
+      .collect()
+  }
+}
+
+/// The identifiers and lifetimes of the type `tokens`, in which we look for the generic parameters of the stream and for `_`.
+fn type_tokens(tokens: proc_macro2::TokenStream, result: &mut Vec<String>) {
+  use proc_macro2::TokenTree::*;
+  // `'a` is the punctuation `'` followed by the identifier `a`.
+  let mut lifetime = false;
+  for token in tokens {
+    let quote_punct = match &token {
+      Punct(punct) => punct.as_char() == '\'',
+      _ => false
+    };
+    match token {
+      Group(group) => type_tokens(group.stream(), result),
+      Ident(id) if lifetime => result.push(format!("'{}", id)),
+      Ident(id) => result.push(id.to_string()),
+      Punct(ref punct) if punct.as_char() == '_' => result.push(String::from("_")),
+      _ => ()
+    }
+    lifetime = quote_punct;
+  }
 }
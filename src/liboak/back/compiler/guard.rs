@@ -0,0 +1,82 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+
+/// `e && { condition }` binds the values of `e` to their names (see `TGrammar::value_names`), e.g. `year month day && { valid_date(year, month, day) }`, and fails if `condition` is false.
+/// The recognizer also builds the values of `e` to evaluate the condition.
+pub struct GuardCompiler
+{
+  expr_idx: usize,
+  condition: syn::Expr,
+  bindings: Vec<Option<Ident>>,
+  recognizer: bool
+}
+
+impl GuardCompiler
+{
+  pub fn recognizer(grammar: &TGrammar, expr_idx: usize, condition: syn::Expr) -> GuardCompiler {
+    GuardCompiler {
+      expr_idx, condition,
+      bindings: grammar.value_names(expr_idx, true),
+      recognizer: true
+    }
+  }
+
+  pub fn parser(grammar: &TGrammar, expr_idx: usize, condition: syn::Expr) -> GuardCompiler {
+    GuardCompiler {
+      recognizer: false,
+      .. GuardCompiler::recognizer(grammar, expr_idx, condition)
+    }
+  }
+}
+
+impl CompileExpr for GuardCompiler
+{
+  fn compile_expr<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    // The recognizer opens a scope for the values of `e`, whereas the parser shares them with its continuation.
+    let scope =
+      if self.recognizer { Some(context.open_scope(self.expr_idx)) }
+      else { None };
+    let free_vars = context.free_variables();
+    let vars = &free_vars[free_vars.len() - self.bindings.len()..];
+    let (names, vars): (Vec<_>, Vec<_>) = self.bindings.iter().zip(vars.iter())
+      .filter_map(|(name, var)| name.clone().map(|name| (name, var.clone())))
+      .unzip();
+    let condition = self.condition.clone();
+    let rebind: Vec<syn::Stmt> =
+      if self.recognizer { vec![] }
+      else { names.iter().zip(vars.iter()).map(|(name, var)| parse_quote!(let #var = #name;)).collect() };
+    let expr = continuation
+      .map_success(|success, failure| parse_quote!({
+        #(#[allow(unused_variables)] let #names = #vars;)*
+        if #condition {
+          #(#rebind)*
+          #success
+        }
+        else {
+          state.action_failure();
+          #failure
+        }
+      }))
+      .compile_success(context, parser_compiler, self.expr_idx)
+      .unwrap_success();
+    if let Some(scope) = scope {
+      context.close_scope(scope);
+    }
+    expr
+  }
+}
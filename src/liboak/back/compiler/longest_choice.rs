@@ -0,0 +1,97 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+use quote::format_ident;
+
+/// `longest(e1 / e2)` recognizes every branch from the same position and remembers the one that went the farthest (the first one in case of equality).
+/// This branch is then compiled again with the continuation of the choice, to build its value and to continue the parsing.
+pub struct LongestChoiceCompiler
+{
+  choices: Vec<usize>,
+  compiler: ExprCompilerFn
+}
+
+impl LongestChoiceCompiler
+{
+  pub fn recognizer(choices: Vec<usize>) -> LongestChoiceCompiler {
+    LongestChoiceCompiler { choices, compiler: recognizer_compiler }
+  }
+
+  pub fn parser(choices: Vec<usize>) -> LongestChoiceCompiler {
+    LongestChoiceCompiler { choices, compiler: parser_compiler }
+  }
+}
+
+impl CompileExpr for LongestChoiceCompiler
+{
+  fn compile_expr<'a>(&self, context: &mut Context<'a>,
+    mut continuation: Continuation) -> syn::Expr
+  {
+    continuation = context.success_as_closure(continuation);
+    let mark = context.next_mark_name();
+    let longest = format_ident!("{}_longest", mark);
+    let end = format_ident!("{}_end", mark);
+
+    let tries: Vec<syn::Stmt> = self.choices.iter().enumerate()
+      .map(|(i, &idx)| {
+        let recognizer = context.compile_recognizer_expr(idx);
+        parse_quote!(
+          {
+            state = #recognizer;
+            if state.is_successful() {
+              state.discard_data();
+              let #end = state.mark();
+              if #longest.as_ref().map_or(true, |&(_, ref longest_end)| #end > *longest_end) {
+                #longest = Some((#i, #end));
+              }
+              state = state.restore(#mark.clone());
+            }
+            else {
+              state = state.restore_from_failure(#mark.clone());
+            }
+          }
+        )
+      })
+      .collect();
+
+    // As in `ChoiceCompiler`, the scope of the last branch is kept since its free variables are bound.
+    let scope = context.save_scope();
+    let arms: Vec<syn::Arm> = self.choices.iter().enumerate()
+      .map(|(i, &idx)| {
+        context.restore_scope(scope.clone());
+        let branch = continuation.compile(context, self.compiler, idx);
+        parse_quote!(Some((#i, _)) => #branch,)
+      })
+      .collect();
+    let (_, failure) = continuation.unwrap();
+
+    parse_quote!(
+      {
+        let mut state = state;
+        let #mark = state.mark();
+        let mut #longest = None;
+        #(#tries)*
+        match #longest {
+          #(#arms)*
+          _ => {
+            // All the branches failed, the items they expected are kept.
+            state.action_failure();
+            #failure
+          }
+        }
+      }
+    )
+  }
+}
@@ -17,18 +17,28 @@
 pub mod rtype;
 pub mod value;
 mod grammar;
+mod ast_visitor;
 mod rule;
 mod str_literal;
 mod sequence;
 mod choice;
+mod longest_choice;
 mod any_single_char;
+mod end_of_input;
+mod cut;
 mod repeat;
+mod separated_list;
 mod optional;
 mod syntactic_predicate;
 mod character_class;
 mod non_terminal;
 mod semantic_action;
 mod spanned_expr;
+mod error_label;
+mod strict;
+mod guard;
+mod recover;
+mod token_pattern;
 
 pub use quote::quote;
 pub use syn::parse_quote;
@@ -38,14 +48,23 @@ pub use back::context::*;
 use back::compiler::str_literal::*;
 use back::compiler::sequence::*;
 use back::compiler::choice::*;
+use back::compiler::longest_choice::*;
 use back::compiler::any_single_char::*;
+use back::compiler::end_of_input::*;
+use back::compiler::cut::*;
 use back::compiler::repeat::*;
+use back::compiler::separated_list::*;
 use back::compiler::optional::*;
 use back::compiler::syntactic_predicate::*;
 use back::compiler::character_class::*;
 use back::compiler::non_terminal::*;
 use back::compiler::semantic_action::*;
 use back::compiler::spanned_expr::*;
+use back::compiler::error_label::*;
+use back::compiler::strict::*;
+use back::compiler::guard::*;
+use back::compiler::recover::*;
+use back::compiler::token_pattern::*;
 
 pub enum CompilerKind
 {
@@ -61,49 +80,93 @@ pub trait CompileExpr
 pub type ExprCompilerFn = fn(&TGrammar, usize) -> Box<dyn CompileExpr>;
 
 pub fn parser_compiler(grammar: &TGrammar, idx: usize) -> Box<dyn CompileExpr> {
-  if grammar[idx].ty == Type::Unit {
+  if grammar.is_unit_type(idx) {
     recognizer_compiler(grammar, idx)
   }
   else {
     match grammar.expr_by_index(idx) {
-      StrLiteral(lit) => Box::new(StrLiteralCompiler::parser(lit)),
+      StrLiteral(lit, case_insensitive) => Box::new(StrLiteralCompiler::parser(lit, case_insensitive)),
       CharacterClass(classes) => Box::new(CharacterClassCompiler::parser(classes)),
       AnySingleChar => Box::new(AnySingleCharCompiler::parser()),
+      TokenPattern(pattern) => Box::new(TokenPatternCompiler::parser(pattern)),
       Sequence(seq) => Box::new(SequenceCompiler::parser(seq)),
-      Choice(choices) => Box::new(ChoiceCompiler::parser(choices)),
-      ZeroOrOne(expr_idx) => Box::new(OptionalCompiler::parser(expr_idx)),
-      ZeroOrMore(expr_idx) => Box::new(RepeatCompiler::parser(expr_idx, 0)),
-      OneOrMore(expr_idx) => Box::new(RepeatCompiler::parser(expr_idx, 1)),
+      Choice(choices) => Box::new(ChoiceCompiler::parser(grammar, choices)),
+      LongestChoice(choices) => Box::new(LongestChoiceCompiler::parser(choices)),
+      ZeroOrOne(expr_idx) => Box::new(OptionalCompiler::parser(expr_idx, false)),
+      CommittedOptional(expr_idx) => Box::new(OptionalCompiler::parser(expr_idx, true)),
+      ZeroOrMore(expr_idx) => Box::new(RepeatCompiler::parser(expr_idx, 0, None, repeat_container(grammar, idx))),
+      OneOrMore(expr_idx) => Box::new(RepeatCompiler::parser(expr_idx, 1, None, repeat_container(grammar, idx))),
+      BoundedRepeat(expr_idx, min, max) => Box::new(RepeatCompiler::parser(expr_idx, min, max, repeat_container(grammar, idx))),
+      RepeatUntilEoi(expr_idx) => Box::new(RepeatCompiler::parser_until_eoi(expr_idx, repeat_container(grammar, idx))),
+      Count(expr_idx) => Box::new(RepeatCompiler::parser_count(expr_idx)),
+      SeparatedList(expr_idx, sep_idx, one_or_more, strict) => Box::new(SeparatedListCompiler::parser(expr_idx, sep_idx, one_or_more, strict)),
       NonTerminalSymbol(id) => Box::new(NonTerminalCompiler::parser(id, idx)),
       ExternalNonTerminalSymbol(path) => Box::new(NonTerminalCompiler::external_parser(path, idx)),
       SemanticAction(expr_idx, boxed, action) => Box::new(SemanticActionCompiler::parser(expr_idx, boxed, action, idx)),
-      TypeAscription(expr_idx, _) => parser_compiler(grammar, expr_idx),
-      SpannedExpr(expr_idx) => Box::new(SpannedExprCompiler::parser(expr_idx, false)),
-      RangeExpr(expr_idx) => Box::new(SpannedExprCompiler::parser(expr_idx, true)),
+      TypeAscription(expr_idx, _)
+    | Named(expr_idx, _) => parser_compiler(grammar, expr_idx),
+      SpannedExpr(expr_idx) => Box::new(SpannedExprCompiler::parser(expr_idx, Extent::Span)),
+      RangeExpr(expr_idx) => Box::new(SpannedExprCompiler::parser(expr_idx, Extent::Range)),
+      ByteSpanned(expr_idx) => Box::new(SpannedExprCompiler::parser(expr_idx, Extent::Bytes)),
+      Capture(expr_idx) => Box::new(SpannedExprCompiler::parser(expr_idx, Extent::Slice)),
+      ErrorLabel(expr_idx, label) => Box::new(ErrorLabelCompiler::parser(expr_idx, label)),
+      Strict(expr_idx) => Box::new(StrictCompiler::parser(expr_idx)),
+      Guard(expr_idx, condition) => Box::new(GuardCompiler::parser(grammar, expr_idx, condition)),
+      Recover(expr_idx, sync_idx) => Box::new(RecoverCompiler::parser(expr_idx, sync_idx)),
       NotPredicate(_)
-    | AndPredicate(_) => unreachable!(
-        "BUG: Syntactic predicate can not be compiled to parser (they do not generate data)."),
+    | AndPredicate(_)
+    | EndOfInput
+    | Cut => unreachable!(
+        "BUG: Syntactic predicate, end of input and cut can not be compiled to parser (they do not generate data)."),
+      Let(_, _, _) => unreachable!(
+        "BUG: the let bindings are expanded into rules before the compilation."),
     }
   }
 }
 
+/// A repetition typed with a Rust type, such as `e*:String`, collects its values in this type instead of a `Vec`.
+fn repeat_container(grammar: &TGrammar, idx: usize) -> Option<syn::Type> {
+  match grammar[idx].ty {
+    Type::Rust(ref ty) => Some(ty.clone()),
+    _ => None
+  }
+}
+
 pub fn recognizer_compiler(grammar: &TGrammar, idx: usize) -> Box<dyn CompileExpr> {
   match grammar.expr_by_index(idx) {
-    StrLiteral(lit) => Box::new(StrLiteralCompiler::recognizer(lit)),
+    StrLiteral(lit, case_insensitive) => Box::new(StrLiteralCompiler::recognizer(lit, case_insensitive)),
     CharacterClass(classes) => Box::new(CharacterClassCompiler::recognizer(classes)),
     AnySingleChar => Box::new(AnySingleCharCompiler::recognizer()),
+    TokenPattern(pattern) => Box::new(TokenPatternCompiler::recognizer(pattern)),
+    EndOfInput => Box::new(EndOfInputCompiler),
+    Cut => Box::new(CutCompiler),
     Sequence(seq) => Box::new(SequenceCompiler::recognizer(seq)),
-    Choice(choices) => Box::new(ChoiceCompiler::recognizer(choices)),
-    ZeroOrOne(expr_idx) => Box::new(OptionalCompiler::recognizer(expr_idx)),
-    ZeroOrMore(expr_idx) => Box::new(RepeatCompiler::recognizer(expr_idx, 0)),
-    OneOrMore(expr_idx) => Box::new(RepeatCompiler::recognizer(expr_idx, 1)),
+    Choice(choices) => Box::new(ChoiceCompiler::recognizer(grammar, choices)),
+    LongestChoice(choices) => Box::new(LongestChoiceCompiler::recognizer(choices)),
+    ZeroOrOne(expr_idx) => Box::new(OptionalCompiler::recognizer(expr_idx, false)),
+    CommittedOptional(expr_idx) => Box::new(OptionalCompiler::recognizer(expr_idx, true)),
+    ZeroOrMore(expr_idx) => Box::new(RepeatCompiler::recognizer(expr_idx, 0, None)),
+    OneOrMore(expr_idx) => Box::new(RepeatCompiler::recognizer(expr_idx, 1, None)),
+    BoundedRepeat(expr_idx, min, max) => Box::new(RepeatCompiler::recognizer(expr_idx, min, max)),
+    RepeatUntilEoi(expr_idx) => Box::new(RepeatCompiler::recognizer_until_eoi(expr_idx)),
+    Count(expr_idx) => Box::new(RepeatCompiler::recognizer(expr_idx, 0, None)),
+    SeparatedList(expr_idx, sep_idx, one_or_more, strict) => Box::new(SeparatedListCompiler::recognizer(expr_idx, sep_idx, one_or_more, strict)),
     NotPredicate(expr_idx) => Box::new(SyntacticPredicateCompiler::recognizer(expr_idx, Kind::Not)),
     AndPredicate(expr_idx) => Box::new(SyntacticPredicateCompiler::recognizer(expr_idx, Kind::And)),
     NonTerminalSymbol(id) => Box::new(NonTerminalCompiler::recognizer(id)),
     ExternalNonTerminalSymbol(path) => Box::new(NonTerminalCompiler::external_recognizer(path)),
+    ErrorLabel(expr_idx, label) => Box::new(ErrorLabelCompiler::recognizer(expr_idx, label)),
+    Strict(expr_idx) => Box::new(StrictCompiler::recognizer(expr_idx)),
+    Guard(expr_idx, condition) => Box::new(GuardCompiler::recognizer(grammar, expr_idx, condition)),
+    Recover(expr_idx, sync_idx) => Box::new(RecoverCompiler::recognizer(expr_idx, sync_idx)),
       SemanticAction(expr_idx, _, _)
     | TypeAscription(expr_idx, _)
+    | Named(expr_idx, _)
     | SpannedExpr(expr_idx)
-    | RangeExpr(expr_idx) => recognizer_compiler(grammar, expr_idx),
+    | RangeExpr(expr_idx)
+    | ByteSpanned(expr_idx)
+    | Capture(expr_idx) => recognizer_compiler(grammar, expr_idx),
+    Let(_, _, _) => unreachable!(
+      "BUG: the let bindings are expanded into rules before the compilation."),
   }
 }
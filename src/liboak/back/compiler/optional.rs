@@ -14,24 +14,29 @@
 
 use back::compiler::*;
 
+/// Compiles `e?` and `committed(e)`: if `e` fails, the state is restored before `e` and the optional expression succeeds.
+/// With `committed(e)`, the failure of `e` after consuming input is propagated instead, since `e` has been started.
 pub struct OptionalCompiler
 {
   expr_idx: usize,
+  committed: bool,
   compiler_kind: CompilerKind
 }
 
 impl OptionalCompiler
 {
-  pub fn recognizer(expr_idx: usize) -> OptionalCompiler {
+  pub fn recognizer(expr_idx: usize, committed: bool) -> OptionalCompiler {
     OptionalCompiler {
-      expr_idx: expr_idx,
+      expr_idx,
+      committed,
       compiler_kind: CompilerKind::Recognizer
     }
   }
 
-  pub fn parser(expr_idx: usize) -> OptionalCompiler {
+  pub fn parser(expr_idx: usize, committed: bool) -> OptionalCompiler {
     OptionalCompiler {
-      expr_idx: expr_idx,
+      expr_idx,
+      committed,
       compiler_kind: CompilerKind::Parser
     }
   }
@@ -40,17 +45,31 @@ impl OptionalCompiler
     continuation: Continuation, body: syn::Expr) -> syn::Expr
   {
     let mark = context.next_mark_name();
+    let committed = self.committed;
     continuation
-      .map_success(|success, _|
-        parse_quote!({
-          let #mark = state.mark();
-          state = #body;
+      .map_success(|success, failure| {
+        let optional: syn::Expr = parse_quote!({
           if state.is_failed() {
             state = state.restore_from_failure(#mark);
           }
           #success
+        });
+        let optional =
+          if committed {
+            parse_quote!(
+              if state.is_failed() && state.consumed_since(&#mark) {
+                #failure
+              }
+              else #optional
+            )
+          }
+          else { optional };
+        parse_quote!({
+          let #mark = state.mark();
+          state = #body;
+          #optional
         })
-      )
+      })
       .unwrap_success()
   }
 
@@ -0,0 +1,135 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+use quote::format_ident;
+
+/// `recover(e, sync)` produces `Some(v)` if `e` succeeds with the value `v`.
+/// Otherwise, the input is skipped until `sync` succeeds, `sync` included, and the error of `e` is recorded in the state (see `ParseState::recover`) before succeeding with `None`.
+/// The errors encountered before `e` are saved since the state then continues with them instead of the errors of `e`.
+/// If `sync` never succeeds, or if no input is skipped, the expression fails with the error of `e`, thus `recover(e, sync)*` always terminates.
+pub struct RecoverCompiler
+{
+  expr_idx: usize,
+  sync_idx: usize,
+  compiler_kind: CompilerKind
+}
+
+impl RecoverCompiler
+{
+  pub fn recognizer(expr_idx: usize, sync_idx: usize) -> RecoverCompiler {
+    RecoverCompiler {
+      expr_idx,
+      sync_idx,
+      compiler_kind: CompilerKind::Recognizer
+    }
+  }
+
+  pub fn parser(expr_idx: usize, sync_idx: usize) -> RecoverCompiler {
+    RecoverCompiler {
+      expr_idx,
+      sync_idx,
+      compiler_kind: CompilerKind::Parser
+    }
+  }
+
+  fn compile<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation, body: syn::Expr) -> syn::Expr
+  {
+    let mark = context.next_mark_name();
+    let sync_mark = format_ident!("{}_sync", mark);
+    let expectation = format_ident!("{}_expectation", mark);
+    let outer_expectation = format_ident!("{}_outer_expectation", mark);
+    let synchronized = format_ident!("{}_synchronized", mark);
+    let sync = context.compile_recognizer_expr(self.sync_idx);
+    let (success, failure) = continuation.unwrap();
+    parse_quote!({
+      let #mark = state.mark();
+      let #outer_expectation = state.expectation();
+      state = #body;
+      if state.is_failed() {
+        state = state.restore_from_failure(#mark.clone());
+        let #expectation = state.expectation();
+        let mut #synchronized = false;
+        loop {
+          let #sync_mark = state.mark();
+          state = #sync;
+          if state.is_successful() {
+            state.discard_data();
+            #synchronized = true;
+            break;
+          }
+          state = state.restore_from_failure(#sync_mark);
+          if state.next().is_none() {
+            break;
+          }
+        }
+        if #synchronized && state.mark() > #mark {
+          state.recover(#mark, #outer_expectation, #expectation);
+        }
+        else {
+          state.expectation_failure(#expectation);
+        }
+      }
+      if state.is_successful() {
+        #success
+      }
+      else {
+        #failure
+      }
+    })
+  }
+
+  fn compile_recognizer<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let body = context.compile_recognizer_expr(self.expr_idx);
+    self.compile(context, continuation, body)
+  }
+
+  fn value_constructor(result_var: Ident, result_value: syn::Expr) -> syn::Expr {
+    parse_quote!({
+      #result_var = Some(#result_value);
+      state
+    })
+  }
+
+  fn compile_parser<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let ty: syn::Type = parse_quote!(Option<_>);
+    let (body, result_var) = context.value_constructor(
+      self.expr_idx,
+      ty,
+      RecoverCompiler::value_constructor
+    );
+    let recover_expr = self.compile(context, continuation, body);
+    parse_quote!({
+      let mut #result_var = None;
+      #recover_expr
+    })
+  }
+}
+
+impl CompileExpr for RecoverCompiler
+{
+  fn compile_expr<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    match self.compiler_kind {
+      CompilerKind::Recognizer => self.compile_recognizer(context, continuation),
+      CompilerKind::Parser => self.compile_parser(context, continuation)
+    }
+  }
+}
@@ -18,24 +18,64 @@ pub struct RepeatCompiler
 {
   expr_idx: usize,
   cardinality_min: usize,
-  compiler_kind: CompilerKind
+  cardinality_max: Option<usize>,
+  compiler_kind: CompilerKind,
+  /// The container collecting the values with `e*:String`, `Vec` if `None`.
+  container: Option<syn::Type>,
+  /// True for `repeat_until_eoi(e)`, see `compile_until_eoi`.
+  until_eoi: bool,
+  /// True for `count(e)`, see `compile_count`.
+  count: bool
 }
 
 impl RepeatCompiler
 {
-  pub fn recognizer(expr_idx: usize, cardinality_min: usize) -> RepeatCompiler {
+  pub fn recognizer(expr_idx: usize, cardinality_min: usize,
+    cardinality_max: Option<usize>) -> RepeatCompiler
+  {
     RepeatCompiler {
       expr_idx: expr_idx,
       cardinality_min: cardinality_min,
-      compiler_kind: CompilerKind::Recognizer
+      cardinality_max,
+      compiler_kind: CompilerKind::Recognizer,
+      container: None,
+      until_eoi: false,
+      count: false
     }
   }
 
-  pub fn parser(expr_idx: usize, cardinality_min: usize) -> RepeatCompiler {
+  pub fn parser(expr_idx: usize, cardinality_min: usize,
+    cardinality_max: Option<usize>, container: Option<syn::Type>) -> RepeatCompiler
+  {
     RepeatCompiler {
       expr_idx: expr_idx,
       cardinality_min: cardinality_min,
-      compiler_kind: CompilerKind::Parser
+      cardinality_max,
+      compiler_kind: CompilerKind::Parser,
+      container,
+      until_eoi: false,
+      count: false
+    }
+  }
+
+  pub fn recognizer_until_eoi(expr_idx: usize) -> RepeatCompiler {
+    RepeatCompiler {
+      until_eoi: true,
+      .. RepeatCompiler::recognizer(expr_idx, 0, None)
+    }
+  }
+
+  pub fn parser_until_eoi(expr_idx: usize, container: Option<syn::Type>) -> RepeatCompiler {
+    RepeatCompiler {
+      until_eoi: true,
+      .. RepeatCompiler::parser(expr_idx, 0, None, container)
+    }
+  }
+
+  pub fn parser_count(expr_idx: usize) -> RepeatCompiler {
+    RepeatCompiler {
+      count: true,
+      .. RepeatCompiler::parser(expr_idx, 0, None, None)
     }
   }
 
@@ -44,7 +84,13 @@ impl RepeatCompiler
   {
     let mark = context.next_mark_name();
     continuation.map_success(|success, failure|
-      if self.cardinality_min > 0 {
+      if self.until_eoi {
+        Self::compile_until_eoi(body, success, failure)
+      }
+      else if let Some(cardinality_max) = self.cardinality_max {
+        self.compile_bounded(context, mark, cardinality_max, body, success, failure)
+      }
+      else if self.cardinality_min > 0 {
         let counter = context.next_counter_name();
         let cardinality_min = self.cardinality_min;
         parse_quote!(
@@ -93,6 +139,72 @@ impl RepeatCompiler
     .unwrap_success()
   }
 
+  // The loop only stops at the end of input: if `e` fails before, the whole expression fails with the errors of `e` instead of leaving the rest of the input unread.
+  fn compile_until_eoi(body: syn::Expr, success: syn::Expr, failure: syn::Expr) -> syn::Expr {
+    parse_quote!(
+      {
+        while state.current.has_next() {
+          state = #body;
+          if state.is_failed() {
+            break;
+          }
+        }
+        if state.is_failed() {
+          #failure
+        }
+        else {
+          #success
+        }
+      }
+    )
+  }
+
+  // The loop stops as soon as `cardinality_max` repetitions succeeded, the state is then successful and must not be restored.
+  fn compile_bounded<'a>(&self, context: &mut Context<'a>, mark: Ident,
+    cardinality_max: usize, body: syn::Expr, success: syn::Expr,
+    failure: syn::Expr) -> syn::Expr
+  {
+    let counter = context.next_counter_name();
+    let cardinality_min = self.cardinality_min;
+    let restore_and_succeed: syn::Expr = parse_quote!(
+      {
+        let mut state =
+          if state.is_failed() { state.restore_from_failure(#mark) }
+          else { state };
+        #success
+      }
+    );
+    let check_min: syn::Expr =
+      if cardinality_min > 0 {
+        parse_quote!(
+          if #counter < #cardinality_min {
+            #failure
+          }
+          else #restore_and_succeed
+        )
+      }
+      else {
+        restore_and_succeed
+      };
+    parse_quote!(
+      {
+        let mut #mark = state.mark();
+        let mut #counter = 0;
+        while #counter < #cardinality_max {
+          state = #body;
+          if state.is_successful() {
+            #counter += 1;
+            #mark = state.mark();
+          }
+          else {
+            break;
+          }
+        }
+        #check_min
+      }
+    )
+  }
+
   fn compile_recognizer<'a>(&self, context: &mut Context<'a>,
     continuation: Continuation) -> syn::Expr
   {
@@ -100,6 +212,36 @@ impl RepeatCompiler
     self.compile(context, continuation, body)
   }
 
+  // `count(e)` repeats the recognizer of `e` as `e*` does, no value of `e` is built and only the number of repetitions is kept.
+  fn compile_count<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let result = context.next_free_var();
+    let body = context.compile_recognizer_expr(self.expr_idx);
+    let mark = context.next_mark_name();
+    let counter = context.next_counter_name();
+    continuation.map_success(|success, _| parse_quote!(
+      {
+        let mut #mark = state.mark();
+        let mut #counter: usize = 0;
+        loop {
+          state = #body;
+          if state.is_successful() {
+            #counter += 1;
+            #mark = state.mark();
+          }
+          else {
+            break;
+          }
+        }
+        let mut state = state.restore_from_failure(#mark);
+        let #result = #counter;
+        #success
+      }
+    ))
+    .unwrap_success()
+  }
+
   fn value_constructor(result_var: Ident, result_value: syn::Expr) -> syn::Expr {
     parse_quote!({
       #result_var.push(#result_value);
@@ -107,20 +249,44 @@ impl RepeatCompiler
     })
   }
 
+  fn extend_constructor(result_var: Ident, result_value: syn::Expr) -> syn::Expr {
+    parse_quote!({
+      #result_var.extend(Some(#result_value));
+      state
+    })
+  }
+
   fn compile_parser<'a>(&self, context: &mut Context<'a>,
     continuation: Continuation) -> syn::Expr
   {
-    let ty: syn::Type = parse_quote!(Vec<_>);
-    let (body, result_var) = context.value_constructor(
-      self.expr_idx,
-      ty,
-      RepeatCompiler::value_constructor
-    );
-    let repeat_expr = self.compile(context, continuation, body);
-    parse_quote!({
-      let mut #result_var = vec![];
-      #repeat_expr
-    })
+    match self.container.clone() {
+      None => {
+        let ty: syn::Type = parse_quote!(Vec<_>);
+        let (body, result_var) = context.value_constructor(
+          self.expr_idx,
+          ty,
+          RepeatCompiler::value_constructor
+        );
+        let repeat_expr = self.compile(context, continuation, body);
+        parse_quote!({
+          let mut #result_var = Vec::new();
+          #repeat_expr
+        })
+      }
+      // The container must implement `Default` and `Extend` of the values.
+      Some(ty) => {
+        let (body, result_var) = context.value_constructor(
+          self.expr_idx,
+          ty.clone(),
+          RepeatCompiler::extend_constructor
+        );
+        let repeat_expr = self.compile(context, continuation, body);
+        parse_quote!({
+          let mut #result_var: #ty = Default::default();
+          #repeat_expr
+        })
+      }
+    }
   }
 }
 
@@ -131,6 +297,7 @@ impl CompileExpr for RepeatCompiler
   {
     match self.compiler_kind {
       CompilerKind::Recognizer => self.compile_recognizer(context, continuation),
+      CompilerKind::Parser if self.count => self.compile_count(context, continuation),
       CompilerKind::Parser => self.compile_parser(context, continuation)
     }
   }
@@ -57,7 +57,7 @@ impl<'a> TypeCompiler<'a>
   }
 
   fn atom_type(&self) -> syn::Type {
-    parse_quote!(char)
+    self.grammar.atom_type()
   }
 
   fn list_type(&self, expr_idx: usize) -> syn::Type {
@@ -25,10 +25,15 @@ impl<'a> RuleCompiler<'a>
 {
   pub fn compile(grammar: &'a TGrammar, rule: Rule) -> Vec<syn::Item> {
     let compiler = RuleCompiler::new(grammar, rule);
-    vec![
-      compiler.compile_recognizer(),
-      compiler.compile_parser()
-    ]
+    if compiler.rule.recognizer_only {
+      vec![compiler.compile_recognizer()]
+    }
+    else {
+      vec![
+        compiler.compile_recognizer(),
+        compiler.compile_parser()
+      ]
+    }
   }
 
   fn new(grammar: &'a TGrammar, rule: Rule) -> Self {
@@ -39,7 +44,7 @@ impl<'a> RuleCompiler<'a>
   }
 
   fn compile_recognizer(&self) -> syn::Item {
-    let mut context = Context::new(self.grammar);
+    let mut context = self.context();
     let success = parse_quote!(state.success(()));
     let failure = parse_quote!(state.failure());
 
@@ -50,7 +55,7 @@ impl<'a> RuleCompiler<'a>
   }
 
   fn compile_parser(&self) -> syn::Item {
-    let mut context = Context::new(self.grammar);
+    let mut context = self.context();
     if self.parser_equals_recognizer() {
       context.into_parser_alias(self.rule.clone())
     }
@@ -68,8 +73,15 @@ impl<'a> RuleCompiler<'a>
     }
   }
 
+  /// The spacing is not called inside the spacing rule itself.
+  fn context(&self) -> Context<'a> {
+    let mut context = Context::new(self.grammar);
+    context.set_strict(self.grammar.is_spacing_rule(&self.rule.ident()));
+    context
+  }
+
   fn parser_equals_recognizer(&self) -> bool {
-    self.grammar[self.expr()].ty == Type::Unit
+    self.grammar.is_unit_type(self.expr())
   }
 
   fn expr(&self) -> usize {
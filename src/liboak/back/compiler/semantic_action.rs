@@ -37,8 +37,9 @@ impl CompileExpr for SemanticActionCompiler
   {
     let result = context.next_free_var();
     let scope = context.open_scope(self.expr_idx);
-    let args: Vec<syn::Expr> = context.free_variables().into_iter()
-      .map(|var| parse_quote!(#var))
+    let vars = context.free_variables();
+    let args: Vec<syn::Expr> = context.action_arguments(self.expr_idx, &self.action).into_iter()
+      .map(|i| { let var = &vars[i]; parse_quote!(#var) })
       .collect();
     let action = self.action.clone();
     let is_unit_variant =
@@ -46,27 +47,52 @@ impl CompileExpr for SemanticActionCompiler
         syn::Expr::Path(ref expr_path) =>
           if let Some(x) = expr_path.path.segments.last() {
             x.ident.to_string().chars().next().expect("non empty identifier").is_uppercase() &&
-            context.has_unit_type(self.expr_idx)
+            context.is_unit_type(self.expr_idx)
           }
           else { false }
         _ => false
       };
+    let fallible = context.is_fallible_action(&self.action);
+    let boxed = self.boxed;
     let expr = continuation
-      .map_success(|success, _| {
+      .map_success(|success, failure| {
         let action_call: syn::Expr =
           if is_unit_variant {
             parse_quote!(#action)
           }
+          else if let syn::Expr::Closure(_) = action {
+            parse_quote!((#action)(#(#args),*))
+          }
           else {
             parse_quote!(#action(#(#args),*))
           };
-        let boxed_action_call: syn::Expr =
-          if self.boxed { parse_quote!(Box::new(#action_call)) }
-          else { action_call };
-        parse_quote!({
-          let #result = #boxed_action_call;
-          #success
-        })})
+        let boxed_value = |value: syn::Expr| -> syn::Expr {
+          if boxed { parse_quote!(Box::new(#value)) }
+          else { value }
+        };
+        // An action returning `Err` fails the expression, which can be recovered by an enclosing choice, and its error is reported if the parsing fails.
+        if fallible {
+          let value = boxed_value(parse_quote!(value));
+          parse_quote!(
+            match #action_call {
+              Ok(value) => {
+                let #result = #value;
+                #success
+              }
+              Err(error) => {
+                state.action_error(error);
+                #failure
+              }
+            }
+          )
+        }
+        else {
+          let boxed_action_call = boxed_value(action_call);
+          parse_quote!({
+            let #result = #boxed_action_call;
+            #success
+          })
+        }})
       .compile_success(context, parser_compiler, self.expr_idx)
       .unwrap_success();
     context.close_scope(scope);
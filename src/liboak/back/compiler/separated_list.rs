@@ -0,0 +1,162 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+use quote::format_ident;
+
+/// Compiles `e % sep` and `e %% sep`: an element `e` is parsed, and then `sep e` is repeated as long as it succeeds.
+/// If `sep` succeeds but not the following `e`, we restore the state before `sep`, similarly to `OptionalCompiler`, thus a trailing separator is not consumed.
+/// With `separated_nonempty(e, sep)` (`strict`), the list fails instead, and the error is reported just after the trailing separator where `e` is expected.
+pub struct SeparatedListCompiler
+{
+  expr_idx: usize,
+  sep_idx: usize,
+  one_or_more: bool,
+  strict: bool,
+  compiler_kind: CompilerKind
+}
+
+impl SeparatedListCompiler
+{
+  pub fn recognizer(expr_idx: usize, sep_idx: usize, one_or_more: bool, strict: bool) -> SeparatedListCompiler {
+    SeparatedListCompiler {
+      expr_idx,
+      sep_idx,
+      one_or_more,
+      strict,
+      compiler_kind: CompilerKind::Recognizer
+    }
+  }
+
+  pub fn parser(expr_idx: usize, sep_idx: usize, one_or_more: bool, strict: bool) -> SeparatedListCompiler {
+    SeparatedListCompiler {
+      expr_idx,
+      sep_idx,
+      one_or_more,
+      strict,
+      compiler_kind: CompilerKind::Parser
+    }
+  }
+
+  fn compile<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation, body: syn::Expr) -> syn::Expr
+  {
+    let sep = context.compile_recognizer_expr(self.sep_idx);
+    let mark = context.next_mark_name();
+    let counter = context.next_counter_name();
+    let trailing = format_ident!("{}_trailing", mark);
+    continuation.map_success(|success, failure| {
+      let restore_and_succeed: syn::Expr = parse_quote!(
+        {
+          let mut state = state.restore_from_failure(#mark);
+          #success
+        }
+      );
+      let mut check_min: syn::Expr =
+        if self.one_or_more {
+          parse_quote!(
+            if #counter == 0 {
+              #failure
+            }
+            else #restore_and_succeed
+          )
+        }
+        else {
+          restore_and_succeed
+        };
+      if self.strict {
+        // The state is failed after the trailing separator and it is not restored.
+        check_min = parse_quote!(
+          if #trailing {
+            #failure
+          }
+          else #check_min
+        );
+      }
+      let (declare_trailing, set_trailing): (Vec<syn::Stmt>, Vec<syn::Stmt>) =
+        if self.strict {
+          (vec![parse_quote!(let mut #trailing = false;)], vec![parse_quote!(#trailing = #counter > 0;)])
+        }
+        else { (vec![], vec![]) };
+      parse_quote!(
+        {
+          let mut #mark = state.mark();
+          let mut #counter = 0;
+          #(#declare_trailing)*
+          loop {
+            if #counter > 0 {
+              state = #sep;
+              if state.is_failed() {
+                break;
+              }
+            }
+            state = #body;
+            if state.is_successful() {
+              #counter += 1;
+              #mark = state.mark();
+            }
+            else {
+              #(#set_trailing)*
+              break;
+            }
+          }
+          #check_min
+        }
+      )
+    })
+    .unwrap_success()
+  }
+
+  fn compile_recognizer<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let body = context.compile_recognizer_expr(self.expr_idx);
+    self.compile(context, continuation, body)
+  }
+
+  fn value_constructor(result_var: Ident, result_value: syn::Expr) -> syn::Expr {
+    parse_quote!({
+      #result_var.push(#result_value);
+      state
+    })
+  }
+
+  fn compile_parser<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let ty: syn::Type = parse_quote!(Vec<_>);
+    let (body, result_var) = context.value_constructor(
+      self.expr_idx,
+      ty,
+      SeparatedListCompiler::value_constructor
+    );
+    let list_expr = self.compile(context, continuation, body);
+    parse_quote!({
+      let mut #result_var = Vec::new();
+      #list_expr
+    })
+  }
+}
+
+impl CompileExpr for SeparatedListCompiler
+{
+  fn compile_expr<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    match self.compiler_kind {
+      CompilerKind::Recognizer => self.compile_recognizer(context, continuation),
+      CompilerKind::Parser => self.compile_parser(context, continuation)
+    }
+  }
+}
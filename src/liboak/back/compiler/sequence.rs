@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use back::compiler::*;
+use back::compiler::non_terminal::*;
 
 pub struct SequenceCompiler
 {
@@ -42,10 +43,25 @@ impl CompileExpr for SequenceCompiler
   fn compile_expr<'a>(&self, context: &mut Context<'a>,
     continuation: Continuation) -> syn::Expr
   {
+    let spacing = context.spacing_rule();
+    let last = self.seq.len() - 1;
     self.seq.clone().into_iter()
+      .enumerate()
       .rev()
-      .fold(continuation, |continuation, idx|
-        continuation.compile_success(context, self.compiler, idx))
+      .fold(continuation, |continuation, (i, idx)| {
+        let continuation = match spacing {
+          Some(ref spacing) if i < last => compile_spacing(context, spacing.clone(), continuation),
+          _ => continuation
+        };
+        continuation.compile_success(context, self.compiler, idx)
+      })
       .unwrap_success()
   }
 }
+
+/// With `#![spacing(r)]`, the rule `r` is called between the elements of the sequence.
+fn compile_spacing<'a>(context: &mut Context<'a>, spacing: Ident, continuation: Continuation) -> Continuation {
+  continuation.map_success(|success, failure|
+    NonTerminalCompiler::recognizer(spacing)
+      .compile_expr(context, Continuation::new(success, failure)))
+}
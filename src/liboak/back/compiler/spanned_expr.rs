@@ -14,15 +14,27 @@
 
 use back::compiler::*;
 
+/// The value built from the marks delimitating the input matched by the expression.
+pub enum Extent {
+  /// `.. e` produces a span.
+  Span,
+  /// `... e` produces the range of streams.
+  Range,
+  /// `spanned(e)` produces the range of bytes offsets.
+  Bytes,
+  /// `$e` produces the slice of the input.
+  Slice
+}
+
 pub struct SpannedExprCompiler{
   expr_idx: usize,
-  is_ranged: bool
+  extent: Extent
 }
 
 impl SpannedExprCompiler
 {
-  pub fn parser(expr_idx: usize, is_ranged: bool) -> SpannedExprCompiler {
-    SpannedExprCompiler { expr_idx, is_ranged }
+  pub fn parser(expr_idx: usize, extent: Extent) -> SpannedExprCompiler {
+    SpannedExprCompiler { expr_idx, extent }
   }
 }
 
@@ -35,12 +47,17 @@ impl CompileExpr for SpannedExprCompiler
     // The `n` next variable belongs to expr_idx so we pop the next one after these.
     let result = context.next_free_var_skip(self.expr_idx);
 
-    let mut result_expr: syn::Expr = parse_quote!(
+    let range_expr: syn::Expr = parse_quote!(
       Range { start: #lo_sp.clone(), end: state.mark() }
     );
-    if !self.is_ranged {
-      result_expr = parse_quote!((#result_expr).stream_span());
-    }
+    let result_expr: syn::Expr = match self.extent {
+      Extent::Span => parse_quote!((#range_expr).stream_span()),
+      Extent::Range => range_expr,
+      Extent::Bytes => parse_quote!(
+        Range { start: #lo_sp.byte_offset(), end: state.mark().byte_offset() }
+      ),
+      Extent::Slice => parse_quote!((#range_expr).stream_slice())
+    };
 
     context.push_mark(lo_sp.clone());
 
@@ -16,19 +16,21 @@ use back::compiler::*;
 
 pub struct StrLiteralCompiler
 {
-  literal: String
+  literal: String,
+  case_insensitive: bool
 }
 
 impl StrLiteralCompiler
 {
-  pub fn recognizer(literal: String) -> StrLiteralCompiler {
+  pub fn recognizer(literal: String, case_insensitive: bool) -> StrLiteralCompiler {
     StrLiteralCompiler {
-      literal: literal
+      literal,
+      case_insensitive
     }
   }
 
-  pub fn parser(literal: String) -> StrLiteralCompiler {
-    StrLiteralCompiler::recognizer(literal)
+  pub fn parser(literal: String, case_insensitive: bool) -> StrLiteralCompiler {
+    StrLiteralCompiler::recognizer(literal, case_insensitive)
   }
 }
 
@@ -38,13 +40,23 @@ impl CompileExpr for StrLiteralCompiler
     continuation: Continuation) -> syn::Expr
   {
     let lit = self.literal.as_str();
+    // Only the ASCII characters are compared case-insensitively, the other characters must match exactly.
+    let (consume_prefix, expected): (syn::Expr, String) =
+      if self.case_insensitive {
+        (parse_quote!(state.consume_prefix_ignore_ascii_case(#lit)),
+         format!("{} (case-insensitive)", lit))
+      }
+      else {
+        (parse_quote!(state.consume_prefix(#lit)), lit.to_string())
+      };
+    let expected = expected.as_str();
     continuation
       .map_success(|success, failure| parse_quote!(
-        if state.consume_prefix(#lit) {
+        if #consume_prefix {
           #success
         }
         else {
-          state.error(#lit);
+          state.error(#expected);
           #failure
         }
       ))
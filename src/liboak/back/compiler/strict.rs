@@ -0,0 +1,51 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+
+pub struct StrictCompiler
+{
+  expr_idx: usize,
+  compiler: ExprCompilerFn
+}
+
+impl StrictCompiler
+{
+  pub fn recognizer(expr_idx: usize) -> StrictCompiler {
+    StrictCompiler {
+      expr_idx,
+      compiler: recognizer_compiler
+    }
+  }
+
+  pub fn parser(expr_idx: usize) -> StrictCompiler {
+    StrictCompiler {
+      expr_idx,
+      compiler: parser_compiler
+    }
+  }
+}
+
+impl CompileExpr for StrictCompiler
+{
+  fn compile_expr<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let previous = context.set_strict(true);
+    let (success, failure) = continuation.unwrap();
+    let expr = context.compile_success(self.compiler, self.expr_idx, success, failure);
+    context.set_strict(previous);
+    expr
+  }
+}
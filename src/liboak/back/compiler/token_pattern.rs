@@ -0,0 +1,73 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use back::compiler::*;
+
+/// `token(p)` reads the next atom and succeeds if it matches the Rust pattern `p`, for example `token(Token::Plus | Token::Minus)`.
+/// In the parser, the atom read is bound to a variable.
+pub struct TokenPatternCompiler
+{
+  pattern: syn::Pat,
+  bind: bool
+}
+
+impl TokenPatternCompiler
+{
+  pub fn recognizer(pattern: syn::Pat) -> TokenPatternCompiler {
+    TokenPatternCompiler { pattern, bind: false }
+  }
+
+  pub fn parser(pattern: syn::Pat) -> TokenPatternCompiler {
+    TokenPatternCompiler { pattern, bind: true }
+  }
+}
+
+impl CompileExpr for TokenPatternCompiler
+{
+  fn compile_expr<'a>(&self, context: &mut Context<'a>,
+    continuation: Continuation) -> syn::Expr
+  {
+    let pattern = &self.pattern;
+    let pattern_desc = format!("{}", quote!(#pattern));
+    let pattern_desc_str = pattern_desc.as_str();
+    // The pattern is kept as tokens since `syn::Pat` cannot be parsed from a top-level or-pattern.
+    let matched =
+      if self.bind {
+        let var = context.next_free_var();
+        match pattern {
+          &syn::Pat::Or(_) => quote!(#var @ (#pattern)),
+          _ => quote!(#var @ #pattern)
+        }
+      }
+      else {
+        quote!(#pattern)
+      };
+    let mark = context.next_mark_name();
+    continuation
+      .map_success(|success, failure| parse_quote!({
+        let #mark = state.mark();
+        match state.next() {
+          Some(#matched) => {
+            #success
+          }
+          _ => {
+            state = state.restore(#mark);
+            state.error(#pattern_desc_str);
+            #failure
+          }
+        }
+      }))
+     .unwrap_success()
+  }
+}
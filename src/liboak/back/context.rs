@@ -19,6 +19,7 @@ use back::compiler::ExprCompilerFn;
 use back::compiler::rtype::*;
 use back::compiler::{recognizer_compiler, parser_compiler};
 use back::compiler::value::*;
+use middle::analysis::ast::FirstSet;
 
 use quote::quote;
 use syn::parse_quote;
@@ -29,9 +30,11 @@ pub struct Context<'a>
   closures: Vec<syn::Stmt>,
   name_factory: NameFactory,
   free_variables: Vec<Ident>,
-  mark_variables: Vec<Ident>,
-  mut_ref_free_variables: Vec<(Ident, syn::Type)>,
-  num_combinators_compiled: usize
+  mark_variables: Vec<(Ident, syn::Type)>,
+  /// The variables modified by the success continuations, the boolean is true if the variable is already a `&mut` reference (see `push_mut_ref_fv_by_ref`).
+  mut_ref_free_variables: Vec<(Ident, syn::Type, bool)>,
+  num_combinators_compiled: usize,
+  strict: bool
 }
 
 impl<'a> Context<'a>
@@ -45,20 +48,39 @@ impl<'a> Context<'a>
       free_variables: vec![],
       mark_variables: vec![],
       mut_ref_free_variables: vec![],
-      num_combinators_compiled: 0
+      num_combinators_compiled: 0,
+      strict: false
     }
   }
 
   pub fn into_recognizer_function(self, body: syn::Expr, rule: Rule) -> syn::Item {
     let recognizer_fn = recognizer_id(rule.ident());
-    self.function(recognizer_fn, true, body, parse_quote!(()))
+    let rule_id = self.rule_id(&rule);
+    let (body, state_mut) =
+      if rule.left_recursive {
+        (parse_quote!(state.grow_left_recursion_recognizer(#rule_id, |mut state| { #body })), false)
+      }
+      else if rule.memoized {
+        (parse_quote!(state.memoize_recognizer(#rule_id, |mut state| { #body })), false)
+      }
+      else {
+        (body, true)
+      };
+    let (body, state_mut) = self.depth_limit(body, state_mut);
+    let body = self.trace(&recognizer_fn, body);
+    let body = self.cst(&rule, body);
+    let summary = format!(
+      " Recognizes the rule `{}` from the current position of `state`, without building its value.", rule.ident());
+    self.function(&rule, recognizer_fn, summary, state_mut, body, parse_quote!(()))
   }
 
   pub fn into_parser_alias(self, rule: Rule) -> syn::Item {
     let id = rule.ident();
     let recognizer_fn = recognizer_name(parse_quote!(#id));
+    let summary = format!(
+      " Parses the rule `{}` from the current position of `state`, its value is `()` and it is the same as `{}`.", id, quote!(#recognizer_fn));
     let parser_fn = parser_id(id);
-    self.function(parser_fn, false,
+    self.function(&rule, parser_fn, summary, false,
       parse_quote!(#recognizer_fn(state)),
       parse_quote!(()))
   }
@@ -66,17 +88,118 @@ impl<'a> Context<'a>
   pub fn into_parser_function(self, body: syn::Expr, rule: Rule) -> syn::Item {
     let parser_fn = parser_id(rule.ident());
     let ty = TypeCompiler::compile(self.grammar, rule.expr_idx);
-    self.function(parser_fn, true, body, ty)
+    let rule_id = self.rule_id(&rule);
+    let (body, state_mut) =
+      if rule.left_recursive {
+        (parse_quote!(state.grow_left_recursion_parser(#rule_id, |mut state| { #body })), false)
+      }
+      else if rule.memoized {
+        (parse_quote!(state.memoize_parser(#rule_id, |mut state| { #body })), false)
+      }
+      else {
+        (body, true)
+      };
+    let (body, state_mut) = self.depth_limit(body, state_mut);
+    let body = self.trace(&parser_fn, body);
+    let body = self.cst(&rule, body);
+    let summary = format!(
+      " Parses the rule `{}` from the current position of `state` and builds its value of type `{}`.", rule.ident(), quote!(#ty));
+    self.function(&rule, parser_fn, summary, state_mut, body, ty)
+  }
+
+  /// The doc comments of the rule, if any, are followed by the description `summary` of the generated function.
+  fn documentation(rule: &Rule, summary: String) -> Vec<syn::Attribute> {
+    let mut doc = rule.doc.clone();
+    if !doc.is_empty() {
+      doc.push(parse_quote!(#[doc = ""]));
+    }
+    doc.push(parse_quote!(#[doc = #summary]));
+    doc
+  }
+
+  /// With `#![trace]`, the call of the function is printed when entering and exiting it, nothing is generated otherwise.
+  /// The parser alias of a recognizer is not traced since it only calls the recognizer.
+  fn trace(&self, fn_name: &Ident, body: syn::Expr) -> syn::Expr {
+    if self.grammar.attributes.trace {
+      let name = fn_name.to_string();
+      parse_quote!({
+        oak_runtime::trace::trace_enter(#name, &state.current);
+        let state = #body;
+        oak_runtime::trace::trace_exit(#name, &state.current, state.is_successful());
+        state
+      })
+    }
+    else {
+      body
+    }
+  }
+
+  /// Identifies the rule in the memoization table, which is shared with the other grammars called with the same state.
+  fn rule_id(&self, rule: &Rule) -> syn::Expr {
+    let rule_idx = self.grammar.rule_index(&rule.ident());
+    let marker = grammar_marker_id();
+    parse_quote!(oak_runtime::RuleId::new::<#marker>(#rule_idx))
+  }
+
+  /// With `#![cst]`, the node of the rule is built in the concrete syntax tree recorded by `oak_runtime::cst`, nothing is generated otherwise.
+  /// The hidden rules of the let bindings have no node, the nodes of the rules they call are the children of the enclosing rule.
+  fn cst(&self, rule: &Rule, body: syn::Expr) -> syn::Expr {
+    if self.grammar.attributes.cst && !rule.hidden {
+      let name = rule.ident().to_string();
+      parse_quote!({
+        oak_runtime::cst::cst_enter(#name, &state.current);
+        let state = #body;
+        oak_runtime::cst::cst_exit(&state.current, state.is_successful());
+        state
+      })
+    }
+    else {
+      body
+    }
+  }
+
+  /// With `#![depth_limit]`, the function fails instead of calling its body if too many rules are being called.
+  /// The state is then rebound as mutable in the body, the parameter does not need to be.
+  fn depth_limit(&self, body: syn::Expr, state_mut: bool) -> (syn::Expr, bool) {
+    match self.grammar.attributes.depth_limit {
+      Some(limit) => (parse_quote!({
+        let mut state = state;
+        if state.enter_rule(#limit) {
+          let mut state = #body;
+          state.exit_rule();
+          state
+        }
+        else {
+          state.failure()
+        }
+      }), false),
+      None => (body, state_mut)
+    }
   }
 
-  fn function(self, name: Ident, state_mut: bool, body: syn::Expr, ty: syn::Type) -> syn::Item {
+  /// With `#![inline_rules]`, the functions of the rules are marked `#[inline(always)]`, except the memoized and left-recursive rules.
+  /// The functions are public so a rule can be called on its own, for example from a hand-written parser, `summary` describes the rule and the type of its value (see `documentation`).
+  /// The functions of the hidden rules of the let bindings are private, and they might not be called if only the recognizer of the enclosing rule is used.
+  fn function(self, rule: &Rule, name: Ident, summary: String, state_mut: bool, body: syn::Expr, ty: syn::Type) -> syn::Item {
+    let mut doc = Self::documentation(rule, summary);
+    let inline_always = self.grammar.attributes.inline_rules && !rule.memoized && !rule.left_recursive;
     let state_param = self.state_param(state_mut);
     let stream_ty = self.grammar.stream_type();
     let generics = self.grammar.stream_generics();
     let closures = self.closures;
+    let inline: syn::Attribute =
+      if inline_always { parse_quote!(#[inline(always)]) }
+      else { parse_quote!(#[inline]) };
+    let vis: syn::Visibility =
+      if rule.hidden {
+        doc.push(parse_quote!(#[allow(dead_code)]));
+        syn::Visibility::Inherited
+      }
+      else { parse_quote!(pub) };
     parse_quote!(
-      #[inline]
-      pub fn #name #generics (#state_param) -> oak_runtime::ParseState<#stream_ty, #ty>
+      #(#doc)*
+      #inline
+      #vis fn #name #generics (#state_param) -> oak_runtime::ParseState<#stream_ty, #ty>
       {
         #(#closures)*
         #body
@@ -165,18 +288,17 @@ impl<'a> Context<'a>
   }
 
   fn closure_params(&self) -> Vec<syn::FnArg> {
-    let stream_ty = self.grammar.stream_type();
     vec![self.state_param(true)]
       .into_iter()
       .chain(self.mut_ref_free_variables
         .iter().cloned()
-        .map(|(var, ty)| parse_quote!(#var: &mut #ty)))
+        .map(|(var, ty, _)| parse_quote!(#var: &mut #ty)))
       .chain(self.free_variables
         .iter()
         .map(|var| parse_quote!(#var:_)))
       .chain(self.mark_variables
         .iter()
-        .map(|var| parse_quote!(#var: #stream_ty)))
+        .map(|(var, ty)| parse_quote!(#var: #ty)))
       .collect()
   }
 
@@ -185,13 +307,15 @@ impl<'a> Context<'a>
       .into_iter()
       .chain(self.mut_ref_free_variables
         .iter().cloned()
-        .map(|(var, _)| parse_quote!(&mut #var)))
+        .map(|(var, _, by_ref)|
+          if by_ref { parse_quote!(&mut *#var) }
+          else { parse_quote!(&mut #var) }))
       .chain(self.free_variables
         .iter()
         .map(|var| parse_quote!(#var)))
       .chain(self.mark_variables
         .iter()
-        .map(|var| parse_quote!(#var.clone())))
+        .map(|(var, _)| parse_quote!(#var.clone())))
       .collect()
   }
 
@@ -207,6 +331,10 @@ impl<'a> Context<'a>
     self.name_factory.next_branch_failed_name()
   }
 
+  pub fn next_cut_saved_name(&mut self) -> Ident {
+    self.name_factory.next_cut_saved_name()
+  }
+
   pub fn next_free_var(&mut self) -> Ident {
     self.free_variables.pop().expect("Free variables are all bound.")
   }
@@ -220,7 +348,13 @@ impl<'a> Context<'a>
   // Push a mark variable (e.g., `mark1 = state.mark()`) in the set of free variables.
   // This is useful if a value constructor relies on a mark to be built, e.g. with the spanned operators `..` and `...`.
   pub fn push_mark(&mut self, mark: Ident) {
-    self.mark_variables.push(mark);
+    let stream_ty = self.grammar.stream_type();
+    self.push_mark_of_type(mark, stream_ty);
+  }
+
+  // Same as `push_mark` for a variable that is not a position in the stream, e.g. the number of expected items saved by `e^"label"`.
+  pub fn push_mark_of_type(&mut self, mark: Ident, ty: syn::Type) {
+    self.mark_variables.push((mark, ty));
   }
 
   pub fn pop_mark(&mut self) {
@@ -232,7 +366,12 @@ impl<'a> Context<'a>
   }
 
   pub fn push_mut_ref_fv(&mut self, mut_ref_var: Ident, mut_ref_ty: syn::Type) {
-    self.mut_ref_free_variables.push((mut_ref_var,mut_ref_ty));
+    self.mut_ref_free_variables.push((mut_ref_var, mut_ref_ty, false));
+  }
+
+  /// Same as `push_mut_ref_fv` for a variable declared as a `&mut` reference, so it is assigned with `*var = ...` in the expression as in the success closures.
+  pub fn push_mut_ref_fv_by_ref(&mut self, mut_ref_var: Ident, mut_ref_ty: syn::Type) {
+    self.mut_ref_free_variables.push((mut_ref_var, mut_ref_ty, true));
   }
 
   pub fn pop_mut_ref_fv(&mut self) {
@@ -244,8 +383,35 @@ impl<'a> Context<'a>
     self.grammar[expr_idx].type_cardinality()
   }
 
-  pub fn has_unit_type(&self, expr_idx: usize) -> bool {
-    self.grammar[expr_idx].ty == crate::middle::typing::ast::Type::Unit
+  /// The FIRST set of the expression, computed during the analysis.
+  pub fn first_set(&self, expr_idx: usize) -> Option<FirstSet> {
+    self.grammar.first_sets.get(&expr_idx).cloned()
+  }
+
+  /// The rule called between the elements of a sequence, unless we are compiling a strict expression.
+  pub fn spacing_rule(&self) -> Option<Ident> {
+    if self.strict { None }
+    else { self.grammar.attributes.spacing_rule.clone() }
+  }
+
+  /// Enables or disables the spacing in the sequences, and returns the previous mode.
+  pub fn set_strict(&mut self, strict: bool) -> bool {
+    std::mem::replace(&mut self.strict, strict)
+  }
+
+  /// True if the semantic action returns `Result<T, E>`, see `Grammar::fallible_action_type`.
+  pub fn is_fallible_action(&self, action: &syn::Expr) -> bool {
+    self.grammar.fallible_action_type(action).is_some()
+  }
+
+  /// The index of the value passed to each parameter of a semantic action, see `TGrammar::action_arguments`.
+  pub fn action_arguments(&self, expr_idx: usize, action: &syn::Expr) -> Vec<usize> {
+    self.grammar.action_arguments(expr_idx, action)
+  }
+
+  /// True if the expression produces no value, see `TGrammar::is_unit_type`.
+  pub fn is_unit_type(&self, expr_idx: usize) -> bool {
+    self.grammar.is_unit_type(expr_idx)
   }
 
   pub fn open_scope(&mut self, expr_idx: usize) -> Scope {
@@ -283,11 +449,11 @@ impl<'a> Context<'a>
 pub struct Scope {
   num_combinators_compiled: usize,
   free_variables: Vec<Ident>,
-  mut_ref_free_variables: Vec<(Ident, syn::Type)>
+  mut_ref_free_variables: Vec<(Ident, syn::Type, bool)>
 }
 
 impl Scope {
-  fn new(n: usize, fv: Vec<Ident>, mfv: Vec<(Ident, syn::Type)>) -> Self {
+  fn new(n: usize, fv: Vec<Ident>, mfv: Vec<(Ident, syn::Type, bool)>) -> Self {
     Scope {
       num_combinators_compiled: n,
       free_variables: fv,
@@ -50,6 +50,15 @@ impl Continuation
       self.failure.clone())
   }
 
+  /// Compiles the expression `idx` with this continuation, which is left unchanged so it can be used several times.
+  pub fn compile(&self, context: &mut Context,
+    compiler: ExprCompilerFn, idx: usize) -> syn::Expr
+  {
+    context.compile_success(compiler, idx,
+      self.success.clone(),
+      self.failure.clone())
+  }
+
   pub fn map_success<F>(mut self, f: F) -> Self where
    F: FnOnce(syn::Expr, syn::Expr) -> syn::Expr
   {
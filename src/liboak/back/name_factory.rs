@@ -38,11 +38,70 @@ pub fn recognizer_id(id: Ident) -> Ident {
   format_ident!("recognize_{}", id)
 }
 
+pub fn full_parser_id(id: Ident) -> Ident {
+  format_ident!("full_parse_{}", id)
+}
+
+pub fn partial_parser_id(id: Ident) -> Ident {
+  format_ident!("partial_parse_{}", id)
+}
+
+pub fn all_parser_id(id: Ident) -> Ident {
+  format_ident!("iter_parse_{}", id)
+}
+
+pub fn cst_parser_id(id: Ident) -> Ident {
+  format_ident!("cst_parse_{}", id)
+}
+
+/// The marker type identifying the rules of the grammar in the memoization table of the state.
+pub fn grammar_marker_id() -> Ident {
+  format_ident!("OakGrammar")
+}
+
+/// The rule `sum_expr` is named `SumExprOutput`.
+pub fn type_alias_id(id: Ident) -> Ident {
+  let camel_case: String = id.to_string()
+    .split('_')
+    .filter(|word| !word.is_empty())
+    .map(|word| {
+      let mut chars = word.chars();
+      let first = chars.next().unwrap();
+      first.to_uppercase().chain(chars).collect::<String>()
+    })
+    .collect();
+  format_ident!("{}Output", camel_case)
+}
+
+/// The type `BinOp` is named `bin_op` in the methods of the generated `Visitor`.
+fn snake_case(id: &Ident) -> String {
+  let mut snake_case = String::new();
+  for (i, c) in id.to_string().chars().enumerate() {
+    if c.is_uppercase() {
+      if i > 0 { snake_case.push('_'); }
+      snake_case.extend(c.to_lowercase());
+    }
+    else {
+      snake_case.push(c);
+    }
+  }
+  snake_case
+}
+
+pub fn visit_type_id(ty: &Ident) -> Ident {
+  format_ident!("visit_{}", snake_case(ty))
+}
+
+pub fn walk_type_id(ty: &Ident) -> Ident {
+  format_ident!("walk_{}", snake_case(ty))
+}
+
 pub struct NameFactory
 {
   prefix_uid: usize,
   mark_uid: usize,
   branch_failed_uid: usize,
+  cut_uid: usize,
   counter_uid: usize,
   closure_uid: usize
 }
@@ -54,6 +113,7 @@ impl NameFactory
       prefix_uid: 1,
       mark_uid: 0,
       branch_failed_uid: 0,
+      cut_uid: 0,
       counter_uid: 0,
       closure_uid: 0
     }
@@ -69,6 +129,11 @@ impl NameFactory
     format_ident!("branch_failed_{}", self.branch_failed_uid)
   }
 
+  pub fn next_cut_saved_name(&mut self) -> Ident {
+    self.cut_uid += 1;
+    format_ident!("cut_saved_{}", self.cut_uid)
+  }
+
   pub fn next_closure_name(&mut self) -> Ident {
     self.closure_uid += 1;
     format_ident!("success_continuation_{}", self.closure_uid)
@@ -18,10 +18,15 @@ pub struct FGrammar
 {
   pub start_span: Span,
   pub rules: Vec<Rule>,
+  pub parametric_rules: Vec<ParametricRule>,
   pub exprs: Vec<Expression>,
   pub exprs_info: Vec<FExpressionInfo>,
   pub rust_items: Vec<syn::Item>,
-  pub attributes: Vec<syn::Attribute>
+  pub attributes: Vec<syn::Attribute>,
+  /// The paths given by `#![include("path")]`, a grammar cannot be included twice.
+  pub includes: Vec<String>,
+  /// The functions folding the levels of `infix(atom, f, ...)`, they are generated once the functions of the grammar are parsed.
+  pub infix_folds: Vec<InfixFold>
 }
 
 impl FGrammar
@@ -30,10 +35,13 @@ impl FGrammar
     FGrammar {
       start_span,
       rules: vec![],
+      parametric_rules: vec![],
       exprs: vec![],
       exprs_info: vec![],
       rust_items: vec![],
-      attributes: vec![]
+      attributes: vec![],
+      includes: vec![],
+      infix_folds: vec![]
     }
   }
 
@@ -48,6 +56,10 @@ impl FGrammar
     self.rules.push(Rule::new(name, def));
   }
 
+  pub fn push_parametric_rule(&mut self, name: Ident, params: Vec<Ident>, def: usize) {
+    self.parametric_rules.push(ParametricRule { name, params, expr_idx: def });
+  }
+
   pub fn push_attrs(&mut self, attrs: Vec<syn::Attribute>) {
     self.attributes.extend(attrs.into_iter());
   }
@@ -57,6 +69,38 @@ impl FGrammar
   }
 }
 
+/// The function `name(first, rest)` folding the operands `first` and the pairs `(op, operand)` of `rest` with the function `binary(lhs, op, rhs)` of the grammar.
+/// It is generated for each level `left(op)` or `right(op)` of `infix(atom, binary, ...)`, the levels with the same function and associativity share it.
+pub struct InfixFold
+{
+  pub name: Ident,
+  pub binary: Ident,
+  pub right_assoc: bool
+}
+
+/// A rule `r<p1, p2> = e` whose parameters are rules, it is expanded for each call `r<r1, r2>` by `middle::analysis::parametric_rule`.
+#[derive(Clone)]
+pub struct ParametricRule
+{
+  pub name: Ident,
+  pub params: Vec<Ident>,
+  pub expr_idx: usize
+}
+
+impl ItemIdent for ParametricRule
+{
+  fn ident(&self) -> Ident {
+    self.name.clone()
+  }
+}
+
+impl Spanned for ParametricRule
+{
+  fn span(&self) -> Span {
+    self.name.span()
+  }
+}
+
 // Implicitly typed expression.
 #[derive(Clone)]
 pub struct FExpressionInfo
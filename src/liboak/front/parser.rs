@@ -15,8 +15,10 @@
 use std::str::Chars;
 use std::iter::Peekable;
 
-use syn::{Token, Ident, Attribute, Result, Error, LitStr, parenthesized, bracketed};
-use syn::parse::{Parse, ParseStream};
+use syn::{Token, Ident, Attribute, Result, Error, LitStr, LitInt, parenthesized, bracketed, braced, parse_quote};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use quote::ToTokens;
 
 use front::ast::*;
 use front::ast::Expression::*;
@@ -25,6 +27,7 @@ impl Parse for FGrammar {
   fn parse(ps: ParseStream) -> Result<Self> {
     let mut grammar = FGrammar::new(ps.span());
     grammar.parse_blocks(ps)?;
+    grammar.push_infix_folds()?;
     Ok(grammar)
   }
 }
@@ -36,9 +39,26 @@ impl FGrammar {
 
   fn parse_blocks(&mut self, ps: ParseStream) -> Result<()> {
     while !ps.is_empty() {
-      self.push_attrs(ps.call(Attribute::parse_inner)?);
+      let attrs = ps.call(Attribute::parse_inner)?;
+      if let Some(attr) = attrs.iter().find(|attr| attr.path.is_ident("keyword_boundary")) {
+        if !self.rules.is_empty() || !self.parametric_rules.is_empty() {
+          return Err(Error::new_spanned(attr,
+            "`#![keyword_boundary(r)]` must be declared before the rules of the grammar."))
+        }
+      }
+      for attr in attrs.iter().filter(|attr| attr.path.is_ident("include")) {
+        self.parse_include(attr)?;
+      }
+      self.push_attrs(attrs);
+      if ps.is_empty() {
+        break;
+      }
       if self.peek_rule_lhs(ps) {
-        self.parse_rule(ps)?;
+        self.parse_rule(ps, vec![])?;
+      }
+      else if self.peek_documented_rule(ps) {
+        let doc = ps.call(Attribute::parse_outer)?;
+        self.parse_rule(ps, doc)?;
       }
       else {
         self.push_rust_item(ps.parse()?);
@@ -47,9 +67,42 @@ impl FGrammar {
     Ok(())
   }
 
-  // A rule can have two shapes:
+  /// `#![include("path")]` parses the grammar in the file `path`, relative to the directory of the crate, as if it was written in place of the attribute.
+  /// The tokens of the included grammar are given the span of the attribute, so the errors in this grammar are reported on the attribute.
+  fn parse_include(&mut self, attr: &Attribute) -> Result<()> {
+    let path = match attr.parse_meta() {
+      Ok(syn::Meta::List(ref list)) if list.nested.len() == 1 => match list.nested[0] {
+        syn::NestedMeta::Lit(syn::Lit::Str(ref path)) => path.value(),
+        _ => return Err(Error::new_spanned(attr, "`#![include(\"path\")]` expects the path of the grammar as a string literal."))
+      },
+      _ => return Err(Error::new_spanned(attr, "`#![include(\"path\")]` expects the path of the grammar as a string literal."))
+    };
+    if self.includes.contains(&path) {
+      return Err(Error::new_spanned(attr,
+        format!("the grammar `{}` is included more than once.", path).as_str()))
+    }
+    let file = crate_relative_path(&path);
+    let content = std::fs::read_to_string(&file).map_err(|err| Error::new_spanned(attr,
+      format!("cannot read the included grammar `{}`: {}.", file.display(), err).as_str()))?;
+    let tokens: proc_macro2::TokenStream = content.parse().map_err(|err| Error::new_spanned(attr,
+      format!("cannot tokenize the included grammar `{}`: {:?}.", path, err).as_str()))?;
+    let tokens = respan(tokens, attr.span());
+    let first_rule = self.rules.len();
+    self.includes.push(path.clone());
+    // The included file is a dependency of the crate, so the grammar is compiled again when it changes.
+    let file_name = file.to_string_lossy().into_owned();
+    self.rust_items.push(parse_quote!(const _: &str = include_str!(#file_name);));
+    (|ps: ParseStream| self.parse_blocks(ps)).parse2(tokens)?;
+    for rule in &mut self.rules[first_rule..] {
+      rule.included_from.get_or_insert_with(|| path.clone());
+    }
+    Ok(())
+  }
+
+  // A rule can have three shapes:
   //   1. rule1 = ...     (untyped)
   //   2. rule2:ty = ...  (typed)
+  //   3. rule3<p1, p2> = ...  (parametric, possibly typed)
   fn peek_rule_lhs(&mut self, ps: ParseStream) -> bool {
     if ps.peek(Ident) {
       if ps.peek2(Token![=]) {
@@ -58,6 +111,9 @@ impl FGrammar {
       else {
         let ps2 = ps.fork();
         let _: Result<Ident> = ps2.parse();
+        if Self::parse_rule_params(&ps2).is_err() {
+          return false;
+        }
         match Self::parse_type(&ps2) {
           Ok(_) => {
             ps2.peek(Token![=])
@@ -69,8 +125,32 @@ impl FGrammar {
     else { false }
   }
 
-  fn parse_rule(&mut self, ps: ParseStream) -> Result<()> {
+  /// True if the next tokens are a rule preceded by outer attributes, which must be doc comments `/// ...` or `#[start]`.
+  fn peek_documented_rule(&mut self, ps: ParseStream) -> bool {
+    let ps2 = ps.fork();
+    match ps2.call(Attribute::parse_outer) {
+      Ok(ref attrs) if !attrs.is_empty() => self.peek_rule_lhs(&ps2),
+      _ => false
+    }
+  }
+
+  fn parse_rule(&mut self, ps: ParseStream, attrs: Vec<Attribute>) -> Result<()> {
     let name: Ident = ps.parse()?;
+    let (start, doc): (Vec<_>, Vec<_>) = attrs.into_iter()
+      .partition(|attr| attr.path.is_ident("start"));
+    if let Some(attr) = doc.iter().find(|attr| !attr.path.is_ident("doc")) {
+      return Err(Error::new_spanned(attr,
+        format!("only doc comments (`/// ...`) and `#[start]` can be attached to a rule (in rule {}).", name).as_str()))
+    }
+    if let Some(attr) = start.iter().find(|attr| !attr.tokens.is_empty()) {
+      return Err(Error::new_spanned(attr,
+        format!("`#[start]` takes no argument, several start rules are declared with `#![start(r1, r2)]` (in rule {}).", name).as_str()))
+    }
+    let params = Self::parse_rule_params(ps)?;
+    if !params.is_empty() && !start.is_empty() {
+      return Err(Error::new_spanned(&start[0],
+        format!("a parametric rule cannot be the entry rule of the grammar (in rule {}).", name).as_str()))
+    }
     let (span, ty) = Self::parse_type(ps)?;
     let _: Token![=] = ps.parse()?;
     let mut body = self.parse_rule_choice(ps, name.to_string().as_str())?;
@@ -78,15 +158,46 @@ impl FGrammar {
     if ty != IType::Infer {
       body = self.alloc_expr(span, TypeAscription(body, ty))
     }
-    self.push_rule(name, body);
+    if params.is_empty() {
+      self.push_rule(name, body);
+      let rule = self.rules.last_mut().unwrap();
+      rule.doc = doc;
+      rule.entry = !start.is_empty();
+    }
+    else {
+      self.push_parametric_rule(name, params, body);
+    }
     Ok(())
   }
 
+  // The parameters `<p1, p2>` of a parametric rule, they are names of rules given when the rule is called, e.g. `r<r1, r2>`.
+  fn parse_rule_params(ps: ParseStream) -> Result<Vec<Ident>> {
+    let mut params = vec![];
+    if ps.peek(Token![<]) {
+      let _: Token![<] = ps.parse()?;
+      loop {
+        params.push(ps.parse()?);
+        if ps.peek(Token![,]) {
+          let _: Token![,] = ps.parse()?;
+        }
+        else {
+          break
+        }
+      }
+      let _: Token![>] = ps.parse()?;
+    }
+    Ok(params)
+  }
+
   fn parse_rule_choice(&mut self, ps: ParseStream, rule_name: &str) -> Result<usize> {
+    if ps.peek(Token![let]) {
+      return self.parse_let(ps, rule_name);
+    }
     let mut choices = Vec::new();
     loop {
       let spanned_expr = self.parse_spanned_expr(ps, rule_name)?;
-      choices.push(self.parse_semantic_action(ps, spanned_expr)?);
+      let guarded_expr = self.parse_guard(ps, spanned_expr)?;
+      choices.push(self.parse_semantic_action(ps, guarded_expr)?);
       if ps.peek(Token![/]) {
         let _: Token![/] = ps.parse()?;
       }
@@ -105,6 +216,27 @@ impl FGrammar {
     Ok(res)
   }
 
+  // `let name = e in body` names `e` in `body` only, the body extends as far as possible, e.g. `let ws = [" \t"]* in ws e1 ws / e2` binds `ws` in both branches.
+  fn parse_let(&mut self, ps: ParseStream, rule_name: &str) -> Result<usize> {
+    let lo = ps.span();
+    let _: Token![let] = ps.parse()?;
+    let name: Ident = ps.parse()?;
+    if !ps.peek(Token![=]) {
+      return Err(Error::new(name.span(),
+        format!("expected `=` after `let {}`, a binding has the form `let name = e in body` (in rule {}).", name, rule_name).as_str()))
+    }
+    let _: Token![=] = ps.parse()?;
+    let expr = self.parse_rule_choice(ps, rule_name)?;
+    if !ps.peek(Token![in]) {
+      return Err(Error::new(ps.span(),
+        format!("expected `in` after the expression bound to `{}`, a binding has the form `let name = e in body` (in rule {}).", name, rule_name).as_str()))
+    }
+    let _: Token![in] = ps.parse()?;
+    let body = self.parse_rule_choice(ps, rule_name)?;
+    let span = lo.join(self.span_of(body)).unwrap();
+    Ok(self.alloc_expr(span, Let(name, expr, body)))
+  }
+
   fn peek_unit_type(ps: ParseStream, invisible: bool) -> bool {
     let ps2 = ps.fork();
     let try = || {
@@ -159,19 +291,85 @@ impl FGrammar {
     }
   }
 
+  fn peek_guard(ps: ParseStream) -> bool {
+    let ps2 = ps.fork();
+    ps2.parse::<Token![&&]>().is_ok() && ps2.peek(syn::token::Brace)
+  }
+
+  // A guard `e && { condition }` where `condition` is a Rust boolean expression over the values of `e`.
+  fn parse_guard(&mut self, ps: ParseStream, expr: usize) -> Result<usize> {
+    if Self::peek_guard(ps) {
+      let _: Token![&&] = ps.parse()?;
+      let span = ps.span();
+      let sub_ps;
+      let _ = braced!(sub_ps in ps);
+      let condition: syn::Expr = sub_ps.parse()?;
+      Ok(self.alloc_expr(span, Guard(expr, condition)))
+    }
+    else {
+      Ok(expr)
+    }
+  }
+
   fn parse_semantic_action(&mut self, ps: ParseStream, expr: usize) -> Result<usize> {
     if ps.peek(Token![>]) {
       let _: Token![>] = ps.parse()?;
       let span = ps.span();
       let boxed = ps.parse::<Token![box]>().is_ok();
-      let action: syn::ExprPath = ps.parse()?;
-      Ok(self.alloc_expr(span, SemanticAction(expr, boxed, syn::Expr::Path(action))))
+      let action =
+        if ps.peek(Token![|]) || ps.peek(Token![||]) {
+          self.parse_closure_action(ps)?
+        }
+        else {
+          syn::Expr::Path(ps.parse()?)
+        };
+      Ok(self.alloc_expr(span, SemanticAction(expr, boxed, action)))
     }
     else {
       Ok(expr)
     }
   }
 
+  /// Parses the closure of `e > |x, y| body`, the body stops before the next branch `/`, comma or item of the grammar.
+  /// Hence, a division must be parenthesized in the body, e.g. `|x| (x / 2)`, and a closure with a return type has a block as body, e.g. `|x| -> u32 { x / 2 }`.
+  fn parse_closure_action(&mut self, ps: ParseStream) -> Result<syn::Expr> {
+    let mut tokens = proc_macro2::TokenStream::new();
+    if ps.peek(Token![||]) {
+      let no_params: Token![||] = ps.parse()?;
+      no_params.to_tokens(&mut tokens);
+    }
+    else {
+      let open: Token![|] = ps.parse()?;
+      open.to_tokens(&mut tokens);
+      while !ps.is_empty() && !ps.peek(Token![|]) {
+        tokens.extend(Some(ps.parse::<proc_macro2::TokenTree>()?));
+      }
+      let close: Token![|] = ps.parse()?;
+      close.to_tokens(&mut tokens);
+    }
+    if ps.peek(Token![->]) {
+      while !ps.is_empty() && !ps.peek(syn::token::Brace) {
+        tokens.extend(Some(ps.parse::<proc_macro2::TokenTree>()?));
+      }
+      tokens.extend(Some(ps.parse::<proc_macro2::TokenTree>()?));
+    }
+    else {
+      while !ps.is_empty() && !self.peek_closure_end(ps) {
+        tokens.extend(Some(ps.parse::<proc_macro2::TokenTree>()?));
+      }
+    }
+    Ok(syn::Expr::Closure(syn::parse2(tokens)?))
+  }
+
+  fn peek_closure_end(&mut self, ps: ParseStream) -> bool {
+    ps.peek(Token![/]) || ps.peek(Token![,]) || ps.peek(Token![#])
+    || ps.peek(Token![fn]) || ps.peek(Token![pub]) || ps.peek(Token![use])
+    || ps.peek(Token![type]) || ps.peek(Token![struct]) || ps.peek(Token![enum])
+    || ps.peek(Token![impl]) || ps.peek(Token![mod]) || ps.peek(Token![const])
+    || ps.peek(Token![static]) || ps.peek(Token![trait]) || ps.peek(Token![extern])
+    || self.peek_rule_lhs(ps)
+  }
+
   // An expression starting with `..` or `...` to capture the span of the current sequence.
   // A range expression `(... e)` is rewritten as `(... (e:()))` because we don't care about the value of `e`.
   fn parse_spanned_expr(&mut self, ps: ParseStream, rule_name: &str) -> Result<usize> {
@@ -195,8 +393,11 @@ impl FGrammar {
   fn parse_seq(&mut self, ps: ParseStream, rule_name: &str) -> Result<usize> {
     let lo = ps.span();
     let mut seq = Vec::new();
-    while let Some(expr) = self.parse_typed_expr(ps, rule_name)? {
-      seq.push(expr);
+    while !Self::peek_guard(ps) {
+      match self.parse_typed_expr(ps, rule_name)? {
+        Some(expr) => seq.push(expr),
+        None => break
+      }
     }
     if seq.len() == 0 {
       return Err(Error::new(lo, format!("expect at least one expression (in rule `{}`).",
@@ -210,7 +411,7 @@ impl FGrammar {
   }
 
   fn parse_typed_expr(&mut self, ps: ParseStream, rule_name: &str) -> Result<Option<usize>> {
-    let expr = self.parse_prefixed_expr(ps, rule_name)?;
+    let expr = self.parse_named_expr(ps, rule_name)?;
     match Self::parse_type(ps)? {
       (_, IType::Infer) => { Ok(expr) }
       (span, ty) => {
@@ -223,29 +424,62 @@ impl FGrammar {
     }
   }
 
-  // Parse prefixed expressions of the form `!e` and `&e`.
+  // A named expression `name@e` passes its value to the parameter `name` of a semantic action, the syntax `name:e` is already used by type ascription.
+  fn parse_named_expr(&mut self, ps: ParseStream, rule_name: &str) -> Result<Option<usize>> {
+    if ps.peek(Ident) && ps.peek2(Token![@]) {
+      let name: Ident = ps.parse()?;
+      let _: Token![@] = ps.parse()?;
+      match self.parse_prefixed_expr(ps, rule_name)? {
+        Some(expr) => {
+          let span = name.span().join(self.span_of(expr)).unwrap();
+          Ok(Some(self.alloc_expr(span, Named(expr, name))))
+        }
+        None => {
+          Err(Error::new(name.span(), format!("The named expression `{}@` is not followed by a valid expression (in rule {}).",
+            name, rule_name).as_str()))
+        }
+      }
+    }
+    else {
+      self.parse_prefixed_expr(ps, rule_name)
+    }
+  }
+
+  // Parse prefixed expressions of the form `!e`, `&e`, `$e` and `box e`.
   fn parse_prefixed_expr(&mut self, ps: ParseStream, rule_name: &str) -> Result<Option<usize>> {
     let span = ps.span();
     if ps.peek(Token![!]) {
       let _: Token![!] = ps.parse()?;
-      self.parse_prefixed_expr2(ps, span, rule_name, |e| NotPredicate(e), "A 'not' predicate (`!expr`)").map(Some)
+      let (span, expr) = self.parse_prefixed_expr2(ps, span, rule_name, "A 'not' predicate (`!expr`)")?;
+      Ok(Some(self.alloc_expr(span, NotPredicate(expr))))
     }
     else if ps.peek(Token![&]) {
       let _: Token![&] = ps.parse()?;
-      self.parse_prefixed_expr2(ps, span, rule_name, |e| AndPredicate(e), "A 'and' predicate (`&expr`)").map(Some)
+      let (span, expr) = self.parse_prefixed_expr2(ps, span, rule_name, "A 'and' predicate (`&expr`)")?;
+      Ok(Some(self.alloc_expr(span, AndPredicate(expr))))
+    }
+    else if ps.peek(Token![$]) {
+      let _: Token![$] = ps.parse()?;
+      let (span, expr) = self.parse_prefixed_expr2(ps, span, rule_name, "A capture (`$expr`)")?;
+      Ok(Some(self.alloc_capture(span, expr)))
+    }
+    // `box e` is rewritten as `e > Box::new`, its type is inferred by Rust from the semantic action using its value.
+    else if ps.peek(Token![box]) {
+      let _: Token![box] = ps.parse()?;
+      let (span, expr) = self.parse_prefixed_expr2(ps, span, rule_name, "A boxed expression (`box expr`)")?;
+      Ok(Some(self.alloc_expr(span, SemanticAction(expr, false, parse_quote!(Box::new)))))
     }
     else {
       self.parse_suffixed_expr(ps, rule_name)
     }
   }
 
-  fn parse_prefixed_expr2<F>(&mut self, ps: ParseStream, lo: Span, rule_name: &str, make_prefix: F, pred_name: &str) -> Result<usize>
-   where F: Fn(usize) -> Expression
-  {
+  /// Parses the expression following a prefix operator and returns it with the span of the whole prefixed expression.
+  fn parse_prefixed_expr2(&mut self, ps: ParseStream, lo: Span, rule_name: &str, pred_name: &str) -> Result<(Span, usize)> {
     match self.parse_suffixed_expr(ps, rule_name)? {
       Some(expr) => {
         let span = lo.join(self.span_of(expr)).unwrap();
-        Ok(self.alloc_expr(span, make_prefix(expr)))
+        Ok((span, expr))
       }
       None => {
         Err(Error::new(lo,
@@ -257,7 +491,13 @@ impl FGrammar {
     }
   }
 
-  // Parse suffixed expressions of the form `e*`, `e+` and `e?`.
+  // A capture `$e` is rewritten as `$(e:())` because we only keep the slice of the input matched by `e`.
+  fn alloc_capture(&mut self, span: Span, expr: usize) -> usize {
+    let unit_expr = self.alloc_expr(span, TypeAscription(expr, IType::Regular(Type::Unit)));
+    self.alloc_expr(span, Capture(unit_expr))
+  }
+
+  // Parse suffixed expressions of the form `e*`, `e+`, `e?`, `e{n,m}` and the separated lists `e % sep` and `e %% sep`.
   fn parse_suffixed_expr(&mut self, ps: ParseStream, rule_name: &str) -> Result<Option<usize>> {
     let lo = ps.span();
     let expr = match self.parse_rule_atom(ps, rule_name)? {
@@ -278,8 +518,79 @@ impl FGrammar {
         let _: Token![?] = ps.parse()?;
         self.alloc_expr(span, ZeroOrOne(expr))
       }
+      else if ps.peek(syn::token::Brace) {
+        let (min, max) = Self::parse_repeat_bounds(ps, rule_name)?;
+        let span = lo.join(ps.span()).unwrap();
+        self.alloc_expr(span, BoundedRepeat(expr, min, max))
+      }
+      else if ps.peek(Token![%]) {
+        let _: Token![%] = ps.parse()?;
+        let one_or_more = ps.peek(Token![%]);
+        if one_or_more {
+          let _: Token![%] = ps.parse()?;
+        }
+        match self.parse_rule_atom(ps, rule_name)? {
+          Some(sep) => {
+            let span = lo.join(self.span_of(sep)).unwrap();
+            self.alloc_expr(span, SeparatedList(expr, sep, one_or_more, false))
+          }
+          None => {
+            return Err(Error::new(span,
+              format!("A separated list (`e % sep` or `e %% sep`) is not followed by a valid separator (in rule {}). \
+                Complex separators must be parenthesized such as in `e % (\",\" spacing)`.", rule_name).as_str()))
+          }
+        }
+      }
       else { expr };
-    Ok(Some(res))
+    Ok(Some(self.parse_error_label(ps, lo, res)?))
+  }
+
+  // An expression followed by `^"label"` reports `label` when it fails.
+  fn parse_error_label(&mut self, ps: ParseStream, lo: Span, expr: usize) -> Result<usize> {
+    if ps.peek(Token![^]) {
+      let _: Token![^] = ps.parse()?;
+      let label: syn::LitStr = ps.parse()?;
+      let span = lo.join(label.span()).unwrap();
+      Ok(self.alloc_expr(span, ErrorLabel(expr, label.value())))
+    }
+    else {
+      Ok(expr)
+    }
+  }
+
+  // The bounds of a repetition have three shapes:
+  //   1. e{n}     (exactly n times)
+  //   2. e{n,}    (at least n times)
+  //   3. e{n,m}   (between n and m times)
+  fn parse_repeat_bounds(ps: ParseStream, rule_name: &str) -> Result<(usize, Option<usize>)> {
+    let span = ps.span();
+    let sub_ps;
+    let _ = braced!(sub_ps in ps);
+    let min: LitInt = sub_ps.parse()?;
+    let min = min.base10_parse::<usize>()?;
+    let max =
+      if sub_ps.peek(Token![,]) {
+        let _: Token![,] = sub_ps.parse()?;
+        if sub_ps.is_empty() { None }
+        else {
+          let max: LitInt = sub_ps.parse()?;
+          Some(max.base10_parse::<usize>()?)
+        }
+      }
+      else { Some(min) };
+    if !sub_ps.is_empty() {
+      return Err(Error::new(span,
+        format!("Unexpected token in the bounds of a repetition (in rule {}). \
+          Bounds must be of the form `e{{n}}`, `e{{n,}}` or `e{{n,m}}`.", rule_name).as_str()))
+    }
+    match max {
+      Some(max) if max < min || max == 0 => {
+        Err(Error::new(span,
+          format!("The upper bound of a repetition must be positive and greater \
+            or equal to its lower bound (in rule {}).", rule_name).as_str()))
+      }
+      _ => Ok((min, max))
+    }
   }
 
   fn peek_paren(ps: ParseStream) -> bool {
@@ -314,18 +625,68 @@ impl FGrammar {
     res.is_ok()
   }
 
+  /// True if the next tokens are of the form `name(...)`.
+  fn peek_call(ps: ParseStream, name: &str) -> bool {
+    let ps2 = ps.fork();
+    match ps2.parse::<Ident>() {
+      Ok(ref ident) if ident == name => Self::peek_paren(&ps2),
+      _ => false
+    }
+  }
+
+  /// Parses `name(e)` and returns the span of the whole expression and `e`.
+  fn parse_call(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<(Span, usize)> {
+    let name: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    if sub_ps.is_empty() {
+      return Err(Error::new(span,
+        format!("`{}(e)` must be given an expression `e` (in rule {}).", name, rule_name).as_str()))
+    }
+    let expr = self.parse_rule_choice(&sub_ps, rule_name)?;
+    Ok((span, expr))
+  }
+
   fn parse_rule_atom(&mut self, ps: ParseStream, rule_name: &str) -> Result<Option<usize>> {
     let span = ps.span();
     let res =
-      // String literal "let", "fn", ...
+      // String literal "let", "fn", ... or case-insensitive string literal "select"i, ...
       if ps.peek(LitStr) {
         let lit_str: LitStr = ps.parse()?;
-        Some(self.alloc_expr(span, StrLiteral(lit_str.value())))
+        let case_insensitive = match lit_str.suffix() {
+          "" => false,
+          "i" => true,
+          suffix => {
+            return Err(Error::new(span,
+              format!("Unknown suffix `{}` on a string literal (in rule {}). \
+                The only suffix allowed is `i` for case-insensitive literals such as in `\"select\"i`.",
+                suffix, rule_name).as_str()))
+          }
+        };
+        Some(self.alloc_expr(span, StrLiteral(lit_str.value(), case_insensitive)))
+      }
+      // `..` must be checked before `.` since `ps.peek(Token![.])` also succeeds on `..` and `...`.
+      else if ps.peek(Token![..]) {
+        return Err(Error::new(span,
+          format!("A span expression `.. e1 e2` must always start a sequence (in rule {}). \
+            You can force this by grouping the spanned expression with parenthesis: `e1 (.. e2)` instead of `e1 .. e2`.",
+            rule_name).as_str()));
       }
-      // Any character `.`
+      // Any character `.`, or `.until(e)`
       else if ps.peek(Token![.]) {
         let _: Token![.] = ps.parse()?;
-        Some(self.alloc_expr(span, AnySingleChar))
+        if Self::peek_until(ps) {
+          Some(self.parse_until(ps, span, rule_name)?)
+        }
+        else {
+          Some(self.alloc_expr(span, AnySingleChar))
+        }
+      }
+      // Cut `~`
+      else if ps.peek(Token![~]) {
+        let _: Token![~] = ps.parse()?;
+        Some(self.alloc_expr(span, Cut))
       }
       // Parenthesized expression `(r1 / r2)`
       else if Self::peek_paren(ps) {
@@ -338,6 +699,83 @@ impl FGrammar {
         }
         Some(self.parse_rule_choice(&sub_ps, rule_name)?)
       }
+      // Strict expression `strict(e)`, no spacing is inserted in the sequences of `e`
+      else if Self::peek_call(ps, "strict") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_expr(span, Strict(expr)))
+      }
+      // `committed(e)`, same as `e?` but it fails if `e` fails after consuming input.
+      else if Self::peek_call(ps, "committed") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_expr(span, CommittedOptional(expr)))
+      }
+      // `peek(e)`, same as `&e`
+      else if Self::peek_call(ps, "peek") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_expr(span, AndPredicate(expr)))
+      }
+      // `spanned(e)`, the value of `e` with the range of bytes it matched.
+      else if Self::peek_call(ps, "spanned") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_expr(span, ByteSpanned(expr)))
+      }
+      // `skip(e)`, same as `e:()`
+      else if Self::peek_call(ps, "skip") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_expr(span, TypeAscription(expr, IType::Regular(Type::Unit))))
+      }
+      // `longest(e1 / e2)`, the branch consuming the most input is taken.
+      else if Self::peek_call(ps, "longest") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        // The choice is replaced in place, a new expression would leave it unreachable from the rules and never typed.
+        if let Choice(children) = self.exprs[expr].clone() {
+          self.exprs[expr] = LongestChoice(children);
+        }
+        else {
+          return Err(Error::new(span,
+            format!("`longest` expects a choice `e1 / e2 / ...` (in rule {}).", rule_name).as_str()))
+        }
+        Some(expr)
+      }
+      // `repeat_until_eoi(e)`, `e` is repeated until the end of input.
+      else if Self::peek_call(ps, "repeat_until_eoi") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_expr(span, RepeatUntilEoi(expr)))
+      }
+      // `count(e)`, the number of repetitions of `e*`.
+      else if Self::peek_call(ps, "count") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_expr(span, Count(expr)))
+      }
+      // `recover(e, sync)`, the input is skipped until `sync` if `e` fails.
+      else if Self::peek_call(ps, "recover") {
+        Some(self.parse_recover(ps, span, rule_name)?)
+      }
+      // `token(p)`, the next atom matches the Rust pattern `p`.
+      else if Self::peek_call(ps, "token") {
+        Some(self.parse_token_pattern(ps, span, rule_name)?)
+      }
+      // `capture(e)`, same as `$e`
+      else if Self::peek_call(ps, "capture") {
+        let (span, expr) = self.parse_call(ps, span, rule_name)?;
+        Some(self.alloc_capture(span, expr))
+      }
+      // `separated_nonempty(e, sep)`, same as `e %% sep` but a separator must be followed by `e`.
+      else if Self::peek_call(ps, "separated_nonempty") {
+        Some(self.parse_separated_nonempty(ps, span, rule_name)?)
+      }
+      // `keyword("if")`, the literal not followed by an identifier character.
+      else if Self::peek_call(ps, "keyword") {
+        Some(self.parse_keyword(ps, span, rule_name)?)
+      }
+      // `one_of("+-*/")`, the character class of the characters of the literal.
+      else if Self::peek_call(ps, "one_of") {
+        Some(self.parse_one_of(ps, span, rule_name)?)
+      }
+      // `infix(atom, f, left(op1), right(op2))`, the binary operators of each level applied with `f` by precedence climbing.
+      else if Self::peek_call(ps, "infix") {
+        Some(self.parse_infix(ps, span, rule_name)?)
+      }
       // Rule call `r1`
       else if Self::peek_path(ps) {
         if self.peek_rule_lhs(ps) { None }
@@ -346,42 +784,363 @@ impl FGrammar {
           Some(self.alloc_expr(span, ExternalNonTerminalSymbol(name)))
         }
       }
-      // Character class `["0-9"]`
+      // Character class `["0-9"]` or negated character class `[^"0-9"]`
       else if Self::peek_bracket(ps) {
         let sub_ps;
         let _ = bracketed!(sub_ps in ps);
         Some(self.parse_char_class(&sub_ps, span, rule_name)?)
       }
-      else if ps.peek(Token![..]) {
-        return Err(Error::new(span,
-          format!("A span expression `.. e1 e2` must always start a sequence (in rule {}). \
-            You can force this by grouping the spanned expression with parenthesis: `e1 (.. e2)` instead of `e1 .. e2`.",
-            rule_name).as_str()));
-      }
       else {
         None
       };
     Ok(res)
   }
 
+  fn peek_until(ps: ParseStream) -> bool {
+    Self::peek_call(ps, "until")
+  }
+
+  // `.until(e)` consumes any character as long as `e` does not match, and is rewritten into `(!e .)*`.
+  fn parse_until(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<usize> {
+    let _: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    if sub_ps.is_empty() {
+      return Err(Error::new(span,
+        format!("`.until(e)` must be given the expression `e` at which it stops (in rule {}).", rule_name).as_str()))
+    }
+    let until = self.parse_rule_choice(&sub_ps, rule_name)?;
+    let not_until = self.alloc_expr(span, NotPredicate(until));
+    let any_char = self.alloc_expr(span, AnySingleChar);
+    let step = self.alloc_expr(span, Sequence(vec![not_until, any_char]));
+    Ok(self.alloc_expr(span, ZeroOrMore(step)))
+  }
+
+  /// Parses `recover(e, sync)` where `e` and `sync` are expressions.
+  fn parse_recover(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<usize> {
+    let _: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    let expected = || Error::new(span,
+      format!("`recover(e, sync)` must be given the expression `e` and the expression `sync` at which the parsing continues when `e` fails (in rule {}).", rule_name).as_str());
+    if sub_ps.is_empty() {
+      return Err(expected());
+    }
+    let expr = self.parse_rule_choice(&sub_ps, rule_name)?;
+    if !sub_ps.peek(Token![,]) {
+      return Err(expected());
+    }
+    let _: Token![,] = sub_ps.parse()?;
+    let sync = self.parse_rule_choice(&sub_ps, rule_name)?;
+    Ok(self.alloc_expr(span, Recover(expr, sync)))
+  }
+
+  /// Parses `one_of("chars")` into the character class of the characters of `chars`, where `-` is not an interval.
+  fn parse_one_of(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<usize> {
+    let _: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    let expected = || Error::new(span,
+      format!("`one_of(\"chars\")` must be given a non-empty string literal of the characters it matches (in rule {}).", rule_name).as_str());
+    let chars: LitStr = sub_ps.parse().map_err(|_| expected())?;
+    if !sub_ps.is_empty() || chars.value().is_empty() {
+      return Err(expected());
+    }
+    let mut intervals: Vec<CharacterInterval> = vec![];
+    for c in chars.value().chars() {
+      if !intervals.iter().any(|interval| interval.lo == c) {
+        intervals.push(CharacterInterval::new(c, c));
+      }
+    }
+    Ok(self.alloc_expr(span, CharacterClass(CharacterClassExpr::new(intervals, vec![], false))))
+  }
+
+  /// Parses `keyword("lit")` and rewrites it into `strict("lit" !ident_char)`, where `ident_char` is the rule given by `#![keyword_boundary(r)]` if any.
+  /// `strict` prevents the spacing rule from being called between the literal and its boundary.
+  fn parse_keyword(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<usize> {
+    let _: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    let expected = || Error::new(span,
+      format!("`keyword(\"lit\")` must be given a non-empty string literal (in rule {}).", rule_name).as_str());
+    let lit: LitStr = sub_ps.parse().map_err(|_| expected())?;
+    if !sub_ps.is_empty() || lit.value().is_empty() {
+      return Err(expected());
+    }
+    let boundary = self.keyword_boundary().unwrap_or_else(|| Ident::new("ident_char", span));
+    let lit = self.alloc_expr(span, StrLiteral(lit.value(), false));
+    let boundary = self.alloc_expr(span, ExternalNonTerminalSymbol(boundary.into()));
+    let not_boundary = self.alloc_expr(span, NotPredicate(boundary));
+    let seq = self.alloc_expr(span, Sequence(vec![lit, not_boundary]));
+    Ok(self.alloc_expr(span, Strict(seq)))
+  }
+
+  /// The rule `r` of `#![keyword_boundary(r)]`, it is checked in `middle::analysis::attribute`.
+  fn keyword_boundary(&self) -> Option<Ident> {
+    self.attributes.iter()
+      .filter(|attr| attr.path.is_ident("keyword_boundary"))
+      .find_map(|attr| match attr.parse_meta() {
+        Ok(syn::Meta::List(ref list)) if list.nested.len() == 1 => match list.nested[0] {
+          syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => path.get_ident().cloned(),
+          _ => None
+        },
+        _ => None
+      })
+  }
+
+  /// Parses `infix(atom, f, level1, ..., levelN)` where each level is `left(op)` or `right(op)`, from the lowest to the highest precedence.
+  /// It is rewritten into `let infix_operand = atom in let infix_level_N = infix_operand (opN infix_operand)* > fold_N in ... infix_level_2 (op1 infix_level_2)* > fold_1`.
+  /// The function `fold_i` applies `f(lhs, op, rhs)` from the left or from the right of the level, it is generated by `push_infix_folds`.
+  fn parse_infix(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<usize> {
+    let _: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    let expected = || Error::new(span,
+      format!("`infix(atom, f, left(op), right(op), ...)` must be given the operand `atom`, the function `f(lhs, op, rhs)` building a binary expression, \
+        and at least one level of operators, from the lowest to the highest precedence (in rule {}).", rule_name).as_str());
+    if sub_ps.is_empty() {
+      return Err(expected());
+    }
+    let atom = self.parse_rule_choice(&sub_ps, rule_name)?;
+    let _: Token![,] = sub_ps.parse().map_err(|_| expected())?;
+    let binary: Ident = sub_ps.parse().map_err(|_| expected())?;
+    let mut levels = vec![];
+    while sub_ps.peek(Token![,]) {
+      let _: Token![,] = sub_ps.parse()?;
+      let right_assoc =
+        if Self::peek_call(&sub_ps, "left") { false }
+        else if Self::peek_call(&sub_ps, "right") { true }
+        else { return Err(expected()) };
+      let (_, op) = self.parse_call(&sub_ps, sub_ps.span(), rule_name)?;
+      levels.push((op, right_assoc));
+    }
+    if levels.is_empty() || !sub_ps.is_empty() {
+      return Err(expected());
+    }
+    let mut operand = Ident::new("infix_operand", span);
+    let mut bindings = vec![(operand.clone(), atom)];
+    let mut body = None;
+    for (i, (op, right_assoc)) in levels.into_iter().enumerate().rev() {
+      let fold = self.infix_fold(&binary, right_assoc);
+      let call_operand = self.alloc_expr(span, ExternalNonTerminalSymbol(operand.clone().into()));
+      let next_operand = self.alloc_expr(span, ExternalNonTerminalSymbol(operand.clone().into()));
+      let step = self.alloc_expr(span, Sequence(vec![op, next_operand]));
+      let steps = self.alloc_expr(span, ZeroOrMore(step));
+      let level = self.alloc_expr(span, Sequence(vec![call_operand, steps]));
+      let level = self.alloc_expr(span, SemanticAction(level, false, parse_quote!(#fold)));
+      if i == 0 {
+        body = Some(level);
+      }
+      else {
+        operand = Ident::new(&format!("infix_level_{}", i + 1), span);
+        bindings.push((operand.clone(), level));
+      }
+    }
+    let body = body.unwrap();
+    Ok(bindings.into_iter().rev()
+      .fold(body, |body, (name, expr)| self.alloc_expr(span, Let(name, expr, body))))
+  }
+
+  /// The name of the function folding a level of `infix(atom, binary, ...)`, it is registered to be generated by `push_infix_folds`.
+  fn infix_fold(&mut self, binary: &Ident, right_assoc: bool) -> Ident {
+    let assoc = if right_assoc { "right" } else { "left" };
+    let name = Ident::new(&format!("infix_{}_{}", assoc, binary), binary.span());
+    if !self.infix_folds.iter().any(|fold| fold.name == name) {
+      self.infix_folds.push(InfixFold { name: name.clone(), binary: binary.clone(), right_assoc });
+    }
+    name
+  }
+
+  /// Generates the functions folding the levels of `infix(atom, binary, ...)`, of type `fn(first: T, rest: Vec<(O, T)>) -> R` for `fn binary(lhs: T, op: O, rhs: T) -> R`.
+  /// If `binary` returns `Result<T, E>`, a fold stops at the first error, which fails the level as any fallible action.
+  fn push_infix_folds(&mut self) -> Result<()> {
+    let folds = std::mem::take(&mut self.infix_folds);
+    for fold in folds {
+      let binary = &fold.binary;
+      let fun = self.rust_items.iter()
+        .find_map(|item| match item {
+          syn::Item::Fn(fun) if fun.sig.ident == *binary => Some(fun.clone()),
+          _ => None
+        })
+        .ok_or_else(|| Error::new(binary.span(),
+          format!("`{}` must be a function of the grammar, it builds the binary expressions of `infix(atom, {}, ...)`.", binary, binary).as_str()))?;
+      let param_types: Vec<syn::Type> = fun.sig.inputs.iter()
+        .filter_map(|param| match param {
+          syn::FnArg::Typed(pat_ty) => Some((*pat_ty.ty).clone()),
+          _ => None
+        })
+        .collect();
+      let ret = match fun.sig.output {
+        syn::ReturnType::Type(_, ref ty) if param_types.len() == 3 => (**ty).clone(),
+        _ => return Err(Error::new(fun.sig.ident.span(),
+          format!("`{}` builds the binary expressions of `infix`, it must have the signature `fn {}(lhs: T, op: O, rhs: T) -> T`.", binary, binary).as_str()))
+      };
+      let (operand_ty, op_ty) = (&param_types[0], &param_types[1]);
+      let fallible = result_ok_type(&ret).is_some();
+      let try_op: Option<Token![?]> = if fallible { Some(Default::default()) } else { None };
+      let ok = |value: syn::Expr| -> syn::Expr {
+        if fallible { parse_quote!(Ok(#value)) }
+        else { value }
+      };
+      let body: syn::Block =
+        if fold.right_assoc {
+          let first = ok(parse_quote!(first));
+          parse_quote!({
+            let mut rest = rest.into_iter().rev();
+            match rest.next() {
+              None => #first,
+              Some((mut op, mut rhs)) => {
+                for (prev_op, lhs) in rest {
+                  rhs = #binary(lhs, op, rhs)#try_op;
+                  op = prev_op;
+                }
+                #binary(first, op, rhs)
+              }
+            }
+          })
+        }
+        else {
+          let lhs = ok(parse_quote!(lhs));
+          parse_quote!({
+            let mut lhs = first;
+            for (op, rhs) in rest {
+              lhs = #binary(lhs, op, rhs)#try_op;
+            }
+            #lhs
+          })
+        };
+      let name = &fold.name;
+      let generics = &fun.sig.generics;
+      let where_clause = &fun.sig.generics.where_clause;
+      self.push_rust_item(parse_quote!(
+        fn #name #generics (first: #operand_ty, rest: Vec<(#op_ty, #operand_ty)>) -> #ret #where_clause
+          #body
+      ));
+    }
+    Ok(())
+  }
+
+  /// Parses `separated_nonempty(e, sep)` where `e` and `sep` are expressions.
+  fn parse_separated_nonempty(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<usize> {
+    let _: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    let expected = || Error::new(span,
+      format!("`separated_nonempty(e, sep)` must be given the element `e` and the separator `sep` (in rule {}).", rule_name).as_str());
+    if sub_ps.is_empty() {
+      return Err(expected());
+    }
+    let expr = self.parse_rule_choice(&sub_ps, rule_name)?;
+    if !sub_ps.peek(Token![,]) {
+      return Err(expected());
+    }
+    let _: Token![,] = sub_ps.parse()?;
+    let sep = self.parse_rule_choice(&sub_ps, rule_name)?;
+    Ok(self.alloc_expr(span, SeparatedList(expr, sep, true, true)))
+  }
+
+  /// Parses `token(p)` where `p` is a Rust pattern, possibly with alternatives such as `Token::Plus | Token::Minus`.
+  fn parse_token_pattern(&mut self, ps: ParseStream, lo: Span, rule_name: &str) -> Result<usize> {
+    let _: Ident = ps.parse()?;
+    let span = lo.join(ps.span()).unwrap();
+    let sub_ps;
+    let _ = parenthesized!(sub_ps in ps);
+    if sub_ps.is_empty() {
+      return Err(Error::new(span,
+        format!("`token(p)` must be given the pattern `p` matching the next atom (in rule {}).", rule_name).as_str()))
+    }
+    let mut cases: Punctuated<syn::Pat, Token![|]> = Punctuated::parse_separated_nonempty(&sub_ps)?;
+    let pattern =
+      if cases.len() == 1 { cases.pop().unwrap().into_value() }
+      else {
+        syn::Pat::Or(syn::PatOr { attrs: vec![], leading_vert: None, cases })
+      };
+    if !sub_ps.is_empty() {
+      return Err(sub_ps.error(format!("expected a pattern (in rule {}).", rule_name).as_str()))
+    }
+    Ok(self.alloc_expr(span, TokenPattern(pattern)))
+  }
+
+  // The negation of the empty set `[^]` (or `[^""]`) accepts any character and is therefore rewritten into `.`.
   fn parse_char_class(&mut self, ps: ParseStream, span: Span, rule_name: &str) -> Result<usize> {
+    let negated = ps.peek(Token![^]);
+    if negated {
+      let _: Token![^] = ps.parse()?;
+      if ps.is_empty() {
+        return Ok(self.alloc_expr(span, AnySingleChar));
+      }
+    }
     if ps.peek(LitStr) {
       let lit_str: LitStr = ps.parse()?;
       if lit_str.value().is_empty() {
+        if negated {
+          return Ok(self.alloc_expr(span, AnySingleChar));
+        }
         return Err(Error::new(span,
           "Empty character classes are forbidden. For empty expression \
           you can use the empty string literal `\"\"`."))
       }
-      self.parse_set_of_char_range(span, lit_str.value(), rule_name)
+      self.parse_set_of_char_range(span, lit_str.value(), negated, rule_name)
+    }
+    else if ps.peek(LitInt) {
+      self.parse_byte_class(ps, span, negated, rule_name)
     }
     else {
       Err(Error::new(span,
         format!("Unexpected character in this character class (in rule {}). \
-            `[` must only be followed by a string literal (such as in `[\"a-z\"]`).", rule_name).as_str()))
+            `[` must only be followed by a string literal (such as in `[\"a-z\"]`) or by bytes (such as in `[0x00-0x1F, 0x7F]`), \
+            optionally preceded by `^` to negate the class (such as in `[^\"a-z\"]`).", rule_name).as_str()))
+    }
+  }
+
+  /// A class of bytes `[0x00-0x1F, 0x7F]` is a list of bytes or intervals of bytes separated by commas, written as integer literals (`0x1F`, `0b0001_1111` or `31`).
+  fn parse_byte_class(&mut self, ps: ParseStream, span: Span, negated: bool, rule_name: &str) -> Result<usize> {
+    let mut intervals = vec![];
+    while !ps.is_empty() {
+      let lo = Self::parse_byte(ps, rule_name)?;
+      let hi =
+        if ps.peek(Token![-]) {
+          let _: Token![-] = ps.parse()?;
+          Self::parse_byte(ps, rule_name)?
+        }
+        else { lo };
+      if lo > hi {
+        return Err(Error::new(span,
+          format!("The interval `0x{:02X}-0x{:02X}` of this class of bytes is empty since its lower bound is greater than its upper bound (in rule {}).",
+            lo, hi, rule_name).as_str()))
+      }
+      intervals.push(CharacterInterval::new(lo as char, hi as char));
+      if !ps.is_empty() {
+        let _: Token![,] = ps.parse()?;
+      }
+    }
+    let mut class = CharacterClassExpr::new(intervals, vec![], negated);
+    class.bytes = true;
+    Ok(self.alloc_expr(span, CharacterClass(class)))
+  }
+
+  fn parse_byte(ps: ParseStream, rule_name: &str) -> Result<u8> {
+    let byte: LitInt = ps.parse()?;
+    match byte.suffix() {
+      "" | "u8" => (),
+      _ => return Err(Error::new(byte.span(),
+        format!("A byte of a class must be an integer literal without suffix, such as `0x1F` (in rule {}).", rule_name).as_str()))
     }
+    byte.base10_parse::<u8>().map_err(|_| Error::new(byte.span(),
+      format!("A byte of a class must be between `0x00` and `0xFF` (in rule {}).", rule_name).as_str()))
   }
 
-  fn parse_set_of_char_range(&mut self, span: Span, ranges: String, rule_name: &str) -> Result<usize> {
+  /// The content of a character class is a sequence of single characters and intervals `lo-hi`, such as `a-z0-9_`.
+  /// The separator `-` is a character when it starts or ends the class (`-+*/` or `+*/-`), otherwise it separates the bounds of an interval.
+  /// The bounds are only checked during the well-formedness analysis, so `+-*` is rejected there as the empty interval `+-*`.
+  fn parse_set_of_char_range(&mut self, span: Span, ranges: String, negated: bool, rule_name: &str) -> Result<usize> {
+    let (ranges, categories) = Self::parse_unicode_categories(span, ranges, rule_name)?;
     let mut ranges = ranges.chars().peekable();
     let mut intervals = vec![];
     match ranges.peek() {
@@ -398,7 +1157,39 @@ impl FGrammar {
           break;
       }
     }
-    Ok(self.alloc_expr(span, CharacterClass(CharacterClassExpr::new(intervals))))
+    Ok(self.alloc_expr(span, CharacterClass(CharacterClassExpr::new(intervals, categories, negated))))
+  }
+
+  // Extracts the Unicode general categories `\p{L}` and `\P{L}` from the class, and returns the remaining characters.
+  fn parse_unicode_categories(span: Span, ranges: String, rule_name: &str) -> Result<(String, Vec<(UnicodeCategory, bool)>)> {
+    let mut remaining = String::new();
+    let mut categories = vec![];
+    let mut rest = ranges.as_str();
+    while let Some(start) = Self::find_unicode_category(rest) {
+      remaining.push_str(&rest[..start]);
+      let negated = rest[start..].starts_with("\\P");
+      let after = &rest[start+3..];
+      let end = match after.find('}') {
+        Some(end) => end,
+        None => return Err(Error::new(span,
+          format!("Unclosed Unicode category in this character class, it must be of the form `\\\\p{{L}}` (in rule {}).", rule_name).as_str()))
+      };
+      match UnicodeCategory::from_abbreviation(&after[..end]) {
+        Some(category) => categories.push((category, negated)),
+        None => return Err(Error::new(span,
+          format!("Unknown Unicode category `{}` (in rule {}). The supported categories are {}.",
+            &after[..end], rule_name, UnicodeCategory::abbreviations().join(", ")).as_str()))
+      }
+      rest = &after[end+1..];
+    }
+    remaining.push_str(rest);
+    Ok((remaining, categories))
+  }
+
+  fn find_unicode_category(ranges: &str) -> Option<usize> {
+    ranges.match_indices('\\')
+      .map(|(i, _)| i)
+      .find(|&i| ranges[i+1..].starts_with("p{") || ranges[i+1..].starts_with("P{"))
   }
 
   fn parse_char_range<'b>(&mut self, span: Span, ranges: &mut Peekable<Chars<'b>>, rule_name: &str) -> Result<Vec<CharacterInterval>> {
@@ -440,3 +1231,20 @@ impl FGrammar {
     Ok(res)
   }
 }
+
+/// Gives the span `span` to every token of `tokens`.
+fn respan(tokens: proc_macro2::TokenStream, span: Span) -> proc_macro2::TokenStream {
+  tokens.into_iter()
+    .map(|mut token| {
+      if let proc_macro2::TokenTree::Group(ref group) = token {
+        let mut respanned = proc_macro2::Group::new(group.delimiter(), respan(group.stream(), span));
+        respanned.set_span(span);
+        token = proc_macro2::TokenTree::Group(respanned);
+      }
+      else {
+        token.set_span(span);
+      }
+      token
+    })
+    .collect()
+}
@@ -21,6 +21,8 @@ extern crate syn;
 extern crate quote;
 extern crate proc_macro;
 extern crate proc_macro2;
+#[cfg(any(feature = "dump_ast", feature = "diagnostics_json"))]
+extern crate serde_json;
 
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
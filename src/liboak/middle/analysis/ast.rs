@@ -17,6 +17,7 @@ pub use visitor::*;
 pub use front::ast::FExpressionInfo;
 
 use std::default::Default;
+use std::cmp::max;
 
 pub type AGrammar = Grammar<FExpressionInfo>;
 
@@ -29,14 +30,189 @@ impl AGrammar
 
 pub struct GrammarAttributes
 {
-  pub print_typing: PrintLevel
+  pub print_typing: PrintLevel,
+  pub packrat: bool,
+  /// Rules memoized without `#![packrat]`, given with `#![memoize(r1, r2)]`.
+  pub memoized_rules: Vec<Ident>,
+  pub left_recursion: bool,
+  /// Level of the diagnostic on left-recursive cycles, given with `#![warn(left_recursion)]` or `#![deny(left_recursion)]`.
+  pub left_recursion_level: Option<LintLevel>,
+  /// The rules only matching a literal or a character class and called a few times are reported with `#![warn(trivial_rules)]` or `#![deny(trivial_rules)]`.
+  pub trivial_rules_level: Option<LintLevel>,
+  /// The functions of the rules are marked `#[inline(always)]`.
+  pub inline_rules: bool,
+  /// The generated code only uses `core` and `alloc`.
+  pub no_std: bool,
+  /// The functions of the rules trace their calls with `oak_runtime::trace`.
+  pub trace: bool,
+  /// The functions of the rules build the concrete syntax tree of `oak_runtime::cst`, and `cst_parse_r` is generated for each start rule `r`.
+  pub cst: bool,
+  /// Rules from which the grammar is used, the first rule if empty.
+  pub start_rules: Vec<Ident>,
+  /// Rule matching the characters that cannot follow a keyword, given with `#![keyword_boundary(r)]`.
+  pub keyword_boundary: Option<Ident>,
+  /// Rule declared with `#[start]`, the function `parse` is generated for it.
+  pub entry_rule: Option<Ident>,
+  /// Rules for which `FromStr` is implemented on their types.
+  pub from_str_rules: Vec<Ident>,
+  /// Rule implicitly called between the elements of a sequence, given with `#![spacing(r)]`.
+  pub spacing_rule: Option<Ident>,
+  /// File in which the typed grammar is written in JSON, given with `#![dump_ast = "path"]`.
+  pub dump_ast: Option<String>,
+  /// A type alias of the type of each rule is generated.
+  pub type_aliases: bool,
+  /// Module in which the generated items are declared, given with `#![module(name)]`.
+  pub module: Option<Ident>,
+  /// A `Visitor` trait of the types built by the grammar is generated with `#![visitor]`.
+  pub visitor: bool,
+  /// The well-formedness attributes of each rule are reported with `#![explain_wfa]`.
+  pub explain_wfa: bool,
+  /// Maximal number of nested rule calls, given with `#![depth_limit]` or `#![depth_limit = n]`.
+  pub depth_limit: Option<usize>
 }
 
+/// `Warn` accepts the left-recursive cycles with a warning, and `Deny` rejects them even with `#![left_recursion]`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LintLevel
+{
+  Warn,
+  Deny
+}
+
+/// Depth limit of `#![depth_limit]` when no limit is given.
+pub const DEFAULT_DEPTH_LIMIT: usize = 1000;
+
 impl Default for GrammarAttributes {
   fn default() -> Self {
     GrammarAttributes {
-      print_typing: PrintLevel::default()
+      print_typing: PrintLevel::default(),
+      packrat: false,
+      memoized_rules: vec![],
+      left_recursion: false,
+      left_recursion_level: None,
+      trivial_rules_level: None,
+      inline_rules: false,
+      no_std: false,
+      trace: false,
+      cst: false,
+      start_rules: vec![],
+      entry_rule: None,
+      keyword_boundary: None,
+      from_str_rules: vec![],
+      spacing_rule: None,
+      dump_ast: None,
+      type_aliases: false,
+      module: None,
+      visitor: false,
+      explain_wfa: false,
+      depth_limit: None
+    }
+  }
+}
+
+/// The set of characters that can start an expression.
+/// It is an over-approximation: the expression fails on every input starting with a character that is not in the set, unless it is nullable.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct FirstSet
+{
+  /// Sorted and disjoint intervals of characters.
+  pub intervals: Vec<(char, char)>,
+  /// True if the expression can succeed without consuming any character.
+  pub nullable: bool
+}
+
+impl FirstSet
+{
+  pub fn new(intervals: Vec<(char, char)>, nullable: bool) -> FirstSet {
+    let mut first = FirstSet { intervals: vec![], nullable };
+    first.union_intervals(intervals);
+    first
+  }
+
+  pub fn nullable() -> FirstSet {
+    FirstSet::new(vec![], true)
+  }
+
+  pub fn any_char() -> FirstSet {
+    FirstSet::new(vec![('\u{0}', ::std::char::MAX)], false)
+  }
+
+  /// The characters that are not in `intervals`.
+  pub fn complement(intervals: Vec<(char, char)>) -> FirstSet {
+    let set = FirstSet::new(intervals, false);
+    let mut complement = vec![];
+    let mut lo = Some('\u{0}');
+    for (l, h) in set.intervals {
+      if let Some(lo) = lo {
+        if let Some(hi) = prev_char(l) {
+          if lo <= hi {
+            complement.push((lo, hi));
+          }
+        }
+      }
+      lo = next_char(h);
+    }
+    if let Some(lo) = lo {
+      complement.push((lo, ::std::char::MAX));
     }
+    FirstSet::new(complement, false)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.intervals.is_empty()
+  }
+
+  pub fn union(&mut self, other: &FirstSet) {
+    self.union_intervals(other.intervals.clone());
+    self.nullable |= other.nullable;
+  }
+
+  /// Union of the characters only, the nullable flag is unchanged.
+  pub fn union_chars(&mut self, other: &FirstSet) {
+    self.union_intervals(other.intervals.clone());
+  }
+
+  fn union_intervals(&mut self, intervals: Vec<(char, char)>) {
+    let mut all = self.intervals.clone();
+    all.extend(intervals);
+    all.sort();
+    let mut merged: Vec<(char, char)> = vec![];
+    for (lo, hi) in all {
+      if let Some(last) = merged.last_mut() {
+        if next_char(last.1).is_none_or(|n| lo <= n) {
+          last.1 = max(last.1, hi);
+          continue;
+        }
+      }
+      merged.push((lo, hi));
+    }
+    self.intervals = merged;
+  }
+
+  /// True if all the characters of `other` are in `self`.
+  pub fn contains(&self, other: &FirstSet) -> bool {
+    other.intervals.iter().all(|&(lo, hi)|
+      self.intervals.iter().any(|&(l, h)| l <= lo && hi <= h))
+  }
+
+  pub fn is_disjoint(&self, other: &FirstSet) -> bool {
+    self.intervals.iter().all(|&(l, h)|
+      other.intervals.iter().all(|&(lo, hi)| h < lo || hi < l))
+  }
+}
+
+fn next_char(c: char) -> Option<char> {
+  match c {
+    '\u{D7FF}' => Some('\u{E000}'),
+    c => ::std::char::from_u32(c as u32 + 1)
+  }
+}
+
+fn prev_char(c: char) -> Option<char> {
+  match c {
+    '\u{0}' => None,
+    '\u{E000}' => Some('\u{D7FF}'),
+    c => ::std::char::from_u32(c as u32 - 1)
   }
 }
 
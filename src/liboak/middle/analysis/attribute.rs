@@ -13,12 +13,59 @@
 // limitations under the License.
 
 use middle::analysis::ast::*;
+use middle::analysis::duplicate::suggest_closest_rule;
 
 pub fn decorate_with_attributes(mut grammar: AGrammar,
   attributes: Vec<syn::Attribute>) -> Partial<AGrammar>
 {
-  merge_grammar_attributes(&mut grammar, attributes);
-  Partial::Value(grammar)
+  if merge_grammar_attributes(&mut grammar, attributes) && merge_entry_rule(&mut grammar) {
+    Partial::Value(grammar)
+  }
+  else {
+    Partial::Nothing
+  }
+}
+
+/// The rule declared with `#[start]` is a start rule, and the functions `parse` and `parse_all` are generated for it.
+/// Returns `false` if several rules are declared with `#[start]` or if `parse` or `parse_all` is already a function of the grammar, including the parser of a rule `all`.
+fn merge_entry_rule(grammar: &mut AGrammar) -> bool {
+  let entries: Vec<_> = grammar.rules.iter()
+    .filter(|r| r.entry)
+    .cloned()
+    .collect();
+  if entries.len() > 1 {
+    entries[1].span().unstable().error(
+      "the entry rule is declared more than once with `#[start]`.")
+    .span_note(entries[0].span().unstable(),
+      "Previous entry rule")
+    .emit();
+    return false;
+  }
+  if let Some(entry) = entries.first() {
+    if let Some(parse_fn) = grammar.rust_functions.keys().find(|f| *f == "parse" || *f == "parse_all") {
+      parse_fn.span().unstable().error(format!(
+        "the function `{}` is generated for the entry rule `{}` declared with `#[start]`.", parse_fn, entry.ident()))
+      .span_note(entry.span().unstable(),
+        "Entry rule")
+      .emit();
+      return false;
+    }
+    // The parser of a rule `all` is also named `parse_all`.
+    if let Some(all) = grammar.rules.iter().find(|r| r.ident() == "all") {
+      all.span().unstable().error(format!(
+        "the parser `parse_all` of this rule conflicts with the function `parse_all` generated for the entry rule `{}` declared with `#[start]`.", entry.ident()))
+      .span_note(entry.span().unstable(),
+        "Entry rule")
+      .emit();
+      return false;
+    }
+    let id = entry.ident();
+    if !grammar.attributes.start_rules.contains(&id) {
+      grammar.attributes.start_rules.insert(0, id.clone());
+    }
+    grammar.attributes.entry_rule = Some(id);
+  }
+  true
 }
 
 fn warn_ignore_attr(span: Span) {
@@ -27,15 +74,257 @@ fn warn_ignore_attr(span: Span) {
     .emit();
 }
 
-fn merge_grammar_attributes(grammar: &mut AGrammar, attrs: Vec<syn::Attribute>) {
-  for attr in attrs {
-    if let Some(ident) = attr.path.get_ident() {
-      merge_grammar_attr(grammar, ident);
+/// Returns `false` if an attribute is erroneous.
+fn merge_grammar_attributes(grammar: &mut AGrammar, attrs: Vec<syn::Attribute>) -> bool {
+  let mut valid = true;
+  for attr in &attrs {
+    match attr.parse_meta() {
+      Ok(syn::Meta::Path(ref path)) if path.get_ident().is_some() => {
+        merge_grammar_attr(grammar, path.get_ident().unwrap());
+      }
+      Ok(syn::Meta::List(ref list)) if list.path.get_ident().is_some() => {
+        valid &= merge_grammar_list_attr(grammar, list.path.get_ident().unwrap(), list);
+      }
+      Ok(syn::Meta::NameValue(ref name_value)) if name_value.path.get_ident().is_some() => {
+        valid &= merge_grammar_name_value_attr(grammar, name_value.path.get_ident().unwrap(), name_value);
+      }
+      _ => {
+        warn_ignore_attr(attr.span());
+      }
     }
-    else {
-      warn_ignore_attr(attr.span());
+  }
+  match grammar.attributes.left_recursion_level {
+    Some(LintLevel::Warn) => grammar.attributes.left_recursion = true,
+    Some(LintLevel::Deny) if grammar.attributes.left_recursion => {
+      let left_rec_attr = attrs.iter().find(|attr| attr.path.is_ident("left_recursion")).unwrap();
+      left_rec_attr.span().unstable().error(
+        "`#![left_recursion]` cannot be used with `#![deny(left_recursion)]`.")
+      .emit();
+      valid = false;
     }
+    _ => ()
+  }
+  if grammar.attributes.trace && grammar.attributes.no_std {
+    let trace_attr = attrs.iter().find(|attr| attr.path.is_ident("trace")).unwrap();
+    trace_attr.span().unstable().error(
+      "`#![trace]` prints on the standard error and cannot be used with `#![no_std]`.")
+    .emit();
+    valid = false;
+  }
+  if grammar.attributes.cst && grammar.attributes.no_std {
+    let cst_attr = attrs.iter().find(|attr| attr.path.is_ident("cst")).unwrap();
+    cst_attr.span().unstable().error(
+      "`#![cst]` records the concrete syntax tree with the standard library and cannot be used with `#![no_std]`.")
+    .emit();
+    valid = false;
   }
+  valid
+}
+
+/// Attributes of the form `#![name(a, b, c)]`.
+fn merge_grammar_list_attr(grammar: &mut AGrammar, ident: &Ident, list: &syn::MetaList) -> bool {
+  match &*ident.to_string() {
+    "start" => {
+      let rules = list_of_idents(list);
+      grammar.attributes.start_rules.extend(rules);
+    },
+    "recognizer" => {
+      let mut valid = true;
+      for id in list_of_idents(list) {
+        match grammar.rules.iter().position(|r| id == r.ident()) {
+          Some(i) => grammar.rules[i].recognizer_only = true,
+          None => valid &= error_unknown_rule(&id, &grammar.rules)
+        }
+      }
+      return valid;
+    },
+    "memoize" => {
+      let mut valid = true;
+      for id in list_of_idents(list) {
+        if grammar.rules.iter().any(|r| id == r.ident()) {
+          grammar.attributes.memoized_rules.push(id);
+        }
+        else {
+          valid &= error_unknown_rule(&id, &grammar.rules);
+        }
+      }
+      return valid;
+    },
+    "derive_from_str" => {
+      let mut valid = true;
+      for id in list_of_idents(list) {
+        if grammar.rules.iter().any(|r| id == r.ident()) {
+          grammar.attributes.from_str_rules.push(id);
+        }
+        else {
+          valid &= error_unknown_rule(&id, &grammar.rules);
+        }
+      }
+      return valid;
+    },
+    "warn" | "deny" => {
+      let level = if ident == "warn" { LintLevel::Warn } else { LintLevel::Deny };
+      for lint in list_of_idents(list) {
+        let lint_level = match &*lint.to_string() {
+          "left_recursion" => &mut grammar.attributes.left_recursion_level,
+          "trivial_rules" => &mut grammar.attributes.trivial_rules_level,
+          _ => {
+            lint.span().unstable().warning(format!(
+              "unknown lint `{}`: it will be ignored, the lints are `left_recursion` and `trivial_rules`.", lint))
+            .emit();
+            continue;
+          }
+        };
+        if lint_level.is_some_and(|l| l != level) {
+          lint.span().unstable().error(format!(
+            "`{}` is given both with `#![warn]` and `#![deny]`.", lint))
+          .emit();
+          return false;
+        }
+        *lint_level = Some(level);
+      }
+    },
+    "module" => {
+      let names = list_of_idents(list);
+      if names.len() != 1 {
+        list.span().unstable().error(
+          "`#![module(name)]` expects exactly one name, the one of the module containing the generated items.")
+        .emit();
+        return false;
+      }
+      grammar.attributes.module = Some(names[0].clone());
+    },
+    "spacing" => {
+      let rules = list_of_idents(list);
+      if rules.len() != 1 {
+        list.span().unstable().error(
+          "`#![spacing(r)]` expects exactly one rule, the one called between the elements of a sequence.")
+        .emit();
+        return false;
+      }
+      let id = rules[0].clone();
+      if !grammar.rules.iter().any(|r| id == r.ident()) {
+        return error_unknown_rule(&id, &grammar.rules);
+      }
+      if let Some(ref previous) = grammar.attributes.spacing_rule {
+        id.span().unstable().error(
+          "the spacing rule is declared more than once.")
+        .span_note(previous.span().unstable(),
+          "Previous spacing rule")
+        .emit();
+        return false;
+      }
+      grammar.attributes.spacing_rule = Some(id);
+    },
+    // The included grammars are already parsed by the parser.
+    "include" => (),
+    // The rule is already used by the parser when it rewrites `keyword("lit")`.
+    "keyword_boundary" => {
+      let rules = list_of_idents(list);
+      if rules.len() != 1 {
+        list.span().unstable().error(
+          "`#![keyword_boundary(r)]` expects exactly one rule, the one matching the characters that cannot follow a keyword.")
+        .emit();
+        return false;
+      }
+      let id = rules[0].clone();
+      if !grammar.rules.iter().any(|r| id == r.ident()) {
+        return error_unknown_rule(&id, &grammar.rules);
+      }
+      grammar.attributes.keyword_boundary = Some(id);
+    },
+    _ => {
+      warn_ignore_attr(ident.span());
+    }
+  }
+  true
+}
+
+/// Attributes of the form `#![name = "value"]`.
+fn merge_grammar_name_value_attr(grammar: &mut AGrammar, ident: &Ident, name_value: &syn::MetaNameValue) -> bool {
+  match &*ident.to_string() {
+    "dump_ast" => {
+      match name_value.lit {
+        syn::Lit::Str(ref path) => {
+          if cfg!(feature = "dump_ast") {
+            grammar.attributes.dump_ast = Some(path.value());
+          }
+          else {
+            ident.span().unstable().warning(
+              "`#![dump_ast]` requires the feature `dump_ast` of Oak: it will be ignored.")
+            .emit();
+          }
+        }
+        _ => {
+          name_value.lit.span().unstable().error(
+            "`#![dump_ast = \"path\"]` expects the path of the file as a string literal.")
+          .emit();
+          return false;
+        }
+      }
+    },
+    // The path is read before the analyses by `diagnostics_json_path`.
+    "diagnostics_json" => {
+      match name_value.lit {
+        syn::Lit::Str(_) => {
+          if !cfg!(feature = "diagnostics_json") {
+            ident.span().unstable().warning(
+              "`#![diagnostics_json]` requires the feature `diagnostics_json` of Oak: it will be ignored.")
+            .emit();
+          }
+        }
+        _ => {
+          name_value.lit.span().unstable().error(
+            "`#![diagnostics_json = \"path\"]` expects the path of the file as a string literal.")
+          .emit();
+          return false;
+        }
+      }
+    },
+    "depth_limit" => {
+      match name_value.lit {
+        syn::Lit::Int(ref limit) if limit.base10_parse::<usize>().is_ok_and(|n| n > 0) => {
+          grammar.attributes.depth_limit = Some(limit.base10_parse().unwrap());
+        }
+        _ => {
+          name_value.lit.span().unstable().error(
+            "`#![depth_limit = n]` expects the maximal number of nested rule calls as a positive integer.")
+          .emit();
+          return false;
+        }
+      }
+    },
+    // The inner doc comments `//! ...` of the grammar are comments of the DSL.
+    "doc" => (),
+    _ => {
+      warn_ignore_attr(ident.span());
+    }
+  }
+  true
+}
+
+fn error_unknown_rule(id: &Ident, rules: &[Rule]) -> bool {
+  let diagnostic = id.span().unstable().error(format!(
+    "unknown rule `{}`.", id));
+  suggest_closest_rule(diagnostic, &id.to_string(), rules).emit();
+  false
+}
+
+fn list_of_idents(list: &syn::MetaList) -> Vec<Ident> {
+  let mut idents = vec![];
+  for nested in &list.nested {
+    match nested {
+      syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if path.get_ident().is_some() => {
+        idents.push(path.get_ident().unwrap().clone());
+      }
+      _ => {
+        nested.span().unstable().warning(
+          "expected a rule name: it will be ignored.")
+        .emit();
+      }
+    }
+  }
+  idents
 }
 
 fn merge_grammar_attr(grammar: &mut AGrammar, ident: &Ident) {
@@ -46,6 +335,41 @@ fn merge_grammar_attr(grammar: &mut AGrammar, ident: &Ident) {
     "show_typing" => {
       grammar.merge_print_typing(PrintLevel::Show);
     },
+    "packrat" => {
+      grammar.attributes.packrat = true;
+    },
+    "left_recursion" => {
+      grammar.attributes.left_recursion = true;
+    },
+    "no_std" => {
+      grammar.attributes.no_std = true;
+    },
+    "inline_rules" => {
+      grammar.attributes.inline_rules = true;
+    },
+    "trace" => {
+      grammar.attributes.trace = true;
+    },
+    "cst" => {
+      grammar.attributes.cst = true;
+    },
+    "depth_limit" => {
+      grammar.attributes.depth_limit = Some(DEFAULT_DEPTH_LIMIT);
+    },
+    "type_aliases" => {
+      grammar.attributes.type_aliases = true;
+    },
+    "visitor" => {
+      grammar.attributes.visitor = true;
+    },
+    "explain_wfa" => {
+      grammar.attributes.explain_wfa = true;
+    },
+    "recognizer" => {
+      for rule in &mut grammar.rules {
+        rule.recognizer_only = true;
+      }
+    },
     _ => {
       warn_ignore_attr(ident.span());
     }
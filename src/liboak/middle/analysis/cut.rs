@@ -0,0 +1,96 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks that every cut `~` is in a branch of a choice of the same rule, since it has no effect otherwise.
+
+use middle::analysis::ast::*;
+use partial::Partial::*;
+
+pub struct CutPosition<'a>
+{
+  grammar: &'a AGrammar,
+  in_choice: bool,
+  /// The branches of `longest(e1 / e2)` are all tried, so a cut cannot prevent the next branches from being tried.
+  in_longest_choice: bool,
+  error: bool
+}
+
+impl<'a> CutPosition<'a>
+{
+  pub fn analyse(grammar: AGrammar) -> Partial<AGrammar> {
+    if CutPosition::check_cuts(&grammar) {
+      Value(grammar)
+    }
+    else {
+      Nothing
+    }
+  }
+
+  fn check_cuts(grammar: &'a AGrammar) -> bool {
+    let mut analyser = CutPosition {
+      grammar,
+      in_choice: false,
+      in_longest_choice: false,
+      error: false
+    };
+    for rule in &grammar.rules {
+      analyser.in_choice = false;
+      analyser.visit_expr(rule.expr_idx);
+    }
+    !analyser.error
+  }
+}
+
+impl<'a> ExprByIndex for CutPosition<'a>
+{
+  fn expr_by_index(&self, index: usize) -> Expression {
+    self.grammar.expr_by_index(index).clone()
+  }
+}
+
+impl<'a> Visitor<()> for CutPosition<'a>
+{
+  unit_visitor_impl!(sequence);
+
+  fn visit_choice(&mut self, _this: usize, children: Vec<usize>) {
+    let old = self.in_choice;
+    self.in_choice = true;
+    walk_exprs(self, children);
+    self.in_choice = old;
+  }
+
+  fn visit_longest_choice(&mut self, _this: usize, children: Vec<usize>) {
+    let old = (self.in_choice, self.in_longest_choice);
+    self.in_choice = false;
+    self.in_longest_choice = true;
+    walk_exprs(self, children);
+    self.in_choice = old.0;
+    self.in_longest_choice = old.1;
+  }
+
+  fn visit_cut(&mut self, this: usize) {
+    if self.in_longest_choice && !self.in_choice {
+      self.error = true;
+      self.grammar[this].span().unstable()
+        .error("the cut operator `~` has no effect in a branch of `longest(..)` since all its branches are tried.")
+        .emit();
+    }
+    else if !self.in_choice {
+      self.error = true;
+      self.grammar[this].span().unstable()
+        .error("the cut operator `~` must be in a branch of a choice of the same rule, otherwise it has no effect.")
+        .emit();
+    }
+  }
+}
@@ -0,0 +1,114 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The errors and warnings of the analyses are recorded with their kind and the rule involved, in addition to being emitted to `rustc`.
+//! With the feature `diagnostics_json`, the attribute `#![diagnostics_json = "path"]` writes them in JSON to the file `path`, for editor integration.
+
+use middle::analysis::ast::*;
+use std::cell::RefCell;
+
+#[cfg_attr(not(feature = "diagnostics_json"), allow(dead_code))]
+pub struct RecordedDiagnostic
+{
+  /// The kind of the diagnostic, such as `left_recursion` or `never_succeed`.
+  pub kind: &'static str,
+  /// `error`, `warning` or `note`.
+  pub level: &'static str,
+  pub rule: Option<String>,
+  /// The line and the column (starting at 1 and 0) of the start and of the end of the span.
+  pub start: (usize, usize),
+  pub end: (usize, usize),
+  pub message: String
+}
+
+thread_local! {
+  /// The grammars are analysed one after the other, the diagnostics of the previous grammar are cleared by `clear_diagnostics`.
+  static DIAGNOSTICS: RefCell<Vec<RecordedDiagnostic>> = const { RefCell::new(vec![]) };
+}
+
+/// Records an error and returns it, so notes can be attached before it is emitted.
+pub fn error<M: Into<String>>(kind: &'static str, rule: Option<&Ident>, span: Span, message: M) -> proc_macro::Diagnostic {
+  let message = message.into();
+  record(kind, "error", rule, span, &message);
+  span.unstable().error(message)
+}
+
+/// Records a warning and returns it, so notes can be attached before it is emitted.
+pub fn warning<M: Into<String>>(kind: &'static str, rule: Option<&Ident>, span: Span, message: M) -> proc_macro::Diagnostic {
+  let message = message.into();
+  record(kind, "warning", rule, span, &message);
+  span.unstable().warning(message)
+}
+
+/// Records a note, reported alone to complete a previous error.
+pub fn note<M: Into<String>>(kind: &'static str, rule: Option<&Ident>, span: Span, message: M) -> proc_macro::Diagnostic {
+  let message = message.into();
+  record(kind, "note", rule, span, &message);
+  span.unstable().note(message)
+}
+
+fn record(kind: &'static str, level: &'static str, rule: Option<&Ident>, span: Span, message: &str) {
+  let span = span.unstable();
+  let (start, end) = (span.start(), span.end());
+  DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().push(RecordedDiagnostic {
+    kind, level,
+    rule: rule.map(|rule| rule.to_string()),
+    start: (start.line(), start.column()),
+    end: (end.line(), end.column()),
+    message: message.to_string()
+  }));
+}
+
+pub fn clear_diagnostics() {
+  DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().clear());
+}
+
+/// The path given by `#![diagnostics_json = "path"]`, it is read before the analyses since they can fail before the attributes are checked (see `merge_grammar_name_value_attr`).
+pub fn diagnostics_json_path(attributes: &[syn::Attribute]) -> Option<String> {
+  attributes.iter()
+    .filter(|attr| attr.path.is_ident("diagnostics_json"))
+    .filter_map(|attr| match attr.parse_meta() {
+      Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(ref path), .. })) => Some(path.value()),
+      _ => None
+    })
+    .next_back()
+}
+
+#[cfg(feature = "diagnostics_json")]
+pub fn write_diagnostics_json(path: &str, span: Span) {
+  use serde_json::{Value, json};
+  let diagnostics: Vec<Value> = DIAGNOSTICS.with(|diagnostics| diagnostics.borrow().iter()
+    .map(|diagnostic| json!({
+      "kind": diagnostic.kind,
+      "level": diagnostic.level,
+      "rule": diagnostic.rule,
+      "span": {
+        "start": [diagnostic.start.0, diagnostic.start.1],
+        "end": [diagnostic.end.0, diagnostic.end.1]
+      },
+      "message": diagnostic.message
+    }))
+    .collect());
+  let content = serde_json::to_string_pretty(&json!({ "diagnostics": diagnostics }))
+    .expect("serializing a JSON value");
+  // The directory of the file is created, e.g. `target` is not created yet when a crate is compiled for the first time with another target directory.
+  let file = crate_relative_path(path);
+  let written = file.parent().map_or(Ok(()), ::std::fs::create_dir_all)
+    .and_then(|_| ::std::fs::write(&file, content));
+  if let Err(err) = written {
+    span.unstable().error(format!(
+      "cannot write the diagnostics to `{}` (`#![diagnostics_json]`): {}.", path, err))
+    .emit();
+  }
+}
@@ -13,8 +13,11 @@
 // limitations under the License.
 
 pub use std::collections::HashMap;
+use std::cmp::min;
 
 use middle::analysis::ast::*;
+use middle::analysis::diagnostics;
+use front::ast::ParametricRule;
 use partial::Partial::*;
 
 pub fn rule_duplicate(mut grammar: AGrammar, rules: Vec<Rule>) -> Partial<AGrammar>
@@ -26,6 +29,22 @@ pub fn rule_duplicate(mut grammar: AGrammar, rules: Vec<Rule>) -> Partial<AGramm
   })
 }
 
+/// Returns `false` if two parametric rules, or a parametric rule and a rule, have the same name.
+pub fn parametric_rule_duplicate(rules: &[Rule], parametric_rules: &[ParametricRule]) -> bool
+{
+  let mut valid = matches!(DuplicateItem::analyse(parametric_rules.iter().cloned(), String::from("parametric rule")), Value(_));
+  for parametric_rule in parametric_rules {
+    if let Some(rule) = rules.iter().find(|r| r.ident() == parametric_rule.ident()) {
+      diagnostics::error("duplicate", Some(&rule.ident()), parametric_rule.span(),
+          format!("duplicate definition of rule with name `{}`", parametric_rule.ident()))
+        .span_note(rule.span().unstable(), format!("previous definition of `{}` here", rule.ident()))
+        .emit();
+      valid = false;
+    }
+  }
+  valid
+}
+
 pub fn rust_functions_duplicate(mut grammar: AGrammar, items: Vec<syn::Item>) -> Partial<AGrammar>
 {
   let mut functions = vec![];
@@ -46,6 +65,38 @@ pub fn rust_functions_duplicate(mut grammar: AGrammar, items: Vec<syn::Item>) ->
     })
 }
 
+/// A rule and a Rust function with the same name are rejected, since a call in a semantic action such as `r > f` could be mistaken for the other.
+pub fn rule_rust_function_duplicate(grammar: AGrammar) -> Partial<AGrammar>
+{
+  let mut valid = true;
+  for rule in &grammar.rules {
+    if let Some(fun) = grammar.rust_functions.get(&rule.ident()) {
+      diagnostics::error("duplicate", Some(&rule.ident()), fun.span(),
+          format!("the rust function `{}` has the same name as a rule", fun.ident()))
+        .span_note(rule.span().unstable(), format!("rule `{}` defined here", rule.ident()))
+        .emit();
+      valid = false;
+    }
+  }
+  if valid { Value(grammar) } else { Fake(grammar) }
+}
+
+/// The path of the grammar included with `#![include("path")]` that declares the item, it is mentioned in the duplicate errors.
+trait IncludedItem
+{
+  fn included_from(&self) -> Option<String> { None }
+}
+
+impl IncludedItem for Rule
+{
+  fn included_from(&self) -> Option<String> {
+    self.included_from.clone()
+  }
+}
+
+impl IncludedItem for ParametricRule {}
+impl IncludedItem for syn::ItemFn {}
+
 struct DuplicateItem<Item>
 {
   items: Vec<(Ident, Item)>,
@@ -54,7 +105,7 @@ struct DuplicateItem<Item>
 }
 
 impl<Item> DuplicateItem<Item> where
- Item: ItemIdent + Spanned
+ Item: ItemIdent + Spanned + IncludedItem
 {
   pub fn analyse<ItemIter>(iter: ItemIter, item_kind: String)
     -> Partial<Vec<(Ident, Item)>> where
@@ -86,9 +137,11 @@ impl<Item> DuplicateItem<Item> where
   }
 
   fn duplicate_items(&self, pre: &Item, current: Item) {
-    current.span().unstable()
-      .error(format!("duplicate definition of {} with name `{}`", self.what_is_duplicated, current.ident()))
-      .span_note(pre.span().unstable(), format!("previous definition of `{}` here", pre.ident()))
+    let included = |item: &Item| item.included_from()
+      .map_or(String::new(), |path| format!(" in the included grammar `{}`", path));
+    diagnostics::error("duplicate", Some(&current.ident()), current.span(),
+        format!("duplicate definition of {} with name `{}`{}", self.what_is_duplicated, current.ident(), included(&current)))
+      .span_note(pre.span().unstable(), format!("previous definition of `{}`{} here", pre.ident(), included(pre)))
       .emit();
   }
 
@@ -100,3 +153,36 @@ impl<Item> DuplicateItem<Item> where
     }
   }
 }
+
+/// Adds a note to `diagnostic` suggesting the rule with the name the closest to `name`, unless no rule is close enough.
+pub fn suggest_closest_rule(diagnostic: proc_macro::Diagnostic, name: &str, rules: &[Rule]) -> proc_macro::Diagnostic {
+  match closest_rule(name, rules) {
+    Some(rule) => diagnostic.span_note(rule.span().unstable(), format!("did you mean `{}`?", rule.ident())),
+    None => diagnostic
+  }
+}
+
+/// The rule with the name the closest to `name`, if the edit distance is at most 2, which is used to suggest a fix for misspelled rule names.
+/// The rules with the same name as `name` are ignored.
+pub fn closest_rule<'a>(name: &str, rules: &'a [Rule]) -> Option<&'a Rule> {
+  rules.iter()
+    .map(|rule| (levenshtein(name, &rule.ident().to_string()), rule))
+    .filter(|&(distance, _)| distance > 0 && distance <= 2)
+    .min_by_key(|&(distance, _)| distance)
+    .map(|(_, rule)| rule)
+}
+
+/// Minimal number of insertions, deletions and substitutions of characters to transform `a` into `b`.
+fn levenshtein(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut previous: Vec<usize> = (0..b.len() + 1).collect();
+  for (i, ca) in a.chars().enumerate() {
+    let mut current = vec![i + 1];
+    for (j, cb) in b.iter().enumerate() {
+      let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+      current.push(min(substitution, min(previous[j + 1], current[j]) + 1));
+    }
+    previous = current;
+  }
+  previous[b.len()]
+}
@@ -0,0 +1,377 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Computes the FIRST set of every expression, that is the set of characters that can start this expression (see `FirstSet`).
+//! Similarly to `WellFormedness`, the FIRST sets of the rules are computed by a fixpoint since rules can be recursive.
+//! The FIRST sets are stored in `grammar.first_sets` for the back-end.
+//!
+//! We use them to warn about unreachable branches in a choice: in `["a-z"] / "if"`, the second branch is never tried because every input starting with a lowercase letter is accepted by the first branch.
+//! Similarly, in the sequence `["a-z"]* "if"`, the literal never matches because the repetition consumes every lowercase letter.
+//!
+//! The FIRST sets are sets of characters, they are not computed when the grammar parses atoms of another type (`type Atom = Token;`).
+
+use middle::analysis::ast::*;
+use partial::Partial::*;
+use std::collections::HashMap;
+
+pub struct FirstSetAnalysis<'a>
+{
+  grammar: &'a AGrammar,
+  rules_first: HashMap<String, FirstSet>,
+  exprs_first: HashMap<usize, FirstSet>,
+  reached_fixpoint: bool,
+  /// True when visiting `strict(e)` or the spacing rule, where no spacing is inserted in the sequences.
+  strict: bool
+}
+
+impl<'a> FirstSetAnalysis<'a>
+{
+  pub fn analyse(mut grammar: AGrammar) -> Partial<AGrammar> {
+    if grammar.atom_alias().is_some() {
+      return Value(grammar);
+    }
+    let first_sets = FirstSetAnalysis::compute(&grammar);
+    grammar.first_sets = first_sets;
+    FirstSetAnalysis::check_choices(&grammar);
+    FirstSetAnalysis::check_sequences(&grammar);
+    Value(grammar)
+  }
+
+  fn compute(grammar: &'a AGrammar) -> HashMap<usize, FirstSet> {
+    let mut analyser = FirstSetAnalysis {
+      grammar,
+      rules_first: grammar.rules.iter()
+        .map(|r| (r.ident().to_string(), FirstSet::default()))
+        .collect(),
+      exprs_first: HashMap::new(),
+      reached_fixpoint: false,
+      strict: false
+    };
+    while !analyser.reached_fixpoint {
+      analyser.reached_fixpoint = true;
+      for rule in &grammar.rules {
+        analyser.strict = grammar.is_spacing_rule(&rule.ident());
+        let first = analyser.visit_expr(rule.expr_idx);
+        let name = rule.ident().to_string();
+        if analyser.rules_first[&name] != first {
+          analyser.reached_fixpoint = false;
+          analyser.rules_first.insert(name, first);
+        }
+      }
+    }
+    analyser.exprs_first
+  }
+
+  fn spacing_first(&self) -> Option<FirstSet> {
+    match self.grammar.attributes.spacing_rule {
+      Some(ref spacing) if !self.strict => Some(self.rules_first[&spacing.to_string()].clone()),
+      _ => None
+    }
+  }
+
+  fn check_choices(grammar: &AGrammar) {
+    for expr in &grammar.exprs {
+      if let Expression::Choice(children) = expr {
+        FirstSetAnalysis::check_choice(grammar, children);
+      }
+    }
+  }
+
+  /// A branch is unreachable if an earlier branch is "complete" (see `is_complete`) and its FIRST set includes the one of the branch.
+  fn check_choice(grammar: &AGrammar, children: &[usize]) {
+    for (i, &earlier) in children.iter().enumerate() {
+      if !is_complete(grammar, earlier, &mut vec![]) { continue; }
+      for &later in children.iter().skip(i + 1) {
+        let later_first = &grammar.first_sets[&later];
+        if !later_first.nullable && !later_first.is_empty()
+         && grammar.first_sets[&earlier].contains(later_first)
+        {
+          grammar[later].span().unstable().warning(
+            "Unreachable branch in a choice expression. Every input starting \
+            like this branch is accepted by an earlier branch.\n\
+            Solution: Either remove (or rewrite) this branch or move it \
+            before the earlier branch.")
+          .span_note(grammar[earlier].span().unstable(),
+            "Earlier branch")
+          .emit();
+          return;
+        }
+      }
+    }
+  }
+
+  /// The implicit spacing inserted by `#![spacing(r)]` between the elements of a sequence might consume the characters stopping a repetition, so we do not check the sequences in this case.
+  fn check_sequences(grammar: &AGrammar) {
+    if grammar.attributes.spacing_rule.is_some() {
+      return;
+    }
+    for expr in &grammar.exprs {
+      if let Expression::Sequence(children) = expr {
+        FirstSetAnalysis::check_sequence(grammar, children);
+      }
+    }
+  }
+
+  /// In `["a-z"]* "if"`, the repetition stops on a character that is not a lowercase letter (or at the end of input), which can never start `"if"`.
+  /// Therefore, if the repeated expression is complete (see `is_complete`), the next element must not start with a character of its FIRST set.
+  fn check_sequence(grammar: &AGrammar, children: &[usize]) {
+    for (&repeat, &next) in children.iter().zip(children.iter().skip(1)) {
+      let child = match grammar.expr_by_index(repeat) {
+        Expression::ZeroOrMore(child)
+      | Expression::OneOrMore(child) => child,
+        _ => continue
+      };
+      if !is_complete(grammar, child, &mut vec![]) { continue; }
+      let next_first = &grammar.first_sets[&next];
+      if !next_first.nullable && !next_first.is_empty()
+       && grammar.first_sets[&child].contains(next_first)
+      {
+        grammar[next].span().unstable().warning(
+          "This expression never matches because the previous repetition consumes \
+          every character it can start with (PEG repetitions are greedy and never give back characters).\n\
+          Solution: Exclude the characters of this expression from the repetition, \
+          for instance `(!\"x\" .)* \"x\"` instead of `.* \"x\"`.")
+        .span_note(grammar[repeat].span().unstable(),
+          "Greedy repetition")
+        .emit();
+        return;
+      }
+    }
+  }
+}
+
+/// An expression is complete if it succeeds on every input starting with a character of its FIRST set, e.g. `["a-z"]+` is complete but not `"ab"`.
+fn is_complete(grammar: &AGrammar, expr_idx: usize, visiting: &mut Vec<String>) -> bool {
+  use ast::Expression::*;
+  if grammar.first_sets[&expr_idx].nullable {
+    return false;
+  }
+  match grammar.expr_by_index(expr_idx) {
+    StrLiteral(lit, _) => lit.chars().count() == 1,
+    AnySingleChar => true,
+    // The FIRST set of a class with Unicode categories is over-approximated (see `visit_character_class`).
+    CharacterClass(class) => class.categories.is_empty(),
+    NonTerminalSymbol(rule) => {
+      let name = rule.to_string();
+      if visiting.contains(&name) {
+        false
+      }
+      else {
+        visiting.push(name);
+        let complete = is_complete(grammar, grammar.expr_index_of_rule(&rule), visiting);
+        visiting.pop();
+        complete
+      }
+    }
+    Choice(children)
+  | LongestChoice(children) => children.into_iter().all(|c| is_complete(grammar, c, visiting)),
+    Sequence(children) => {
+      is_complete(grammar, children[0], visiting)
+      && children.into_iter().skip(1).all(|c| never_fail(grammar, c))
+    }
+    OneOrMore(child)
+  | BoundedRepeat(child, 1, _)
+  | SeparatedList(child, _, true, _)
+  | TypeAscription(child, _)
+  | SpannedExpr(child)
+  | RangeExpr(child)
+  | ByteSpanned(child)
+  | Capture(child)
+  | ErrorLabel(child, _)
+  | Strict(child)
+  | Named(child, _) => is_complete(grammar, child, visiting),
+    // A semantic action returning `Result<T, E>` fails the expression on an error, as a guard on a false condition.
+    SemanticAction(child, _, action) =>
+      grammar.fallible_action_type(&action).is_none() && is_complete(grammar, child, visiting),
+    _ => false
+  }
+}
+
+fn never_fail(grammar: &AGrammar, expr_idx: usize) -> bool {
+  use ast::Expression::*;
+  match grammar.expr_by_index(expr_idx) {
+    Cut
+  | ZeroOrMore(_)
+  | Count(_)
+  | ZeroOrOne(_)
+  | BoundedRepeat(_, 0, _)
+  | SeparatedList(_, _, false, _) => true,
+    SemanticAction(child, _, action) =>
+      grammar.fallible_action_type(&action).is_none() && never_fail(grammar, child),
+    TypeAscription(child, _)
+  | SpannedExpr(child)
+  | RangeExpr(child)
+  | ByteSpanned(child)
+  | Capture(child)
+  | ErrorLabel(child, _)
+  | Strict(child)
+  | Named(child, _) => never_fail(grammar, child),
+    _ => false
+  }
+}
+
+impl<'a> ExprByIndex for FirstSetAnalysis<'a>
+{
+  fn expr_by_index(&self, index: usize) -> Expression {
+    self.grammar.expr_by_index(index).clone()
+  }
+}
+
+impl<'a> Visitor<FirstSet> for FirstSetAnalysis<'a>
+{
+  fn visit_expr(&mut self, this: usize) -> FirstSet {
+    let first = walk_expr(self, this);
+    self.exprs_first.insert(this, first.clone());
+    first
+  }
+
+  fn visit_str_literal(&mut self, _this: usize, lit: String, case_insensitive: bool) -> FirstSet {
+    match lit.chars().next() {
+      None => FirstSet::nullable(),
+      Some(c) if case_insensitive => {
+        let (l, u) = (c.to_ascii_lowercase(), c.to_ascii_uppercase());
+        FirstSet::new(vec![(l, l), (u, u)], false)
+      }
+      Some(c) => FirstSet::new(vec![(c, c)], false)
+    }
+  }
+
+  fn visit_any_single_char(&mut self, _this: usize) -> FirstSet {
+    FirstSet::any_char()
+  }
+
+  // The pattern is a Rust pattern, it is over-approximated by any character.
+  fn visit_token_pattern(&mut self, _this: usize, _pattern: syn::Pat) -> FirstSet {
+    FirstSet::any_char()
+  }
+
+  fn visit_character_class(&mut self, _this: usize, char_class: CharacterClassExpr) -> FirstSet {
+    let intervals = char_class.intervals.iter()
+      .map(|i| (i.lo, i.hi))
+      .collect();
+    // The Unicode categories are over-approximated by any character, even in a negated class.
+    if !char_class.categories.is_empty() {
+      FirstSet::any_char()
+    }
+    else if char_class.negated {
+      FirstSet::complement(intervals)
+    }
+    else {
+      FirstSet::new(intervals, false)
+    }
+  }
+
+  fn visit_end_of_input(&mut self, _this: usize) -> FirstSet {
+    FirstSet::nullable()
+  }
+
+  fn visit_cut(&mut self, _this: usize) -> FirstSet {
+    FirstSet::nullable()
+  }
+
+  fn visit_non_terminal_symbol(&mut self, _this: usize, rule: &Ident) -> FirstSet {
+    self.rules_first[&rule.to_string()].clone()
+  }
+
+  // We know nothing about external parsers.
+  fn visit_external_non_terminal_symbol(&mut self, _this: usize, _rule: &syn::Path) -> FirstSet {
+    let mut first = FirstSet::any_char();
+    first.nullable = true;
+    first
+  }
+
+  /// The spacing rule is called between the elements of a sequence, thus it can start the sequence if the previous elements are nullable.
+  fn visit_sequence(&mut self, _this: usize, children: Vec<usize>) -> FirstSet {
+    let mut first = FirstSet::nullable();
+    let mut prefix_nullable = true;
+    let spacing_first = self.spacing_first();
+    for (i, child) in children.into_iter().enumerate() {
+      let child_first = self.visit_expr(child);
+      if prefix_nullable {
+        match spacing_first {
+          Some(ref spacing_first) if i > 0 => first.union_chars(spacing_first),
+          _ => ()
+        }
+        first.union_chars(&child_first);
+        prefix_nullable = child_first.nullable;
+      }
+    }
+    first.nullable = prefix_nullable;
+    first
+  }
+
+  fn visit_choice(&mut self, _this: usize, children: Vec<usize>) -> FirstSet {
+    let mut first = FirstSet::default();
+    for child in children {
+      let child_first = self.visit_expr(child);
+      first.union(&child_first);
+    }
+    first
+  }
+
+  fn visit_zero_or_more(&mut self, _this: usize, child: usize) -> FirstSet {
+    let mut first = self.visit_expr(child);
+    first.nullable = true;
+    first
+  }
+
+  fn visit_optional(&mut self, this: usize, child: usize) -> FirstSet {
+    self.visit_zero_or_more(this, child)
+  }
+
+  fn visit_repeat_until_eoi(&mut self, this: usize, child: usize) -> FirstSet {
+    self.visit_zero_or_more(this, child)
+  }
+
+  fn visit_bounded_repeat(&mut self, this: usize, child: usize, min: usize, _max: Option<usize>) -> FirstSet {
+    if min == 0 {
+      self.visit_zero_or_more(this, child)
+    }
+    else {
+      self.visit_expr(child)
+    }
+  }
+
+  fn visit_separated_list(&mut self, this: usize, child: usize, sep: usize, one_or_more: bool) -> FirstSet {
+    self.visit_expr(sep);
+    if one_or_more {
+      self.visit_expr(child)
+    }
+    else {
+      self.visit_zero_or_more(this, child)
+    }
+  }
+
+  // The input skipped after a failure of `child` can start with any character.
+  fn visit_recover(&mut self, _this: usize, child: usize, sync: usize) -> FirstSet {
+    self.visit_expr(sync);
+    let mut first = FirstSet::any_char();
+    first.nullable = self.visit_expr(child).nullable;
+    first
+  }
+
+  fn visit_strict(&mut self, _this: usize, child: usize) -> FirstSet {
+    let strict = self.strict;
+    self.strict = true;
+    let first = self.visit_expr(child);
+    self.strict = strict;
+    first
+  }
+
+  // Predicates do not consume input.
+  fn visit_syntactic_predicate(&mut self, _this: usize, child: usize) -> FirstSet {
+    self.visit_expr(child);
+    FirstSet::nullable()
+  }
+}
@@ -0,0 +1,119 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A left-recursive rule is compiled with the seed-growing algorithm where the first seed is obtained by calling the rule while its recursive calls fail.
+//! `BaseCase` checks that this first seed can succeed, otherwise the rule would loop forever (e.g. `a = a "x"`).
+//! It is an approximation: we only check that the expression can succeed when the recursive calls fail, independently of the input.
+
+use middle::analysis::ast::*;
+
+pub struct BaseCase<'a>
+{
+  grammar: &'a AGrammar,
+  head: Ident,
+  visiting: Vec<Ident>
+}
+
+impl<'a> BaseCase<'a>
+{
+  pub fn has_base_case(grammar: &'a AGrammar, head: &Ident) -> bool {
+    let mut analyser = BaseCase {
+      grammar,
+      head: head.clone(),
+      visiting: vec![]
+    };
+    let expr_idx = grammar.expr_index_of_rule(head);
+    analyser.visit_expr(expr_idx)
+  }
+
+  fn is_rec(&self, rule: &Ident) -> bool {
+    self.head == *rule
+    || self.visiting.contains(rule)
+  }
+}
+
+impl<'a> ExprByIndex for BaseCase<'a>
+{
+  fn expr_by_index(&self, index: usize) -> Expression {
+    self.grammar.expr_by_index(index).clone()
+  }
+}
+
+impl<'a> Visitor<bool> for BaseCase<'a>
+{
+  fn visit_str_literal(&mut self, _this: usize, _lit: String, _case_insensitive: bool) -> bool {
+    true
+  }
+
+  fn visit_atom(&mut self, _this: usize) -> bool {
+    true
+  }
+
+  fn visit_end_of_input(&mut self, _this: usize) -> bool {
+    true
+  }
+
+  fn visit_cut(&mut self, _this: usize) -> bool {
+    true
+  }
+
+  fn visit_external_non_terminal_symbol(&mut self, _this: usize, _rule: &syn::Path) -> bool {
+    true
+  }
+
+  fn visit_non_terminal_symbol(&mut self, _this: usize, rule: &Ident) -> bool {
+    if self.is_rec(rule) {
+      false
+    }
+    else {
+      self.visiting.push(rule.clone());
+      let expr_idx = self.grammar.expr_index_of_rule(rule);
+      let base_case = self.visit_expr(expr_idx);
+      self.visiting.pop();
+      base_case
+    }
+  }
+
+  fn visit_sequence(&mut self, _this: usize, children: Vec<usize>) -> bool {
+    children.into_iter().all(|child| self.visit_expr(child))
+  }
+
+  fn visit_choice(&mut self, _this: usize, children: Vec<usize>) -> bool {
+    children.into_iter().any(|child| self.visit_expr(child))
+  }
+
+  fn visit_zero_or_more(&mut self, _this: usize, _child: usize) -> bool {
+    true
+  }
+
+  fn visit_optional(&mut self, _this: usize, _child: usize) -> bool {
+    true
+  }
+
+  fn visit_repeat_until_eoi(&mut self, _this: usize, _child: usize) -> bool {
+    true
+  }
+
+  fn visit_bounded_repeat(&mut self, _this: usize, child: usize, min: usize, _max: Option<usize>) -> bool {
+    min == 0 || self.visit_expr(child)
+  }
+
+  fn visit_separated_list(&mut self, _this: usize, child: usize, _sep: usize, one_or_more: bool) -> bool {
+    !one_or_more || self.visit_expr(child)
+  }
+
+  fn visit_not_predicate(&mut self, _this: usize, _child: usize) -> bool {
+    true
+  }
+}
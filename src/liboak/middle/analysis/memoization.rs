@@ -0,0 +1,65 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Selects the rules memoized in packrat mode (`#![packrat]`) and the left-recursive rules compiled with the seed-growing algorithm (`#![left_recursion]`).
+//! Memoizing a rule called only once at a given position is a waste of memory, so we only memoize the rules that are recursive or referenced more than once in the grammar.
+//! The rules listed in `#![memoize(r1, r2)]` are memoized even without `#![packrat]`, it limits the memory used by the memoization table to the rules known to be called several times at the same position.
+//! The rules of a left-recursive cycle, except its head, are never memoized because their results change while the seed of the head grows.
+
+use middle::analysis::ast::*;
+use std::collections::{HashMap, HashSet};
+
+/// Rules involved in cycles, computed by the well-formedness analysis.
+#[derive(Default)]
+pub struct RecursionInfo
+{
+  pub recursive_rules: HashSet<Ident>,
+  /// Rules belonging to a left-recursive cycle.
+  pub left_recursive_cycles: HashSet<Ident>,
+  /// A single rule per left-recursive cycle, the one where the seed grows.
+  pub left_recursive_heads: HashSet<Ident>
+}
+
+pub fn memoize_rules(mut grammar: AGrammar, info: RecursionInfo) -> AGrammar {
+  let packrat = grammar.attributes.packrat;
+  let references = count_references(&grammar);
+  let mut memoized_rules = HashSet::new();
+  for id in &grammar.attributes.memoized_rules {
+    if info.left_recursive_cycles.contains(id) {
+      id.span().unstable().warning(format!(
+        "the rule `{}` belongs to a left-recursive cycle: it will not be memoized.", id))
+      .emit();
+    }
+    memoized_rules.insert(id.to_string());
+  }
+  for rule in &mut grammar.rules {
+    let id = rule.ident();
+    rule.left_recursive = info.left_recursive_heads.contains(&id);
+    let called_again = info.recursive_rules.contains(&id)
+      || references.get(&id.to_string()).is_some_and(|&n| n > 1);
+    rule.memoized = !info.left_recursive_cycles.contains(&id)
+      && (memoized_rules.contains(&id.to_string()) || packrat && called_again);
+  }
+  grammar
+}
+
+fn count_references(grammar: &AGrammar) -> HashMap<String, usize> {
+  let mut references = HashMap::new();
+  for expr in &grammar.exprs {
+    if let Expression::NonTerminalSymbol(id) = expr {
+      *references.entry(id.to_string()).or_insert(0) += 1;
+    }
+  }
+  references
+}
@@ -19,6 +19,10 @@ use middle::analysis::resolve_non_terminal::*;
 use middle::analysis::well_formedness::*;
 use middle::analysis::attribute::*;
 use middle::analysis::useless_chaining::*;
+use middle::analysis::unused_rule::*;
+use middle::analysis::first_set::*;
+use middle::analysis::cut::*;
+use middle::analysis::parametric_rule::*;
 // use middle::analysis::unreachable_rule::*;
 
 mod duplicate;
@@ -26,18 +30,34 @@ mod resolve_non_terminal;
 mod well_formedness;
 mod attribute;
 mod useless_chaining;
+mod memoization;
+mod left_recursion;
+mod unused_rule;
+mod first_set;
+mod cut;
+mod parametric_rule;
 // mod unreachable_rule;
 pub mod ast;
+pub mod diagnostics;
 
 pub fn analyse(fgrammar: FGrammar) -> Partial<AGrammar> {
+  expand_parametric_rules(fgrammar)
+  .and_then(analyse_rules)
+}
+
+fn analyse_rules(fgrammar: FGrammar) -> Partial<AGrammar> {
   let grammar = AGrammar::new(fgrammar.start_span, fgrammar.exprs, fgrammar.exprs_info);
   let frust_items = fgrammar.rust_items;
   let fattributes = fgrammar.attributes;
   rule_duplicate(grammar, fgrammar.rules)
   .and_then(|grammar| rust_functions_duplicate(grammar, frust_items))
-  .and_then(|grammar| ResolveNonTerminal::resolve(grammar))
-  .and_then(|grammar| WellFormedness::analyse(grammar))
-  .and_then(|grammar| UselessChaining::analyse(grammar))
-  // .and_then(|grammar| UnreachableRule::analyse(grammar))   // This analysis must be reviewed and fixed.
+  .and_then(rule_rust_function_duplicate)
   .and_then(|grammar| decorate_with_attributes(grammar, fattributes))
+  .and_then(ResolveNonTerminal::resolve)
+  .and_then(UnusedRule::analyse)
+  .and_then(WellFormedness::analyse)
+  .and_then(CutPosition::analyse)
+  .and_then(UselessChaining::analyse)
+  .and_then(FirstSetAnalysis::analyse)
+  // .and_then(|grammar| UnreachableRule::analyse(grammar))   // This analysis must be reviewed and fixed.
 }
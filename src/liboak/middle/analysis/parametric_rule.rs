@@ -0,0 +1,296 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A parametric rule `list<elem> = elem ("," elem)*` is a template whose parameters are rules.
+//! Each call `list<number>` is expanded into the rule `list_number = number ("," number)*`, distinct calls with the same arguments share the same rule.
+//! The parametric rules are then removed from the grammar, and the following analyses are performed on the expanded rules (in particular, the duplicate rules are detected on the names of the instances).
+//! Similarly, a binding `let ws = [" \t"]* in ws e ws` in the rule `r` is expanded into the hidden rule `r_ws = [" \t"]*` and the body `r_ws e r_ws`, the name `ws` only refers to this rule in the body.
+
+use front::ast::*;
+use front::ast::Expression::*;
+use middle::analysis::duplicate::parametric_rule_duplicate;
+use partial::Partial::*;
+
+use std::collections::{HashMap, HashSet};
+
+/// Nesting limit on the expansion of the parametric rules to reject grammars such as `r<e> = e r<r<e>>` where the expansion never stops.
+const EXPANSION_LIMIT: usize = 32;
+
+pub fn expand_parametric_rules(fgrammar: FGrammar) -> Partial<FGrammar> {
+  let has_let = fgrammar.exprs.iter().any(|expr| matches!(expr, Let(_, _, _)));
+  if fgrammar.parametric_rules.is_empty() && !has_let {
+    return Value(fgrammar);
+  }
+  if !parametric_rule_duplicate(&fgrammar.rules, &fgrammar.parametric_rules) {
+    return Nothing;
+  }
+  let mut used_names: HashSet<String> = fgrammar.rules.iter().map(|r| r.name.to_string())
+    .chain(fgrammar.parametric_rules.iter().map(|r| r.name.to_string()))
+    .collect();
+  for item in &fgrammar.rust_items {
+    if let syn::Item::Fn(fun) = item {
+      used_names.insert(fun.sig.ident.to_string());
+    }
+  }
+  let mut grammar = FGrammar::new(fgrammar.start_span);
+  grammar.rust_items = fgrammar.rust_items;
+  grammar.attributes = fgrammar.attributes;
+  let mut expansion = ParametricRuleExpansion {
+    exprs: fgrammar.exprs,
+    exprs_info: fgrammar.exprs_info,
+    parametric_rules: fgrammar.parametric_rules,
+    grammar,
+    instances: HashMap::new(),
+    generated_rules: vec![],
+    current_rule: Ident::new("grammar", fgrammar.start_span),
+    used_names,
+    valid: true
+  };
+  for rule in fgrammar.rules {
+    expansion.current_rule = rule.name.clone();
+    let body = expansion.copy_expr(rule.expr_idx, &HashMap::new(), 0);
+    expansion.grammar.rules.push(Rule { expr_idx: body, ..rule });
+  }
+  if expansion.valid {
+    let mut grammar = expansion.grammar;
+    grammar.rules.extend(expansion.generated_rules);
+    Value(grammar)
+  }
+  else {
+    Nothing
+  }
+}
+
+/// The rules are copied in a new grammar where the calls to parametric rules are replaced by calls to their instances, and the let bindings by calls to their hidden rules.
+/// It avoids keeping the expressions of the parametric rules in the grammar, since they cannot be typed.
+struct ParametricRuleExpansion
+{
+  exprs: Vec<Expression>,
+  exprs_info: Vec<FExpressionInfo>,
+  parametric_rules: Vec<ParametricRule>,
+  grammar: FGrammar,
+  /// The name of the rule created for each instantiation, e.g. `list_number` for `list<number>`.
+  instances: HashMap<String, Ident>,
+  /// The instances of the parametric rules and the hidden rules of the let bindings.
+  generated_rules: Vec<Rule>,
+  /// The rule being copied, it prefixes the names of the rules of its let bindings.
+  current_rule: Ident,
+  /// The names of the rules, parametric rules and Rust functions of the grammar, and of the hidden rules already generated.
+  used_names: HashSet<String>,
+  valid: bool
+}
+
+impl ParametricRuleExpansion
+{
+  fn parametric_rule_of(&self, path: &syn::Path) -> Option<ParametricRule> {
+    if path.segments.len() == 1 {
+      let name = &path.segments[0].ident;
+      self.parametric_rules.iter().find(|r| r.name == *name).cloned()
+    }
+    else { None }
+  }
+
+  /// Creates the rule instantiating `rule` with the arguments of the call `path` (at the expression `idx`), unless it already exists.
+  fn instantiate(&mut self, idx: usize, rule: &ParametricRule, path: &syn::Path, depth: usize) -> Option<Ident> {
+    let span = self.exprs_info[idx].span();
+    let args = self.arguments(span, path)?;
+    if args.len() != rule.params.len() {
+      span.unstable().error(format!(
+        "the parametric rule `{}` expects {} rule argument(s) but {} were given.",
+        rule.name, rule.params.len(), args.len()))
+      .span_note(rule.span().unstable(), format!("`{}` is declared here", rule.name))
+      .emit();
+      self.valid = false;
+      return None;
+    }
+    let name = instance_name(path);
+    if let Some(instance) = self.instances.get(&name) {
+      return Some(instance.clone());
+    }
+    if depth >= EXPANSION_LIMIT {
+      span.unstable().error(format!(
+        "the expansion of the parametric rule `{}` does not terminate (more than {} nested instantiations).",
+        rule.name, EXPANSION_LIMIT))
+      .emit();
+      self.valid = false;
+      return None;
+    }
+    let instance = Ident::new(&name, span);
+    self.instances.insert(name, instance.clone());
+    let substitution: HashMap<_, _> = rule.params.iter().map(|p| p.to_string())
+      .zip(args)
+      .collect();
+    let caller = ::std::mem::replace(&mut self.current_rule, instance.clone());
+    let body = self.copy_expr(rule.expr_idx, &substitution, depth + 1);
+    self.current_rule = caller;
+    self.generated_rules.push(Rule::new(instance.clone(), body));
+    Some(instance)
+  }
+
+  /// The arguments `r1, r2` of the call `r<r1, r2>`, they must be rules, possibly instantiating other parametric rules.
+  fn arguments(&mut self, span: Span, path: &syn::Path) -> Option<Vec<syn::Path>> {
+    let mut args = vec![];
+    if let syn::PathArguments::AngleBracketed(ref generics) = path.segments[0].arguments {
+      for arg in &generics.args {
+        match arg {
+          &syn::GenericArgument::Type(syn::Type::Path(ref ty)) if ty.qself.is_none() && ty.path.segments.len() == 1 => {
+            args.push(ty.path.clone());
+          }
+          _ => {
+            arg.span().unstable().error(
+              "the arguments of a parametric rule must be rules.")
+            .emit();
+            self.valid = false;
+            return None;
+          }
+        }
+      }
+    }
+    if args.is_empty() {
+      span.unstable().error(format!(
+        "`{}` is a parametric rule, it must be called with its rule arguments (e.g. `{}<r>`).",
+        path.segments[0].ident, path.segments[0].ident))
+      .emit();
+      self.valid = false;
+      return None;
+    }
+    Some(args)
+  }
+
+  /// Copies the expression `idx` in the new grammar where the parameters are replaced by the rules of `substitution`, and the calls to parametric rules by calls to their instances.
+  fn copy_expr(&mut self, idx: usize, substitution: &HashMap<String, syn::Path>, depth: usize) -> usize {
+    let expr = match self.exprs[idx].clone() {
+      Let(name, expr, body) => return self.expand_let(name, expr, body, substitution, depth),
+      ExternalNonTerminalSymbol(path) => {
+        let path = substitute(&path, substitution);
+        match self.parametric_rule_of(&path) {
+          Some(rule) => {
+            match self.instantiate(idx, &rule, &path, depth) {
+              Some(instance) => ExternalNonTerminalSymbol(instance.into()),
+              None => ExternalNonTerminalSymbol(path)
+            }
+          }
+          None => ExternalNonTerminalSymbol(path)
+        }
+      }
+      Sequence(children) => Sequence(self.copy_exprs(children, substitution, depth)),
+      Choice(children) => Choice(self.copy_exprs(children, substitution, depth)),
+      LongestChoice(children) => LongestChoice(self.copy_exprs(children, substitution, depth)),
+      ZeroOrMore(child) => ZeroOrMore(self.copy_expr(child, substitution, depth)),
+      OneOrMore(child) => OneOrMore(self.copy_expr(child, substitution, depth)),
+      ZeroOrOne(child) => ZeroOrOne(self.copy_expr(child, substitution, depth)),
+      CommittedOptional(child) => CommittedOptional(self.copy_expr(child, substitution, depth)),
+      RepeatUntilEoi(child) => RepeatUntilEoi(self.copy_expr(child, substitution, depth)),
+      Count(child) => Count(self.copy_expr(child, substitution, depth)),
+      BoundedRepeat(child, min, max) => BoundedRepeat(self.copy_expr(child, substitution, depth), min, max),
+      SeparatedList(child, sep, non_empty, strict) => {
+        let child = self.copy_expr(child, substitution, depth);
+        SeparatedList(child, self.copy_expr(sep, substitution, depth), non_empty, strict)
+      }
+      NotPredicate(child) => NotPredicate(self.copy_expr(child, substitution, depth)),
+      AndPredicate(child) => AndPredicate(self.copy_expr(child, substitution, depth)),
+      SemanticAction(child, boxed, action) => SemanticAction(self.copy_expr(child, substitution, depth), boxed, action),
+      TypeAscription(child, ty) => TypeAscription(self.copy_expr(child, substitution, depth), ty),
+      SpannedExpr(child) => SpannedExpr(self.copy_expr(child, substitution, depth)),
+      RangeExpr(child) => RangeExpr(self.copy_expr(child, substitution, depth)),
+      ByteSpanned(child) => ByteSpanned(self.copy_expr(child, substitution, depth)),
+      Capture(child) => Capture(self.copy_expr(child, substitution, depth)),
+      ErrorLabel(child, label) => ErrorLabel(self.copy_expr(child, substitution, depth), label),
+      Strict(child) => Strict(self.copy_expr(child, substitution, depth)),
+      Recover(child, sync) => {
+        let child = self.copy_expr(child, substitution, depth);
+        Recover(child, self.copy_expr(sync, substitution, depth))
+      }
+      Named(child, name) => Named(self.copy_expr(child, substitution, depth), name),
+      Guard(child, condition) => Guard(self.copy_expr(child, substitution, depth), condition),
+      StrLiteral(lit, case_insensitive) => StrLiteral(lit, case_insensitive),
+      AnySingleChar => AnySingleChar,
+      EndOfInput => EndOfInput,
+      Cut => Cut,
+      CharacterClass(class) => CharacterClass(class),
+      TokenPattern(pattern) => TokenPattern(pattern),
+      NonTerminalSymbol(rule) => NonTerminalSymbol(rule)
+    };
+    let span = self.exprs_info[idx].span();
+    self.grammar.alloc_expr(span, expr)
+  }
+
+  /// `let name = expr in body` is replaced by the copy of `body` where `name` calls the hidden rule defined by `expr`.
+  /// The binding is not visible in `expr`, where `name` still refers to the enclosing scope.
+  fn expand_let(&mut self, name: Ident, expr: usize, body: usize, substitution: &HashMap<String, syn::Path>, depth: usize) -> usize {
+    let rule_name = self.fresh_rule_name(&name);
+    let enclosing_rule = ::std::mem::replace(&mut self.current_rule, rule_name.clone());
+    let expr = self.copy_expr(expr, substitution, depth);
+    self.current_rule = enclosing_rule;
+    let mut rule = Rule::new(rule_name.clone(), expr);
+    rule.hidden = true;
+    self.generated_rules.push(rule);
+    let mut substitution = substitution.clone();
+    substitution.insert(name.to_string(), rule_name.into());
+    self.copy_expr(body, &substitution, depth)
+  }
+
+  /// The name `r_name` of the hidden rule of the binding `name` in the rule `r`.
+  /// A number is appended if this name is already used in the grammar (e.g. `r_name_1`), so it never collides with another rule or function.
+  fn fresh_rule_name(&mut self, name: &Ident) -> Ident {
+    let base = format!("{}_{}", self.current_rule, name);
+    let mut fresh = base.clone();
+    let mut n = 0;
+    while self.used_names.contains(&fresh) || self.instances.contains_key(&fresh) {
+      n += 1;
+      fresh = format!("{}_{}", base, n);
+    }
+    self.used_names.insert(fresh.clone());
+    Ident::new(&fresh, name.span())
+  }
+
+  fn copy_exprs(&mut self, children: Vec<usize>, substitution: &HashMap<String, syn::Path>, depth: usize) -> Vec<usize> {
+    children.into_iter().map(|child| self.copy_expr(child, substitution, depth)).collect()
+  }
+}
+
+/// Replaces the parameters in `path`, including in the arguments of a call to another parametric rule such as `list<elem>`.
+fn substitute(path: &syn::Path, substitution: &HashMap<String, syn::Path>) -> syn::Path {
+  if let Some(ident) = path.get_ident() {
+    if let Some(arg) = substitution.get(&ident.to_string()) {
+      return arg.clone();
+    }
+  }
+  let mut path = path.clone();
+  for segment in &mut path.segments {
+    if let syn::PathArguments::AngleBracketed(ref mut generics) = segment.arguments {
+      for arg in &mut generics.args {
+        if let &mut syn::GenericArgument::Type(syn::Type::Path(ref mut ty)) = arg {
+          ty.path = substitute(&ty.path, substitution);
+        }
+      }
+    }
+  }
+  path
+}
+
+/// `list<pair<key, value>>` is named `list_pair_key_value`.
+fn instance_name(path: &syn::Path) -> String {
+  let segment = &path.segments[0];
+  let mut name = segment.ident.to_string();
+  if let syn::PathArguments::AngleBracketed(ref generics) = segment.arguments {
+    for arg in &generics.args {
+      if let &syn::GenericArgument::Type(syn::Type::Path(ref ty)) = arg {
+        name.push('_');
+        name.push_str(&instance_name(&ty.path));
+      }
+    }
+  }
+  name
+}
@@ -14,8 +14,10 @@
 
 //! Up to this point, the parser automatically created `ExternalNonTerminalSymbol` for all rule's calls.
 //! Here, we convert non terminal symbols that are declared in the current grammar to `NonTerminalSymbol`.
+//! The remaining symbols naming a built-in expression, such as `eoi` or the character classes `digit`, `alpha`, `alnum`, `space`, `hexdigit` and `ident_char`, are converted to this expression; a rule with the same name shadows the built-in expression.
 
 use middle::analysis::ast::*;
+use middle::analysis::duplicate::closest_rule;
 use partial::Partial::*;
 
 pub struct ResolveNonTerminal
@@ -54,6 +56,35 @@ impl Visitor<()> for ResolveNonTerminal
       if contains_key {
         self.grammar.exprs[this] = Expression::NonTerminalSymbol(ident.clone());
       }
+      else if ident == "eoi" {
+        self.grammar.exprs[this] = Expression::EndOfInput;
+      }
+      else if let Some(class) = builtin_class(&ident.to_string()) {
+        self.grammar.exprs[this] = Expression::CharacterClass(class);
+      }
+      else if let Some(rule) = closest_rule(&ident.to_string(), &self.grammar.rules) {
+        self.grammar[this].span().unstable().warning(format!(
+          "`{}` is not a rule of the grammar, it is called as an external parser (`parse_{}` and `recognize_{}`).", ident, ident, ident))
+        .span_note(rule.span().unstable(), format!("did you mean `{}`?", rule.ident()))
+        .emit();
+      }
     }
   }
 }
+
+/// The built-in character classes, they are the same as the explicit classes, e.g. `digit` is `["0-9"]`.
+fn builtin_class(name: &str) -> Option<CharacterClassExpr> {
+  let intervals: &[(char, char)] = match name {
+    "digit" => &[('0', '9')],
+    "alpha" => &[('a', 'z'), ('A', 'Z')],
+    "alnum" => &[('a', 'z'), ('A', 'Z'), ('0', '9')],
+    "space" => &[(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r')],
+    "hexdigit" => &[('0', '9'), ('a', 'f'), ('A', 'F')],
+    "ident_char" => &[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')],
+    _ => return None
+  };
+  let intervals = intervals.iter()
+    .map(|&(lo, hi)| CharacterInterval::new(lo, hi))
+    .collect();
+  Some(CharacterClassExpr::new(intervals, vec![], false))
+}
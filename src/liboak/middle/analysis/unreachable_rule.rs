@@ -279,6 +279,18 @@ impl<'a, 'b, 'c> Visitor<Occurence> for UnreachableRule<'a, 'b, 'c>
     }
   }
 
+  fn visit_repeat_until_eoi(&mut self, _this: usize, _child: usize) -> Occurence{
+    Occurence{
+      choice: vec![]
+    }
+  }
+
+  fn visit_recover(&mut self, _this: usize, _child: usize, _sync: usize) -> Occurence{
+    Occurence{
+      choice: vec![]
+    }
+  }
+
   fn visit_not_predicate(&mut self, _this: usize, _child: usize) -> Occurence{
     Occurence{
       choice: vec![]
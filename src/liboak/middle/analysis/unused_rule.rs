@@ -0,0 +1,164 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Warns about the rules that cannot be reached from the start rules.
+//! The start rules are given with `#![start(r1, r2)]` and the first rule of the grammar is the start rule by default.
+//! With `#![warn(trivial_rules)]`, it also reports the rules only matching a literal or a character class that are called a few times, they could be inlined.
+
+use middle::analysis::ast::*;
+use middle::analysis::diagnostics;
+use middle::analysis::duplicate::suggest_closest_rule;
+use partial::Partial::*;
+use std::collections::{HashSet, HashMap};
+
+/// A trivial rule called more than this number of times is not reported, it is probably worth its name.
+const TRIVIAL_RULE_MAX_CALLS: usize = 3;
+
+pub struct UnusedRule<'a>
+{
+  grammar: &'a AGrammar,
+  reached: HashSet<String>,
+  /// Number of calls to each rule in the rules reached from the start rules.
+  calls: HashMap<String, usize>
+}
+
+impl<'a> UnusedRule<'a>
+{
+  pub fn analyse(grammar: AGrammar) -> Partial<AGrammar> {
+    if UnusedRule::check_unused_rules(&grammar) {
+      Value(grammar)
+    }
+    else {
+      Nothing
+    }
+  }
+
+  fn check_unused_rules(grammar: &'a AGrammar) -> bool {
+    let start_rules = grammar.start_rules();
+    if !UnusedRule::check_start_rules(grammar, &start_rules) {
+      return false;
+    }
+    let mut analyser = UnusedRule {
+      grammar,
+      reached: HashSet::new(),
+      calls: HashMap::new()
+    };
+    for rule in &start_rules {
+      analyser.visit_rule(rule);
+    }
+    // The spacing rule is implicitly called in the sequences.
+    if let Some(ref spacing) = grammar.attributes.spacing_rule {
+      analyser.visit_rule(spacing);
+    }
+    for rule in &grammar.rules {
+      if !analyser.reached.contains(&rule.ident().to_string()) {
+        analyser.warn_unused_rule(rule, &start_rules);
+      }
+    }
+    match grammar.attributes.trivial_rules_level {
+      Some(level) => analyser.report_trivial_rules(&start_rules, level),
+      None => true
+    }
+  }
+
+  /// Reports the rules whose body is a literal or a character class and that are called at most `TRIVIAL_RULE_MAX_CALLS` times.
+  /// The start rules and the rules of the attributes are never reported since they cannot be inlined.
+  /// Returns `false` if a rule is reported with `#![deny(trivial_rules)]`.
+  fn report_trivial_rules(&self, start_rules: &[Ident], level: LintLevel) -> bool {
+    let mut valid = true;
+    for rule in &self.grammar.rules {
+      let id = rule.ident();
+      let calls = self.calls.get(&id.to_string()).cloned().unwrap_or(0);
+      if calls == 0 || calls > TRIVIAL_RULE_MAX_CALLS
+       || rule.hidden
+       || start_rules.contains(&id)
+       || self.grammar.is_spacing_rule(&id)
+       || self.grammar.attributes.keyword_boundary.as_ref().is_some_and(|r| *r == id)
+      {
+        continue;
+      }
+      let body = match self.grammar.expr_by_index(rule.expr_idx) {
+        Expression::StrLiteral(lit, false) => format!("{:?}", lit),
+        Expression::CharacterClass(class) => format!("{}", class),
+        _ => continue
+      };
+      let message = format!(
+        "rule `{}` only matches `{}` and is called {} time(s).\n\
+        Solution: Inline `{}` where `{}` is called.",
+        id, body, calls, body, id);
+      match level {
+        LintLevel::Warn => diagnostics::warning("trivial_rule", Some(&id), rule.span(), message).emit(),
+        LintLevel::Deny => {
+          diagnostics::error("trivial_rule", Some(&id), rule.span(), message).emit();
+          valid = false;
+        }
+      }
+    }
+    valid
+  }
+
+  fn check_start_rules(grammar: &AGrammar, start_rules: &[Ident]) -> bool {
+    let mut valid = true;
+    for start in start_rules {
+      if !grammar.rules.iter().any(|r| r.ident() == *start) {
+        let diagnostic = start.span().unstable().error(format!(
+          "unknown start rule `{}`.", start));
+        suggest_closest_rule(diagnostic, &start.to_string(), &grammar.rules).emit();
+        valid = false;
+      }
+    }
+    valid
+  }
+
+  /// If the name of the unused rule is close to the one of a used rule, it might be a misspelling and we suggest this rule.
+  fn warn_unused_rule(&self, rule: &Rule, start_rules: &[Ident]) {
+    let start_rules: Vec<_> = start_rules.iter()
+      .map(|r| format!("`{}`", r))
+      .collect();
+    let diagnostic = diagnostics::warning("unused_rule", Some(&rule.ident()), rule.span(), format!(
+      "rule `{}` is never used from the start rule(s) {}.\n\
+      Solution: Remove this rule or declare it as a start rule with `#![start(...)]`.",
+      rule.ident(), start_rules.join(", ")));
+    let used_rules: Vec<_> = self.grammar.rules.iter()
+      .filter(|r| self.reached.contains(&r.ident().to_string()))
+      .cloned()
+      .collect();
+    suggest_closest_rule(diagnostic, &rule.ident().to_string(), &used_rules).emit();
+  }
+
+  fn visit_rule(&mut self, rule: &Ident) {
+    if self.reached.insert(rule.to_string()) {
+      let expr_idx = self.grammar.find_rule_by_ident(rule).expr_idx;
+      self.visit_expr(expr_idx);
+    }
+  }
+}
+
+impl<'a> ExprByIndex for UnusedRule<'a>
+{
+  fn expr_by_index(&self, index: usize) -> Expression {
+    self.grammar.expr_by_index(index).clone()
+  }
+}
+
+impl<'a> Visitor<()> for UnusedRule<'a>
+{
+  unit_visitor_impl!(sequence);
+  unit_visitor_impl!(choice);
+
+  fn visit_non_terminal_symbol(&mut self, _this: usize, rule: &Ident) {
+    *self.calls.entry(rule.to_string()).or_insert(0) += 1;
+    self.visit_rule(rule);
+  }
+}
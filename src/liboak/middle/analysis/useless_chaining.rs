@@ -33,6 +33,8 @@
 //! e**         Taken care of by WFA.
 //!
 //! The cases where predicates and repeating operators are mixed are taken care of by WFA.
+//!
+//! The inner expression must be directly nested in the outer one (possibly through a rule call or a type ascription), for example `("x"? "y")?` or `("x"? > f)?` are not unnecessary chaining.
 
 #![macro_use]
 use middle::analysis::ast::*;
@@ -74,7 +76,7 @@ impl <'a> UselessChaining<'a>
 
   fn warn_useless_chaining(&self, span: Span, pattern_detected: &'static str, how_to_rewrite: &'static str) -> bool {
     span.unstable().warning(format!(
-      "unnecessary chaining of predicates of the form `{}`\n\
+      "unnecessary chaining of operators of the form `{}`\n\
        You can rewrite this expression to the equivalent one `{}`.\n\
        ({} ~~~> {})",
       pattern_detected, how_to_rewrite, pattern_detected, how_to_rewrite))
@@ -144,6 +146,18 @@ impl<'a> Visitor<(PredicateOrRepeat, bool)> for InnerPredicateOrRepeat<'a> {
     (PZeroOrMore, false)
   }
 
+  fn visit_bounded_repeat(&mut self, _this: usize, _child: usize, _min: usize, _max: Option<usize>) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_separated_list(&mut self, _this: usize, _child: usize, _sep: usize, _one_or_more: bool) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_repeat_until_eoi(&mut self, _this: usize, _child: usize) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
   fn visit_not_predicate(&mut self, _this: usize, _child: usize) -> (PredicateOrRepeat, bool) {
     (PNot, false)
   }
@@ -154,7 +168,41 @@ impl<'a> Visitor<(PredicateOrRepeat, bool)> for InnerPredicateOrRepeat<'a> {
 
 
   fn visit_sequence(&mut self, _: usize, children: Vec<usize>) -> (PredicateOrRepeat, bool) {
-    self.visit_expr(children[0])
+    if children.len() == 1 {
+      self.visit_expr(children[0])
+    }
+    else {
+      (PNothing, false)
+    }
+  }
+
+  // The following expressions transform the value of their sub-expressions, so they cannot be simplified.
+  fn visit_semantic_action(&mut self, _this: usize, _child: usize, _boxed: bool, _action: syn::Expr) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_spanned_expr(&mut self, _this: usize, _child: usize) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_range_expr(&mut self, _this: usize, _child: usize) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_byte_spanned(&mut self, _this: usize, _child: usize) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_capture(&mut self, _this: usize, _child: usize) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_guard(&mut self, _this: usize, _child: usize, _condition: syn::Expr) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
+  }
+
+  fn visit_recover(&mut self, _this: usize, _child: usize, _sync: usize) -> (PredicateOrRepeat, bool) {
+    (PNothing, false)
   }
 
   fn visit_choice(&mut self, _: usize, children: Vec<usize>) -> (PredicateOrRepeat, bool) {
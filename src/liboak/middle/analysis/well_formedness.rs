@@ -13,8 +13,12 @@
 // limitations under the License.
 
 use middle::analysis::ast::*;
+use middle::analysis::diagnostics;
+use middle::analysis::memoization::*;
+use middle::analysis::left_recursion::*;
 use std::mem::swap;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 /// Well-formedness attributes, it represents the possible behavior of an expression.
 
@@ -39,6 +43,13 @@ impl WFA
   }
 }
 
+impl fmt::Display for WFA {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "can_fail: {}, can_succeed: {}, always_consume: {}, never_consume: {}",
+      self.can_fail, self.can_succeed, self.always_consume, self.never_consume)
+  }
+}
+
 impl Default for WFA {
   fn default() -> Self {
     WFA {
@@ -53,12 +64,19 @@ impl Default for WFA {
 pub struct WellFormedness<'a>
 {
   grammar: &'a AGrammar,
-  recursion_path: Vec<(Ident, bool)>,
+  /// The rules being visited with whether input was consumed before calling them, and the non-terminal expression calling them (`None` for the rule where the visit started).
+  recursion_path: Vec<(Ident, bool, Option<usize>)>,
   consumed_input: bool,
   rules_wfa: HashMap<Ident, WFA>,
   reached_fixpoint: bool,
   well_formed: bool,
-  errors: HashSet<usize> // Whether we already spot an error on this rule (to avoid multi-reporting).
+  recursion_info: RecursionInfo,
+  errors: HashSet<usize>, // Whether we already spot an error on this rule (to avoid multi-reporting).
+  warnings: HashSet<usize>,
+  /// The not-predicates `!e` being visited with their child `e`.
+  not_predicates: Vec<(usize, usize)>,
+  /// The last attributes computed for each expression, used to explain the errors involving sub-expressions.
+  exprs_wfa: HashMap<usize, WFA>
 }
 
 // Start with an empty set of the expression attributes.
@@ -66,17 +84,21 @@ pub struct WellFormedness<'a>
 impl<'a> WellFormedness<'a>
 {
   pub fn analyse(grammar: AGrammar) -> Partial<AGrammar> {
-    if WellFormedness::is_well_formed(&grammar) {
-      Partial::Value(grammar)
+    let (well_formed, recursion_info) = WellFormedness::is_well_formed(&grammar);
+    if well_formed {
+      Partial::Value(memoize_rules(grammar, recursion_info))
     } else {
       Partial::Nothing
     }
   }
 
-  fn is_well_formed(grammar: &'a AGrammar) -> bool {
+  fn is_well_formed(grammar: &'a AGrammar) -> (bool, RecursionInfo) {
     let mut analyser = WellFormedness::new(grammar);
     analyser.visit_rules();
-    analyser.well_formed
+    if grammar.attributes.explain_wfa {
+      analyser.explain_rules();
+    }
+    (analyser.well_formed, analyser.recursion_info)
   }
 
   fn new(grammar: &'a AGrammar) -> Self {
@@ -89,28 +111,45 @@ impl<'a> WellFormedness<'a>
         .collect(),
       reached_fixpoint: false,
       well_formed: true,
-      errors: HashSet::new()
+      recursion_info: RecursionInfo::default(),
+      errors: HashSet::new(),
+      warnings: HashSet::new(),
+      not_predicates: vec![],
+      exprs_wfa: HashMap::new()
     }
   }
 
+  /// A rule called while it is already being visited gives its attributes of the previous iteration, which start optimistic (`WFA::default()`).
+  /// Every rule is visited again until the attributes are stable, so the repetitions are checked against the final attributes, even when the nullability of their body is only known through a cycle of rules (e.g. `z1 = "a" z2 / ""` and `z2 = (&"b" z1)*`).
   fn visit_rules(&mut self) {
     while !self.reached_fixpoint && self.well_formed {
       self.reached_fixpoint = true;
       for rule in self.grammar.rules.iter() {
-        self.visit_rule(&rule.ident());
+        self.visit_rule(&rule.ident(), None);
         if !self.well_formed { break; }
       }
     }
   }
 
-  fn visit_rule(&mut self, rule: &Ident) -> WFA {
+  /// Reports the attributes of each rule computed by `visit_rules`, they are the last ones computed if the grammar is not well-formed.
+  fn explain_rules(&self) {
+    for rule in &self.grammar.rules {
+      let id = rule.ident();
+      id.span().unstable().note(format!(
+        "well-formedness of `{}`: {}.", id, self.rules_wfa[&id]))
+      .emit();
+    }
+  }
+
+  fn visit_rule(&mut self, rule: &Ident, call_site: Option<usize>) -> WFA {
     if self.is_rec(rule) {
+      self.register_cycle(rule);
       if !self.consume_input_since(rule) && !self.consumed_input {
-        self.error_left_recursion(rule);
+        self.left_recursion(rule, call_site);
       }
     }
     else {
-      self.push_rule_in_path(rule);
+      self.push_rule_in_path(rule, call_site);
       let wfa = self.visit_rule_expr(rule);
       self.pop_rule_in_path();
       self.fixpoint_update(wfa, rule);
@@ -123,13 +162,13 @@ impl<'a> WellFormedness<'a>
     self.visit_expr(expr_idx)
   }
 
-  fn push_rule_in_path(&mut self, rule: &Ident) {
-    self.recursion_path.push((rule.clone(), self.consumed_input));
+  fn push_rule_in_path(&mut self, rule: &Ident, call_site: Option<usize>) {
+    self.recursion_path.push((rule.clone(), self.consumed_input, call_site));
     self.consumed_input = false;
   }
 
   fn pop_rule_in_path(&mut self) {
-    let (_, old_consumed_input) = self.recursion_path.pop().unwrap();
+    let (_, old_consumed_input, _) = self.recursion_path.pop().unwrap();
     self.consumed_input = old_consumed_input;
   }
 
@@ -141,19 +180,70 @@ impl<'a> WellFormedness<'a>
   }
 
   fn is_rec(&self, rule: &Ident) -> bool {
-    self.recursion_path.iter().any(|&(ref r,_)| r == rule)
+    self.recursion_path.iter().any(|(r,_,_)| r == rule)
   }
 
-  fn rec_path_from(&self, rule: &Ident) -> Vec<(Ident, bool)> {
+  fn rec_path_from(&self, rule: &Ident) -> Vec<(Ident, bool, Option<usize>)> {
     self.recursion_path.iter().cloned()
       .rev()
-      .take_while(|&(ref r, _)| r != rule)
+      .take_while(|(r,_,_)| r != rule)
+      .collect()
+  }
+
+  /// Each rule of the cycle starting with `rule` together with the non-terminal expression where it calls the next rule of the cycle.
+  /// The last rule calls `rule` in `closing_site`.
+  fn cycle_call_sites(&self, rule: &Ident, closing_site: Option<usize>) -> Vec<(Ident, usize)> {
+    let start = self.recursion_path.iter()
+      .position(|(r,_,_)| r == rule)
+      .expect("`rule` must be in the recursion path.");
+    let cycle = &self.recursion_path[start..];
+    let next_sites = cycle.iter().skip(1)
+      .map(|&(_,_,site)| site)
+      .chain(Some(closing_site));
+    cycle.iter().zip(next_sites)
+      .filter_map(|((r,_,_), site)| site.map(|site| (r.clone(), site)))
       .collect()
   }
 
+  fn register_cycle(&mut self, rule: &Ident) {
+    for r in self.cycle_from(rule) {
+      self.recursion_info.recursive_rules.insert(r);
+    }
+  }
+
+  /// With `#![left_recursion]`, a left-recursive cycle is accepted if it has a base case.
+  /// The first rule detected in a cycle becomes its head, where the seed grows.
+  fn left_recursion(&mut self, rule: &Ident, call_site: Option<usize>) {
+    if self.grammar.attributes.left_recursion
+     && BaseCase::has_base_case(self.grammar, rule)
+    {
+      if self.grammar.attributes.left_recursion_level == Some(LintLevel::Warn) {
+        self.warn_left_recursion(rule);
+      }
+      let cycle = self.cycle_from(rule);
+      let info = &mut self.recursion_info;
+      if !cycle.iter().any(|r| info.left_recursive_heads.contains(r)) {
+        info.left_recursive_heads.insert(rule.clone());
+      }
+      info.left_recursive_cycles.extend(cycle);
+    }
+    else {
+      self.error_left_recursion(rule, call_site);
+    }
+  }
+
+  /// The rules of the cycle starting and ending with `rule`, in the calling order.
+  fn cycle_from(&self, rule: &Ident) -> Vec<Ident> {
+    let mut cycle: Vec<_> = vec![rule.clone()];
+    cycle.extend(self.rec_path_from(rule).into_iter()
+      .map(|(r,_,_)| r)
+      .rev());
+    cycle
+  }
+
   fn consume_input_since(&self, rule: &Ident) -> bool {
     let mut has_consumed = false;
-    for (_, consumed_input) in self.rec_path_from(rule) {
+    for (_, consumed_input, _) in self.rec_path_from(rule) {
       has_consumed |= consumed_input;
     }
     has_consumed
@@ -167,52 +257,147 @@ impl<'a> WellFormedness<'a>
     self.consumed_input = savepoint;
   }
 
-  fn error_left_recursion(&mut self, rule_id: &Ident) {
+  fn error_left_recursion(&mut self, rule_id: &Ident, call_site: Option<usize>) {
     self.well_formed = false;
     let rule = self.grammar.find_rule_by_ident(rule_id);
     if self.register_error(rule.expr_idx) {
-      let mut rec_path: Vec<_> = vec![rule_id.clone()];
-      rec_path.extend(self.rec_path_from(rule_id).into_iter()
-        .map(|(r,_)| r)
-        .rev());
-      rule.span().unstable().error(format!(
-        "Left-recursion is not supported in Oak; the following rule cycle \
-        do not consume any input and would therefore loop forever\n\
-        Detected cycle: {}\n\
-        Solution: Rewrite one of the incriminated rules such that it \
-        consumes at least one atom in the input before calling \
-        the next one. Usually, left-recursion is rewritten with a \
-        repeat operator (`e*` or `e+`).",
-        display_path_cycle(&rec_path))).emit();
+      let rec_path = self.cycle_from(rule_id);
+      let diagnostic = if self.grammar.attributes.left_recursion_level == Some(LintLevel::Deny) {
+        diagnostics::error("left_recursion", Some(rule_id), rule.span(), format!(
+          "Left-recursion is denied by `#![deny(left_recursion)]`; the following rule cycle \
+          do not consume any input\n\
+          Detected cycle: {}\n\
+          Solution: Rewrite one of the incriminated rules such that it \
+          consumes at least one atom in the input before calling \
+          the next one, usually with a repeat operator (`e*` or `e+`).",
+          display_path_cycle(&rec_path)))
+      }
+      else if self.grammar.attributes.left_recursion {
+        diagnostics::error("left_recursion", Some(rule_id), rule.span(), format!(
+          "Left-recursion without base case; the following rule cycle \
+          do not consume any input and `{}` cannot succeed without calling \
+          itself, it would therefore loop forever\n\
+          Detected cycle: {}\n\
+          Solution: Add an alternative to one of the incriminated rules \
+          that does not start with the recursive call, for example \
+          `expr = expr \"+\" term / term`.",
+          rule_id, display_path_cycle(&rec_path)))
+      }
+      else {
+        diagnostics::error("left_recursion", Some(rule_id), rule.span(), format!(
+          "Left-recursion is not supported in Oak; the following rule cycle \
+          do not consume any input and would therefore loop forever\n\
+          Detected cycle: {}\n\
+          Solution: Rewrite one of the incriminated rules such that it \
+          consumes at least one atom in the input before calling \
+          the next one. Usually, left-recursion is rewritten with a \
+          repeat operator (`e*` or `e+`), otherwise it can be enabled \
+          with the grammar attribute `#![left_recursion]`.",
+          display_path_cycle(&rec_path)))
+      };
+      self.cycle_call_sites(rule_id, call_site).into_iter()
+        .fold(diagnostic, |diagnostic, (caller, site)| {
+          let callee = match self.grammar.expr_by_index(site) {
+            Expression::NonTerminalSymbol(callee) => callee,
+            _ => unreachable!("A call site in the recursion path must be a non-terminal.")
+          };
+          diagnostic.span_note(self.grammar[site].span().unstable(), format!(
+            "`{}` calls `{}` here without consuming any input", caller, callee))
+        })
+        .emit();
+    }
+  }
+
+  fn warn_left_recursion(&mut self, rule_id: &Ident) {
+    let rule = self.grammar.find_rule_by_ident(rule_id);
+    if self.warnings.insert(rule.expr_idx) {
+      let rec_path = self.cycle_from(rule_id);
+      diagnostics::warning("left_recursion", Some(rule_id), rule.span(), format!(
+        "Left-recursive rule `{}`, it is compiled with the seed-growing algorithm (`#![warn(left_recursion)]`).\n\
+        Detected cycle: {}",
+        rule_id, display_path_cycle(&rec_path)))
+      .emit();
     }
   }
 
   fn error_never_succeed(&mut self, expr_idx: usize) {
     if self.register_error(expr_idx) {
       self.well_formed = false;
-      self.grammar[expr_idx].span().unstable().error(format!(
+      let diagnostic = diagnostics::error("never_succeed", self.current_rule().as_ref(), self.grammar[expr_idx].span(),
         "Expression will never succeed.\n\
-        Solution: Remove this expression.")).emit();
+        Solution: Remove this expression.");
+      // The not-predicate is reported with its child instead of separately.
+      match self.not_predicates.iter().find(|&&(_, child)| child == expr_idx) {
+        Some(&(predicate, _)) => {
+          diagnostic.span_note(self.grammar[predicate].span().unstable(),
+            "This not-predicate therefore always succeeds and never consumes input, it is probably not what you intended.")
+          .emit();
+        }
+        None => diagnostic.emit()
+      }
     }
   }
 
+  fn note_unreachable_elements(&mut self, elements: &[usize]) {
+    let first = self.grammar[elements[0]].span();
+    let last = self.grammar[elements[elements.len() - 1]].span();
+    let span = first.join(last).unwrap_or(first);
+    diagnostics::note("unreachable_elements", self.current_rule().as_ref(), span,
+      "The elements following an expression that never succeeds are unreachable.").emit();
+  }
+
   fn error_always_succeed_without_consuming(&mut self, expr_idx: usize) {
     if self.register_error(expr_idx) {
       self.well_formed = false;
-      self.grammar[expr_idx].span().unstable().error(format!(
+      diagnostics::error("always_succeed", self.current_rule().as_ref(), self.grammar[expr_idx].span(),
         "Expression will always succeed without consuming any input.\n\
-        Solution: Remove this expression.")).emit();
+        Solution: Remove this expression.").emit();
     }
   }
 
-  fn error_loop_repeat(&mut self, expr_idx: usize) {
+  fn error_loop_repeat(&mut self, expr_idx: usize, child: usize) {
     if self.register_error(expr_idx) {
       self.well_formed = false;
-      self.grammar[expr_idx].span().unstable().error(format!(
-        "Infinite loop detected. A repeat operator (`e*` or `e+`) will \
+      let diagnostic = diagnostics::error("infinite_loop", self.current_rule().as_ref(), self.grammar[expr_idx].span(),
+        "Infinite loop detected. A repeat operator (`e*`, `e+`, `e{n,m}`, `count(e)` or `e % sep`) will \
         never stop because the sub-expression does not consume input.\n\
         Solution: Rewrite the expression such that it consumes at least \
-        one atom in the input or get rid of the repeat operator.")).emit();
+        one atom in the input or get rid of the repeat operator.");
+      match self.nullable_branch(child, &mut HashSet::new()) {
+        Some(branch) => {
+          diagnostic.span_note(self.grammar[branch].span().unstable(),
+            "This branch can succeed without consuming input")
+          .emit();
+        }
+        None => diagnostic.emit()
+      }
+    }
+  }
+
+  /// If `expr` is a choice (possibly behind rule calls or semantic actions), the first branch that can succeed without consuming input.
+  fn nullable_branch(&self, expr: usize, visited_rules: &mut HashSet<Ident>) -> Option<usize> {
+    use ast::Expression::*;
+    match self.grammar.expr_by_index(expr) {
+      Choice(children)
+    | LongestChoice(children) => children.into_iter()
+        .find(|child| self.exprs_wfa.get(child)
+          .is_some_and(|wfa| wfa.can_succeed && !wfa.always_consume)),
+      NonTerminalSymbol(rule) => {
+        if visited_rules.insert(rule.clone()) {
+          self.nullable_branch(self.grammar.expr_index_of_rule(&rule), visited_rules)
+        }
+        else { None }
+      }
+      SemanticAction(child, _, _)
+    | TypeAscription(child, _)
+    | SpannedExpr(child)
+    | RangeExpr(child)
+    | ByteSpanned(child)
+    | Capture(child)
+    | ErrorLabel(child, _)
+    | Strict(child)
+    | Named(child, _) => self.nullable_branch(child, visited_rules),
+      _ => None
     }
   }
 
@@ -220,20 +405,173 @@ impl<'a> WellFormedness<'a>
   {
     if self.register_error(always_succeed_branch) {
       self.well_formed = false;
-      self.grammar[choice].span().unstable().error(format!(
+      diagnostics::error("unreachable_branch", self.current_rule().as_ref(), self.grammar[choice].span(),
         "Unreachable branch in a choice expression. We detected that \
         some branches cannot be reached in this expression.\n\
         Solution: Either remove (or rewrite) this branch or move it \
-        to the end of the choice expression."))
+        to the end of the choice expression.")
       .span_note(self.grammar[always_succeed_branch].span().unstable(), format!(
         "Branch always succeeding"))
       .emit();
     }
   }
 
+  /// An interval such as `z-a` in `["z-a"]` accepts no character, and an interval overlapping another one is redundant, e.g. `a-c` in `["a-ca-z"]`.
+  /// The intervals are sorted, so two overlapping intervals are found next to each other.
+  fn check_intervals(&mut self, class: usize, intervals: &[CharacterInterval]) {
+    if let Some(empty) = intervals.iter().find(|i| i.lo > i.hi) {
+      self.error_empty_interval(class, empty);
+    }
+    else {
+      let mut sorted = intervals.to_vec();
+      sorted.sort_by_key(|i| (i.lo, i.hi));
+      if let Some(overlap) = sorted.windows(2).find(|w| w[1].lo <= w[0].hi) {
+        self.warn_overlapping_intervals(class, &overlap[0], &overlap[1]);
+      }
+    }
+  }
+
+  fn error_empty_interval(&mut self, class: usize, interval: &CharacterInterval) {
+    if self.register_error(class) {
+      self.well_formed = false;
+      diagnostics::error("empty_interval", self.current_rule().as_ref(), self.grammar[class].span(), format!(
+        "The interval `{}-{}` of this character class is empty since its lower bound is greater than its upper bound.\n\
+        Solution: Swap the bounds of the interval (`{}-{}`), or put `-` at the start or the end of the class if it is meant as a character (e.g. `[\"+*/-\"]`).",
+        interval.escape_lo(), interval.escape_hi(), interval.escape_hi(), interval.escape_lo())).emit();
+    }
+  }
+
+  /// `first` is before `second` in the sorted intervals, thus `first` can only be contained in `second` if they start with the same character.
+  /// An exact duplicate, such as `a` in `["aa"]` or `a-z` in `["a-za-z"]`, and an interval fully contained in another one are probably copy-paste mistakes and are reported as such.
+  fn warn_overlapping_intervals(&mut self, class: usize, first: &CharacterInterval, second: &CharacterInterval) {
+    if self.warnings.insert(class) {
+      let message =
+        if first.lo == second.lo && first.hi == second.hi {
+          let what = if first.lo == first.hi { "character" } else { "interval" };
+          format!("The {} `{}` appears more than once in this character class.\n\
+            Solution: Remove the duplicate.", what, first)
+        }
+        else if second.hi <= first.hi || first.lo == second.lo {
+          let (inner, outer) = if second.hi <= first.hi { (second, first) } else { (first, second) };
+          format!("The characters of `{}` are already in the interval `{}` of this character class.\n\
+            Solution: Remove `{}`.", inner, outer, inner)
+        }
+        else {
+          format!("The intervals `{}` and `{}` of this character class overlap.\n\
+            Solution: Remove the redundant characters or merge the intervals.", first, second)
+        };
+      diagnostics::warning("overlapping_intervals", self.current_rule().as_ref(), self.grammar[class].span(), message).emit();
+    }
+  }
+
+  /// The rule being visited, to which the diagnostics on expressions are attached.
+  fn current_rule(&self) -> Option<Ident> {
+    self.recursion_path.last().map(|(rule, _, _)| rule.clone())
+  }
+
   fn register_error(&mut self, expr_idx: usize) -> bool {
     self.errors.insert(expr_idx)
   }
+
+  fn warn_shadowed_branch(&mut self, shadowing_branch: usize, shadowed_branch: usize, prefix: &str) {
+    if self.warnings.insert(shadowed_branch) {
+      diagnostics::warning("shadowed_branch", self.current_rule().as_ref(), self.grammar[shadowed_branch].span(), format!(
+        "Unreachable branch in a choice expression: every input matched by this branch starts with `{}`, \
+        which is already matched by an earlier branch.\n\
+        Solution: Move this branch before the shadowing branch.", prefix))
+      .span_note(self.grammar[shadowing_branch].span().unstable(),
+        "Shadowing branch")
+      .emit();
+    }
+  }
+
+  /// Heuristic detection of the branches that can never succeed because an earlier branch matches a prefix of all their inputs.
+  /// We only consider earlier branches matching exactly a string literal, e.g. `"for" / "foreach"`.
+  /// Single character literals are skipped since they are already reported by the FIRST set analysis (`first_set.rs`).
+  fn check_shadowed_branches(&mut self, branches: &[usize]) {
+    for (i, &branch) in branches.iter().enumerate() {
+      if let Some(prefix) = self.literal_prefix(branch) {
+        let shadowing = branches[..i].iter().cloned()
+          .find(|&earlier| {
+            match self.exact_literal(earlier) {
+              Some(literal) => literal.0.chars().count() > 1 && is_literal_prefix(&literal, &prefix),
+              None => false
+            }
+          });
+        if let Some(shadowing) = shadowing {
+          self.warn_shadowed_branch(shadowing, branch, &prefix.0);
+        }
+      }
+    }
+  }
+
+  /// The string literal matched by `expr` if it matches nothing else, with the boolean indicating if it is case-insensitive.
+  fn exact_literal(&self, expr: usize) -> Option<(String, bool)> {
+    use ast::Expression::*;
+    match self.grammar.expr_by_index(expr) {
+      StrLiteral(lit, case_insensitive) => Some((lit, case_insensitive)),
+      SemanticAction(child, _, _)
+    | TypeAscription(child, _)
+    | SpannedExpr(child)
+    | RangeExpr(child)
+    | ByteSpanned(child)
+    | Capture(child)
+    | ErrorLabel(child, _)
+    | Strict(child)
+    | Named(child, _) => self.exact_literal(child),
+      _ => None
+    }
+  }
+
+  /// A string literal starting every input matched by `expr`.
+  fn literal_prefix(&self, expr: usize) -> Option<(String, bool)> {
+    use ast::Expression::*;
+    match self.grammar.expr_by_index(expr) {
+      StrLiteral(lit, case_insensitive) => Some((lit, case_insensitive)),
+      SemanticAction(child, _, _)
+    | TypeAscription(child, _)
+    | SpannedExpr(child)
+    | RangeExpr(child)
+    | ByteSpanned(child)
+    | Capture(child)
+    | ErrorLabel(child, _)
+    | Strict(child)
+    | Named(child, _)
+    | OneOrMore(child)
+    | SeparatedList(child, _, true, _) => self.literal_prefix(child),
+      BoundedRepeat(child, min, _) if min > 0 => self.literal_prefix(child),
+      Sequence(children) => {
+        let (mut prefix, case_insensitive) = self.literal_prefix(children[0])?;
+        if self.exact_literal(children[0]).is_some() {
+          // The following literals with the same case sensitivity extend the prefix.
+          for &child in &children[1..] {
+            match self.exact_literal(child) {
+              Some((lit, ci)) if ci == case_insensitive => prefix.push_str(&lit),
+              _ => break
+            }
+          }
+        }
+        Some((prefix, case_insensitive))
+      }
+      _ => None
+    }
+  }
+}
+
+/// True if the inputs starting with `prefix` always start with `literal` too.
+/// A case-sensitive literal cannot be the prefix of a case-insensitive one, e.g. `"for"` does not match all the inputs of `"FOREACH"i`.
+fn is_literal_prefix(literal: &(String, bool), prefix: &(String, bool)) -> bool {
+  let &(ref lit, lit_ci) = literal;
+  let &(ref pre, pre_ci) = prefix;
+  if lit.is_empty() || pre.len() < lit.len() || !pre.is_char_boundary(lit.len()) {
+    false
+  }
+  else if lit_ci {
+    pre[..lit.len()].eq_ignore_ascii_case(lit)
+  }
+  else {
+    !pre_ci && pre.starts_with(lit.as_str())
+  }
 }
 
 impl<'a> ExprByIndex for WellFormedness<'a>
@@ -247,6 +585,7 @@ impl<'a> Visitor<WFA> for WellFormedness<'a>
 {
   fn visit_expr(&mut self, this: usize) -> WFA {
     let mut wfa = walk_expr(self, this);
+    self.exprs_wfa.insert(this, wfa);
     assert!(wfa.can_fail || wfa.can_succeed,
       "Expression must either fails or succeeds.");
     assert!(!wfa.always_consume || !wfa.never_consume,
@@ -262,25 +601,51 @@ impl<'a> Visitor<WFA> for WellFormedness<'a>
     wfa
   }
 
-  fn visit_str_literal(&mut self, _this: usize, literal: String) -> WFA {
+  fn visit_str_literal(&mut self, _this: usize, literal: String, _case_insensitive: bool) -> WFA {
     let mut wfa = WFA::default();
     if literal.is_empty() {
       wfa.can_fail = false;
       wfa.always_consume = false;
       // NOTE: the empty string is special, and it can be desirable that it always succeeds without consuming, for some patterns in combination with choice.
-      // Therefore, we do not set `never_consume = true`.
+      // Therefore, we do not set `never_consume = true`, so `("a" / "")?` is accepted although the choice can succeed without consuming.
+      // It does not hide a non-consuming loop: a repetition only accepts a body that always consumes input (see `visit_repeat`), which `""` never does, so `("" e)*` is rejected unless `e` always consumes.
     }
     wfa
   }
 
-  fn visit_non_terminal_symbol(&mut self, _this: usize, rule: &Ident) -> WFA {
-    self.visit_rule(rule)
+  // `eoi` behaves like `!.`.
+  fn visit_end_of_input(&mut self, _this: usize) -> WFA {
+    WFA {
+      can_fail: true,
+      can_succeed: true,
+      always_consume: false,
+      never_consume: true
+    }
+  }
+
+  // `~` behaves like the empty string.
+  fn visit_cut(&mut self, _this: usize) -> WFA {
+    WFA {
+      can_fail: false,
+      can_succeed: true,
+      always_consume: false,
+      never_consume: false
+    }
+  }
+
+  fn visit_character_class(&mut self, this: usize, char_class: CharacterClassExpr) -> WFA {
+    self.check_intervals(this, &char_class.intervals);
+    WFA::default()
+  }
+
+  fn visit_non_terminal_symbol(&mut self, this: usize, rule: &Ident) -> WFA {
+    self.visit_rule(rule, Some(this))
   }
 
   fn visit_repeat(&mut self, this: usize, child: usize) -> WFA {
     let child_wfa = self.visit_expr(child);
     if child_wfa.can_succeed && !child_wfa.always_consume {
-      self.error_loop_repeat(this);
+      self.error_loop_repeat(this, child);
       WFA::default()
     }
     else {
@@ -293,11 +658,63 @@ impl<'a> Visitor<WFA> for WellFormedness<'a>
     WFA::always_succeed(child_wfa.never_consume)
   }
 
+  // `repeat_until_eoi(e)` succeeds without consuming input only at the end of input, and fails if `e` fails before.
+  fn visit_repeat_until_eoi(&mut self, this: usize, child: usize) -> WFA {
+    let child_wfa = self.visit_repeat(this, child);
+    WFA {
+      can_fail: true,
+      can_succeed: true,
+      always_consume: false,
+      never_consume: child_wfa.never_consume
+    }
+  }
+
+  // `e{0,m}` behaves like `e?` whereas `e{n,m}` with `n > 0` behaves like `e+`.
+  fn visit_bounded_repeat(&mut self, this: usize, child: usize, min: usize, _max: Option<usize>) -> WFA {
+    let child_wfa = self.visit_repeat(this, child);
+    if min == 0 {
+      WFA::always_succeed(child_wfa.never_consume)
+    }
+    else {
+      child_wfa
+    }
+  }
+
+  // `e % sep` behaves like `e*` and `e %% sep` like `e+`, however the loop body is `sep e`, so it stops if either `sep` or `e` consumes input.
+  fn visit_separated_list(&mut self, this: usize, child: usize, sep: usize, one_or_more: bool) -> WFA {
+    let savepoint = self.save();
+    let child_wfa = self.visit_expr(child);
+    if child_wfa.always_consume {
+      self.consumed_input = true;
+    }
+    let sep_wfa = self.visit_expr(sep);
+    self.restore(savepoint);
+    let never_consume = child_wfa.never_consume && sep_wfa.never_consume;
+    if child_wfa.can_succeed && sep_wfa.can_succeed
+     && !child_wfa.always_consume && !sep_wfa.always_consume
+    {
+      self.error_loop_repeat(this, child);
+      WFA::default()
+    }
+    else if one_or_more {
+      WFA { never_consume, .. child_wfa }
+    }
+    else {
+      WFA::always_succeed(never_consume)
+    }
+  }
+
   fn visit_optional(&mut self, _this: usize, child: usize) -> WFA {
     let child_wfa = self.visit_expr(child);
     WFA::always_succeed(child_wfa.never_consume)
   }
 
+  // `committed(e)` can only fail if `e` fails after consuming input.
+  fn visit_committed_optional(&mut self, _this: usize, child: usize) -> WFA {
+    let child_wfa = self.visit_expr(child);
+    WFA { can_fail: child_wfa.can_fail && !child_wfa.never_consume, .. WFA::always_succeed(child_wfa.never_consume) }
+  }
+
   fn visit_syntactic_predicate(&mut self, _this: usize, child: usize) -> WFA {
     let child_wfa = self.visit_expr(child);
     let mut wfa = child_wfa;
@@ -306,8 +723,11 @@ impl<'a> Visitor<WFA> for WellFormedness<'a>
     wfa
   }
 
+  // `!e` fails where `e` succeeds and conversely, so `!e` can fail as soon as `e` can succeed, e.g. `!"if"` can fail even though `"if"` can also fail.
   fn visit_not_predicate(&mut self, this: usize, child: usize) -> WFA {
+    self.not_predicates.push((this, child));
     let mut wfa = self.visit_syntactic_predicate(this, child);
+    self.not_predicates.pop();
     swap(&mut wfa.can_succeed, &mut wfa.can_fail);
     wfa
   }
@@ -333,9 +753,50 @@ impl<'a> Visitor<WFA> for WellFormedness<'a>
         return wfa;
       }
     }
+    self.check_shadowed_branches(&children);
+    wfa
+  }
+
+  // All the branches are tried, so none is unreachable: it fails only if all branches fail, and consumes like their union.
+  fn visit_longest_choice(&mut self, _this: usize, children: Vec<usize>) -> WFA {
+    let mut wfa = WFA {
+      can_fail: true,
+      can_succeed: false,
+      always_consume: true,
+      never_consume: true
+    };
+    for child in children {
+      let savepoint = self.save();
+      let child_wfa = self.visit_expr(child);
+      self.restore(savepoint);
+      wfa.can_fail &= child_wfa.can_fail;
+      wfa.can_succeed |= child_wfa.can_succeed;
+      wfa.always_consume &= child_wfa.always_consume;
+      wfa.never_consume &= child_wfa.never_consume;
+    }
     wfa
   }
 
+  // When `e` fails, `recover(e, sync)` succeeds only if it skipped some input, and fails otherwise.
+  fn visit_recover(&mut self, _this: usize, child: usize, sync: usize) -> WFA {
+    let savepoint = self.save();
+    self.visit_expr(sync);
+    self.restore(savepoint);
+    let child_wfa = self.visit_expr(child);
+    WFA {
+      can_fail: true,
+      can_succeed: true,
+      always_consume: child_wfa.always_consume,
+      never_consume: false
+    }
+  }
+
+  // The condition of `e && { condition }` can reject the value of `e`.
+  fn visit_guard(&mut self, _this: usize, child: usize, _condition: syn::Expr) -> WFA {
+    let child_wfa = self.visit_expr(child);
+    WFA { can_fail: true, .. child_wfa }
+  }
+
   fn visit_sequence(&mut self, _this: usize, children: Vec<usize>) -> WFA {
     let savepoint = self.save();
     let mut wfa = WFA {
@@ -344,7 +805,9 @@ impl<'a> Visitor<WFA> for WellFormedness<'a>
       always_consume: false,
       never_consume: true
     };
-    for child in children {
+    // The sequence can fail as soon as one of its children can fail, including a syntactic predicate guarding the rest of the sequence such as `!"if" .*`.
+    // Therefore, a branch guarded by a predicate that can fail never makes the next branches of a choice unreachable.
+    for (i, &child) in children.iter().enumerate() {
       let child_wfa = self.visit_expr(child);
       wfa.can_fail |= child_wfa.can_fail;
       wfa.can_succeed &= child_wfa.can_succeed;
@@ -353,6 +816,11 @@ impl<'a> Visitor<WFA> for WellFormedness<'a>
       if child_wfa.always_consume {
         self.consumed_input = true;
       }
+      // `visit_expr` reported that the child never succeeds (its recorded WFA is the one before error-recovery), the elements following it are not analysed.
+      if !self.exprs_wfa[&child].can_succeed && i + 1 < children.len() {
+        self.note_unreachable_elements(&children[i+1..]);
+        break;
+      }
     }
     self.restore(savepoint);
     wfa
@@ -0,0 +1,126 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! With the feature `dump_ast`, the attribute `#![dump_ast = "path"]` writes the typed grammar in JSON to the file `path`, for tools such as grammar visualizers.
+//! The expressions are referred to by their indices, and every expression has a span (line and column, starting at 1 and 0) to relate it to the grammar source.
+
+use middle::typing::ast::*;
+use middle::typing::ast::Type::*;
+use serde_json::{Value, json};
+use quote::quote;
+use std::fs;
+
+pub fn dump_ast(grammar: TGrammar) -> Partial<TGrammar> {
+  let path = match grammar.attributes.dump_ast {
+    Some(ref path) => path.clone(),
+    None => return Partial::Value(grammar)
+  };
+  let json = grammar_json(&grammar);
+  let content = serde_json::to_string_pretty(&json).expect("serializing a JSON value");
+  match fs::write(crate_relative_path(&path), content) {
+    Ok(()) => Partial::Value(grammar),
+    Err(err) => {
+      grammar.start_span.unstable().error(format!(
+        "cannot write the grammar to `{}` (`#![dump_ast]`): {}.", path, err))
+      .emit();
+      Partial::Nothing
+    }
+  }
+}
+
+fn grammar_json(grammar: &TGrammar) -> Value {
+  let rules: Vec<Value> = grammar.rules.iter()
+    .map(|rule| json!({
+      "name": rule.ident().to_string(),
+      "expr": rule.expr_idx,
+      "type": type_name(grammar, rule.expr_idx),
+      "recognizer_only": rule.recognizer_only
+    }))
+    .collect();
+  // The last two expressions are not part of the grammar, they only hold the bytes range and span types (see `IGrammar::alloc_span_ty_expr`).
+  let exprs: Vec<Value> = (0..grammar.exprs.len() - 2)
+    .map(|idx| expr_json(grammar, idx))
+    .collect();
+  json!({
+    "rules": rules,
+    "exprs": exprs
+  })
+}
+
+fn expr_json(grammar: &TGrammar, idx: usize) -> Value {
+  use middle::typing::ast::Expression::*;
+  let (kind, mut fields) = match grammar.expr_by_index(idx) {
+    StrLiteral(lit, case_insensitive) => ("StrLiteral", json!({"literal": lit, "case_insensitive": case_insensitive})),
+    AnySingleChar => ("AnySingleChar", json!({})),
+    CharacterClass(class) => ("CharacterClass", json!({"class": format!("{}", class)})),
+    TokenPattern(pattern) => ("TokenPattern", json!({"pattern": format!("{}", quote!(#pattern))})),
+    NonTerminalSymbol(rule) => ("NonTerminalSymbol", json!({"rule": rule.to_string()})),
+    ExternalNonTerminalSymbol(path) => ("ExternalNonTerminalSymbol", json!({"path": format!("{}", quote!(#path))})),
+    Sequence(children) => ("Sequence", json!({"children": children})),
+    Choice(children) => ("Choice", json!({"children": children})),
+    LongestChoice(children) => ("LongestChoice", json!({"children": children})),
+    ZeroOrMore(child) => ("ZeroOrMore", json!({"child": child})),
+    OneOrMore(child) => ("OneOrMore", json!({"child": child})),
+    ZeroOrOne(child) => ("ZeroOrOne", json!({"child": child})),
+    CommittedOptional(child) => ("CommittedOptional", json!({"child": child})),
+    BoundedRepeat(child, min, max) => ("BoundedRepeat", json!({"child": child, "min": min, "max": max})),
+    SeparatedList(child, sep, one_or_more, strict) => ("SeparatedList", json!({"child": child, "separator": sep, "one_or_more": one_or_more, "strict": strict})),
+    NotPredicate(child) => ("NotPredicate", json!({"child": child})),
+    AndPredicate(child) => ("AndPredicate", json!({"child": child})),
+    SemanticAction(child, boxed, action) => ("SemanticAction", json!({"child": child, "boxed": boxed, "action": format!("{}", quote!(#action))})),
+    TypeAscription(child, _) => ("TypeAscription", json!({"child": child})),
+    SpannedExpr(child) => ("SpannedExpr", json!({"child": child})),
+    RangeExpr(child) => ("RangeExpr", json!({"child": child})),
+    ByteSpanned(child) => ("ByteSpanned", json!({"child": child})),
+    Capture(child) => ("Capture", json!({"child": child})),
+    ErrorLabel(child, label) => ("ErrorLabel", json!({"child": child, "label": label})),
+    Strict(child) => ("Strict", json!({"child": child})),
+    RepeatUntilEoi(child) => ("RepeatUntilEoi", json!({"child": child})),
+    Count(child) => ("Count", json!({"child": child})),
+    Recover(child, sync) => ("Recover", json!({"child": child, "sync": sync})),
+    Named(child, name) => ("Named", json!({"child": child, "name": name.to_string()})),
+    Guard(child, condition) => ("Guard", json!({"child": child, "condition": format!("{}", quote!(#condition))})),
+    Let(name, expr, body) => ("Let", json!({"name": name.to_string(), "expr": expr, "body": body})),
+    EndOfInput => ("EndOfInput", json!({})),
+    Cut => ("Cut", json!({}))
+  };
+  let span = grammar[idx].span().unstable();
+  let (start, end) = (span.start(), span.end());
+  let object = fields.as_object_mut().expect("JSON object");
+  object.insert(format!("index"), json!(idx));
+  object.insert(format!("kind"), json!(kind));
+  object.insert(format!("type"), json!(type_name(grammar, idx)));
+  object.insert(format!("span"), json!({
+    "start": [start.line(), start.column()],
+    "end": [end.line(), end.column()]
+  }));
+  fields
+}
+
+fn type_name(grammar: &TGrammar, idx: usize) -> String {
+  match grammar[idx].ty.clone() {
+    Unit => format!("()"),
+    Atom => {
+      let ty = grammar.atom_type();
+      format!("{}", quote!(#ty))
+    }
+    Optional(child) => format!("Option<{}>", type_name(grammar, child)),
+    List(child) => format!("Vec<{}>", type_name(grammar, child)),
+    Tuple(children) => {
+      let tys: Vec<_> = children.into_iter().map(|c| type_name(grammar, c)).collect();
+      format!("({})", tys.join(", "))
+    }
+    Rust(ty) => format!("{}", quote!(#ty))
+  }
+}
@@ -17,24 +17,56 @@
 //! The `analysis` module performs some verifications on the grammar description and the `typing` module gives a type to each rule and expression.
 
 use middle::typing::ast::*;
-use middle::analysis::ast::AGrammar;
+use middle::analysis::ast::{AGrammar, Expression};
+use middle::analysis::diagnostics::{clear_diagnostics, diagnostics_json_path};
 
 pub use front::ast::FGrammar;
 use partial::*;
 
 pub mod analysis;
 pub mod typing;
+#[cfg(feature = "dump_ast")]
+mod dump;
 
 pub fn typecheck(fgrammar: FGrammar) -> TGrammar {
-  Partial::Value(fgrammar)
-    .and_then(|grammar| at_least_one_rule_declared(grammar))
-    .and_then(|grammar| analysis::analyse(grammar))
+  let diagnostics_json = diagnostics_json_path(&fgrammar.attributes);
+  let start_span = fgrammar.start_span;
+  clear_diagnostics();
+  let agrammar = Partial::Value(fgrammar)
+    .and_then(at_least_one_rule_declared)
+    .and_then(analysis::analyse);
+  // The diagnostics are written even if the analysis failed.
+  if let Some(path) = diagnostics_json {
+    write_diagnostics_json(&path, start_span);
+  }
+  agrammar
     .ensure("aborting due to previous error (analysis phase).")
-    .and_then(|grammar| extract_stream_type(grammar))
-    .and_then(|grammar| typing::type_inference(grammar))
+    .and_then(extract_stream_type)
+    .and_then(classes_on_stream)
+    .and_then(characters_on_atoms)
+    .and_then(typing::type_inference)
+    .and_then(dump_ast)
     .expect("aborting due to previous error (typing phase).")
 }
 
+#[cfg(feature = "dump_ast")]
+fn dump_ast(grammar: TGrammar) -> Partial<TGrammar> {
+  dump::dump_ast(grammar)
+}
+
+#[cfg(not(feature = "dump_ast"))]
+fn dump_ast(grammar: TGrammar) -> Partial<TGrammar> {
+  Partial::Value(grammar)
+}
+
+#[cfg(feature = "diagnostics_json")]
+fn write_diagnostics_json(path: &str, start_span: Span) {
+  analysis::diagnostics::write_diagnostics_json(path, start_span)
+}
+
+#[cfg(not(feature = "diagnostics_json"))]
+fn write_diagnostics_json(_path: &str, _start_span: Span) {}
+
 fn at_least_one_rule_declared(fgrammar: FGrammar) -> Partial<FGrammar> {
   if fgrammar.rules.len() == 0 {
     fgrammar.start_span.unstable()
@@ -75,3 +107,53 @@ fn extract_stream_type(mut grammar: AGrammar)
   }
   Partial::Value(grammar)
 }
+
+/// The Unicode categories `\p{L}` are not meaningful for the bytes of a `ByteStream`, and the classes of bytes `[0x00-0x1F]` are only meaningful for them.
+fn classes_on_stream(grammar: AGrammar) -> Partial<AGrammar> {
+  let byte_stream = grammar.is_byte_stream();
+  let mut valid = true;
+  for (expr, info) in grammar.exprs.iter().zip(grammar.exprs_info.iter()) {
+    match expr {
+      Expression::CharacterClass(class) if byte_stream && !class.categories.is_empty() => {
+        info.span.unstable()
+          .error("Unicode categories (e.g. `\\\\p{L}`) can only be used when parsing characters, not with `ByteStream`.")
+          .emit();
+        valid = false;
+      }
+      Expression::CharacterClass(class) if !byte_stream && class.bytes => {
+        info.span.unstable()
+          .error("Classes of bytes (e.g. `[0x00-0x1F]`) can only be used with `ByteStream`, use a character class such as `[\"\\u{0}-\\u{1f}\"]` to match characters.")
+          .emit();
+        valid = false;
+      }
+      _ => ()
+    }
+  }
+  if valid { Partial::Value(grammar) } else { Partial::Nothing }
+}
+
+/// When the grammar parses atoms that are not characters (`type Atom = Token;`), the character classes and the case-insensitive literals are meaningless.
+fn characters_on_atoms(grammar: AGrammar) -> Partial<AGrammar> {
+  if grammar.atom_alias().is_none() {
+    return Partial::Value(grammar);
+  }
+  let mut valid = true;
+  for (expr, info) in grammar.exprs.iter().zip(grammar.exprs_info.iter()) {
+    match *expr {
+      Expression::CharacterClass(_) => {
+        info.span.unstable()
+          .error("Character classes can only be used when parsing characters, use `token(pattern)` to match the atoms of type `Atom`.")
+          .emit();
+        valid = false;
+      }
+      Expression::StrLiteral(_, true) => {
+        info.span.unstable()
+          .error("Case-insensitive literals can only be used when parsing characters, not with the atoms of type `Atom`.")
+          .emit();
+        valid = false;
+      }
+      _ => ()
+    }
+  }
+  if valid { Partial::Value(grammar) } else { Partial::Nothing }
+}
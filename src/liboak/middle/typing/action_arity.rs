@@ -0,0 +1,141 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The values produced by the expression of a semantic action are passed as arguments to the function `e > f`, or to the closure `e > |x| x * 2`.
+//! If the function of the grammar or the closure does not take as many parameters as there are values, we report the mismatch here instead of letting rustc complain in the generated code.
+//! The boxed expressions `box e`, rewritten as `e > Box::new`, must also produce a single value.
+//! We also check that a named expression `name@e` does not produce several values, since a single value is passed to the parameter `name`.
+//! A named expression without value is accepted because it is typed as unit when its value is not used, e.g. in a recognizer.
+//! When the values are named, a parameter that is not named like any value can only take an unnamed value, otherwise a name of the expression or of the parameter is probably misspelled.
+
+use middle::typing::ast::*;
+
+pub fn check_action_arity(grammar: TGrammar) -> Partial<TGrammar> {
+  let mut valid = true;
+  for (this, expr) in grammar.exprs.iter().enumerate() {
+    match *expr {
+      SemanticAction(child, _, ref action) => valid &= check_arity(&grammar, this, child, action),
+      Named(child, ref name) => valid &= check_named_value(&grammar, this, child, name),
+      _ => ()
+    }
+  }
+  if valid { Partial::Value(grammar) }
+  else { Partial::Nothing }
+}
+
+fn check_arity(grammar: &TGrammar, this: usize, child: usize, action: &syn::Expr) -> bool {
+  let fun = match action {
+    syn::Expr::Path(expr_path) if is_box_new(&expr_path.path) => return check_boxed_value(grammar, this, child),
+    syn::Expr::Path(expr_path) => {
+      match expr_path.path.get_ident().and_then(|ident| grammar.rust_functions.get(ident)) {
+        Some(fun) => fun,
+        None => return true
+      }
+    }
+    syn::Expr::Closure(closure) => return check_closure_arity(grammar, this, child, closure),
+    _ => return true
+  };
+  let num_values = grammar[child].type_cardinality();
+  let num_params = fun.sig.inputs.len();
+  if num_values != num_params {
+    grammar[this].span().unstable().error(format!(
+      "the semantic action `{}` takes {} parameter(s) but its expression produces {} value(s).",
+      fun.sig.ident, num_params, num_values))
+    .span_note(grammar[child].span().unstable(),
+      "Each expression of a sequence produces one value, except the ones of type `()` such as string literals or the expressions annotated with `:()`.")
+    .span_note(fun.sig.span().unstable(), format!(
+      "Function `{}` declared here", fun.sig.ident))
+    .emit();
+    false
+  }
+  else { check_argument_names(grammar, this, child, action, fun) }
+}
+
+/// A closure takes the values of its expression in order, e.g. `number "+" number > |a, b| a + b`.
+fn check_closure_arity(grammar: &TGrammar, this: usize, child: usize, closure: &syn::ExprClosure) -> bool {
+  let num_values = grammar[child].type_cardinality();
+  let num_params = closure.inputs.len();
+  if num_values != num_params {
+    grammar[this].span().unstable().error(format!(
+      "the closure of this semantic action takes {} parameter(s) but its expression produces {} value(s).",
+      num_params, num_values))
+    .span_note(grammar[child].span().unstable(),
+      "Each expression of a sequence produces one value, except the ones of type `()` such as string literals or the expressions annotated with `:()`.")
+    .emit();
+    false
+  }
+  else { true }
+}
+
+fn is_box_new(path: &syn::Path) -> bool {
+  let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+  segments == ["Box", "new"]
+}
+
+/// `box e` is rewritten as `e > Box::new` which takes a single value, e.g. `box ("a" "b")` or `box (e1 e2)` cannot be boxed.
+fn check_boxed_value(grammar: &TGrammar, this: usize, child: usize) -> bool {
+  let num_values = grammar[child].type_cardinality();
+  if num_values != 1 {
+    grammar[this].span().unstable().error(format!(
+      "the boxed expression `box e` must produce a single value but `e` produces {} value(s).",
+      num_values))
+    .span_note(grammar[child].span().unstable(),
+      "Use a semantic action or a type ascription to produce a single value, e.g. `box (e > f)`.")
+    .emit();
+    false
+  }
+  else { true }
+}
+
+/// `year@number "-" month@number "-" dya@number > make_date` passes the value named `dya` to the parameter `day` of `make_date` since it is the only value left, which is reported.
+fn check_argument_names(grammar: &TGrammar, this: usize, child: usize, action: &syn::Expr, fun: &syn::ItemFn) -> bool {
+  let names = grammar.value_names(child, false);
+  let args = grammar.action_arguments(child, action);
+  let mut valid = true;
+  for (param, arg) in fun.sig.inputs.iter().zip(args) {
+    match (param_name(param), &names[arg]) {
+      (Some(ref param_id), Some(value_name)) if param_id != value_name => {
+        let named: Vec<String> = names.iter()
+          .filter_map(|name| name.as_ref().map(|name| format!("`{}`", name)))
+          .collect();
+        grammar[this].span().unstable().error(format!(
+          "no value is named `{}` for the parameter `{}` of the semantic action `{}`, it would take the value named `{}` instead.",
+          param_id, param_id, fun.sig.ident, value_name))
+        .span_note(grammar[child].span().unstable(), format!(
+          "The named values of this expression are {}, the unnamed values are passed in order to the other parameters.",
+          named.join(", ")))
+        .span_note(param.span().unstable(), format!(
+          "Parameter `{}` declared here", param_id))
+        .emit();
+        valid = false;
+      }
+      _ => ()
+    }
+  }
+  valid
+}
+
+fn check_named_value(grammar: &TGrammar, this: usize, child: usize, name: &Ident) -> bool {
+  let num_values = grammar[child].type_cardinality();
+  if num_values > 1 {
+    grammar[this].span().unstable().error(format!(
+      "the named expression `{}@e` must produce a single value but `e` produces {} values.",
+      name, num_values))
+    .span_note(grammar[child].span().unstable(),
+      "Use a semantic action or a type ascription to produce a single value.")
+    .emit();
+    false
+  }
+  else { true }
+}
@@ -41,15 +41,27 @@ impl IGrammar
       stream_alias: agrammar.stream_alias,
       rust_functions: agrammar.rust_functions,
       rust_items: agrammar.rust_items,
-      attributes: agrammar.attributes
+      attributes: agrammar.attributes,
+      first_sets: agrammar.first_sets
     };
     grammar.exprs_info = exprs_info.into_iter()
       .map(|e| ExprIType::infer(e.span))
       .collect();
+    grammar.type_recognizer_rules();
+    grammar.alloc_byte_range_ty_expr();
     grammar.alloc_span_ty_expr();
     grammar
   }
 
+  /// The rules that only have a recognizer do not produce values, so their expressions are typed with the unit type.
+  fn type_recognizer_rules(&mut self) {
+    for rule in self.rules.clone() {
+      if rule.recognizer_only {
+        self.exprs_info[rule.expr_idx].ty = Regular(Unit);
+      }
+    }
+  }
+
   fn error_unit_action_type(span: Span) {
     span.unstable().error("a semantic action with type unit (`()`) will never be called as a semantic action is supposed to produce data.\n\
       If this is intentional because the function has side-effects, return a custom type such as `MyUnit` with `type MyUnit = ()`.\n")
@@ -57,36 +69,51 @@ impl IGrammar
   }
 
   /// If the semantic action is a single identifier, and that we can retrieve a Rust function with the same name, it resolves to the return type of that function.
+  /// Similarly, a closure declaring its return type, such as `|x| -> u32 { x * 2 }`, resolves to this type.
+  /// If this function returns `Result<T, E>`, the action can fail and it resolves to `T` (see `fallible_action_type`).
   /// We try to convert Rust unit type into Oak unit type for better typechecking.
   /// Otherwise, `External` is returned.
   /// If we detect a semantic action with no type or type `()`, we generate an error because this semantic action will never be called.
   pub fn resolve_action_type(&self, span: Span, boxed: bool, action: syn::Expr) -> IType
   {
-    match action {
-      syn::Expr::Path(expr_path) => {
-        if let Some(ident) = expr_path.path.get_ident() {
-          if let Some(fun) = self.rust_functions.get(ident) {
-            match &fun.sig.output {
-              &syn::ReturnType::Default => {
-                Self::error_unit_action_type(span);
-                Regular(Unit)
-              },
-              &syn::ReturnType::Type(_, ref ty) => {
-                let unit_ty = syn::parse_str("()").expect("unit type");
-                let ty = (**ty).clone();
-                if ty == unit_ty {
-                  Self::error_unit_action_type(span)
-                }
-                let ty: syn::Type =
-                  if boxed { parse_quote!(Box<#ty>) }
-                  else { ty };
-                Regular(Rust(ty))
-              }
-            }
-          } else { External }
-        } else { External }
+    match self.action_output(&action) {
+      Some(syn::ReturnType::Default) => {
+        Self::error_unit_action_type(span);
+        Regular(Unit)
+      },
+      Some(syn::ReturnType::Type(_, ref ty)) => {
+        self.check_input_lifetimes(ty);
+        let unit_ty = syn::parse_str("()").expect("unit type");
+        let ty = result_ok_type(ty).unwrap_or((**ty).clone());
+        if ty == unit_ty {
+          Self::error_unit_action_type(span)
+        }
+        let ty: syn::Type =
+          if boxed { parse_quote!(Box<#ty>) }
+          else { ty };
+        Regular(Rust(ty))
+      }
+      None => External
+    }
+  }
+
+  /// The type of a semantic action becomes the type of a rule, in the signature of its function generated with the generic parameters of the stream.
+  /// Hence, a value borrowing from the input, such as `&'src str`, must be typed with the lifetime of the stream (`type Stream<'src> = StrStream<'src>;`).
+  fn check_input_lifetimes(&self, ty: &syn::Type) {
+    let mut lifetimes = vec![];
+    lifetimes_of(quote!(#ty), &mut lifetimes);
+    let declared: Vec<String> = self.stream_alias.generics.lifetimes()
+      .map(|def| def.lifetime.ident.to_string())
+      .collect();
+    for lifetime in lifetimes {
+      let name = lifetime.to_string();
+      if name != "static" && name != "_" && !declared.contains(&name) {
+        lifetime.span().unstable().error(format!(
+          "the lifetime `'{}` is not a lifetime of the input stream, it cannot appear in the type of a rule.\n\
+          Solution: Declare the stream with this lifetime, for example `type Stream<'{}> = StrStream<'{}>;`.",
+          name, name, name))
+        .emit();
       }
-      _ => External
     }
   }
 
@@ -103,7 +130,8 @@ impl IGrammar
       stream_alias: self.stream_alias,
       rust_functions: self.rust_functions,
       rust_items: self.rust_items,
-      attributes: self.attributes
+      attributes: self.attributes,
+      first_sets: self.first_sets
     }
   }
 
@@ -123,6 +151,109 @@ impl IGrammar
   pub fn span_ty_idx(&self) -> usize {
     self.exprs_info.len() - 1
   }
+
+  /// Same as `alloc_span_ty_expr` for the type `Range<usize>` of the bytes offsets in `spanned(e)`, typed `(Range<usize>, T)`.
+  fn alloc_byte_range_ty_expr(&mut self) {
+    self.exprs.push(Expression::ByteSpanned(0)); // useless, just to keep exprs and exprs_info consistent.
+    let range_ty: syn::Type = parse_quote!(Range<usize>);
+    self.exprs_info.push(
+      ExpressionInfo::new(range_ty.span(),
+        IType::Regular(Type::Rust(range_ty))));
+  }
+
+  /// The type `Range<usize>` is stored just before the span type.
+  pub fn byte_range_ty_idx(&self) -> usize {
+    self.exprs_info.len() - 2
+  }
+}
+
+impl TGrammar
+{
+  /// True if the expression is typed `()`: it produces no value and is compiled with its recognizer, even in a parser (see `back::compiler::parser_compiler`).
+  pub fn is_unit_type(&self, expr_idx: usize) -> bool {
+    self[expr_idx].ty == Type::Unit
+  }
+
+  /// The name of each value produced by `expr_idx`, given by the named expressions `name@e`.
+  /// With `by_rule`, a value produced by a rule is also named after this rule.
+  /// A name given to several values is ambiguous, these values are not named.
+  pub fn value_names(&self, expr_idx: usize, by_rule: bool) -> Vec<Option<Ident>> {
+    let cardinality = self[expr_idx].type_cardinality();
+    let mut names = match self.expr_by_index(expr_idx) {
+      Sequence(children) => children.into_iter()
+        .flat_map(|child| self.value_name(child, by_rule))
+        .collect(),
+      _ => self.value_name(expr_idx, by_rule)
+    };
+    // The values are not named if they cannot be matched with the sub-expressions of `expr_idx`.
+    if names.len() != cardinality {
+      return vec![None; cardinality];
+    }
+    let all_names: Vec<_> = names.iter().filter_map(|name| name.clone()).collect();
+    for name in &mut names {
+      let ambiguous = name.as_ref()
+        .is_some_and(|n| all_names.iter().filter(|m| *m == n).count() > 1);
+      if ambiguous {
+        *name = None;
+      }
+    }
+    names
+  }
+
+  fn value_name(&self, expr_idx: usize, by_rule: bool) -> Vec<Option<Ident>> {
+    let cardinality = self[expr_idx].type_cardinality();
+    match self.expr_by_index(expr_idx) {
+      Named(_, name) if cardinality == 1 => vec![Some(name)],
+      NonTerminalSymbol(rule) if by_rule && cardinality == 1 => vec![Some(rule)],
+      TypeAscription(child, _) if cardinality == 1 => self.value_name(child, by_rule),
+      _ => vec![None; cardinality]
+    }
+  }
+
+  /// The index of the value of `child` passed to each parameter of the semantic action `child > action`.
+  /// If `action` is a function of the grammar, a parameter takes the value named like it (see `value_names`), and the other parameters take the remaining values in order.
+  pub fn action_arguments(&self, child: usize, action: &syn::Expr) -> Vec<usize> {
+    let cardinality = self[child].type_cardinality();
+    let positional: Vec<usize> = (0..cardinality).collect();
+    let fun = match action {
+      syn::Expr::Path(expr_path) => {
+        match expr_path.path.get_ident().and_then(|ident| self.rust_functions.get(ident)) {
+          Some(fun) => fun,
+          None => return positional
+        }
+      }
+      _ => return positional
+    };
+    let names = self.value_names(child, false);
+    if fun.sig.inputs.len() != cardinality || names.iter().all(|name| name.is_none()) {
+      return positional;
+    }
+    let mut args: Vec<Option<usize>> = fun.sig.inputs.iter()
+      .map(|param| param_name(param)
+        .and_then(|param| names.iter().position(|name| name.as_ref() == Some(&param))))
+      .collect();
+    let unnamed: Vec<usize> = (0..cardinality).filter(|i| !args.contains(&Some(*i))).collect();
+    let mut unnamed = unnamed.into_iter();
+    for arg in &mut args {
+      if arg.is_none() {
+        *arg = unnamed.next();
+      }
+    }
+    args.into_iter().map(|arg| arg.expect("each parameter takes a value.")).collect()
+  }
+}
+
+/// The name of the parameter `name: T` of a function.
+pub fn param_name(param: &syn::FnArg) -> Option<Ident> {
+  match param {
+    syn::FnArg::Typed(pat_ty) => {
+      match *pat_ty.pat {
+        syn::Pat::Ident(ref pat) => Some(pat.ident.clone()),
+        _ => None
+      }
+    }
+    _ => None
+  }
 }
 
 pub type ExprIType = ExpressionInfo<IType>;
@@ -446,3 +577,20 @@ impl Type
     }
   }
 }
+
+/// The names of the lifetimes appearing in `tokens`, `'a` is the punctuation `'` followed by the identifier `a`.
+fn lifetimes_of(tokens: proc_macro2::TokenStream, result: &mut Vec<Ident>) {
+  use proc_macro2::TokenTree::*;
+  let mut quote_punct = false;
+  for token in tokens {
+    match token {
+      Group(ref group) => lifetimes_of(group.stream(), result),
+      Ident(ref id) if quote_punct => result.push(id.clone()),
+      _ => ()
+    }
+    quote_punct = match token {
+      Punct(ref punct) => punct.as_char() == '\'',
+      _ => false
+    };
+  }
+}
@@ -39,6 +39,7 @@ impl Depth
     if engine.surface.error { return Partial::Nothing }
     engine.reduce_all_invisible();
     engine.check_all_rules_have_type();
+    engine.check_from_str_rules();
     let grammar = engine.surface.grammar;
     if grammar.attributes.print_typing.debug() {
       println!("After applying Depth.");
@@ -96,6 +97,20 @@ impl Depth
     }
   }
 
+  /// `FromStr` can only be implemented for a Rust type defined by the user, not for tuples, units or the types of the standard library.
+  fn check_from_str_rules(&mut self) {
+    for id in self.surface.grammar.attributes.from_str_rules.clone() {
+      let rule = self.surface.grammar.find_rule_by_ident(&id);
+      let is_rust_type = matches!(self.type_of(rule.expr_idx), Regular(Rust(_)));
+      if rule.recognizer_only || !is_rust_type {
+        self.surface.error = true;
+        id.span().unstable()
+          .error(format!("cannot derive `FromStr` for the rule `{}` because its type is not a Rust type (e.g. a struct produced by a semantic action).", id))
+          .emit();
+      }
+    }
+  }
+
   fn depth(&mut self) {
     for rule in self.surface.grammar.rules.clone() {
       self.visit_expr(rule.expr_idx);
@@ -131,6 +146,17 @@ impl Depth
     self.under_ty_ascription = old;
   }
 
+  /// A repetition `e*`, `e+`, `e{n,m}` or `repeat_until_eoi(e)` ascribed with a Rust type such as `e*:String` collects the values of `e` into this type instead of a `Vec`.
+  /// The values of `e` are not under the type ascription.
+  fn is_repeat_into_container(&self, this: usize, ty: &IType, aty: &IType) -> bool {
+    let is_repeat = matches!(self.expr_by_index(this),
+      ZeroOrMore(_) | OneOrMore(_) | BoundedRepeat(_, _, _) | RepeatUntilEoi(_));
+    match (ty, aty) {
+      (&Regular(List(_)), &Regular(Rust(_))) => is_repeat,
+      _ => false
+    }
+  }
+
   fn error_if_not_match_ty_ascription(&mut self, this: usize, ty: IType, aty: IType) {
     if !ty.syntactic_eq(&self.surface.grammar, &aty) {
       self.surface.error = true;
@@ -172,6 +198,13 @@ impl Visitor<()> for Depth
         if this_ty == External {
           self.surface.type_expr(this, aty);
         }
+        else if self.is_repeat_into_container(this, &this_ty, &aty) {
+          self.surface.type_expr(this, aty);
+          let old = self.under_ty_ascription.take();
+          walk_expr(self, this);
+          self.under_ty_ascription = old;
+          return;
+        }
         else {
           self.error_if_not_match_ty_ascription(this, this_ty, aty);
         }
@@ -201,6 +234,23 @@ impl Visitor<()> for Depth
     }
   }
 
+  // The separator of `e % sep` does not produce data, therefore it is typed as unit.
+  fn visit_separated_list(&mut self, _this: usize, child: usize, sep: usize, _one_or_more: bool) {
+    self.visit_expr(child);
+    let old = self.under_unit;
+    self.under_unit = true;
+    self.visit_expr(sep);
+    self.under_unit = old;
+  }
+
+  fn visit_recover(&mut self, _this: usize, child: usize, sync: usize) {
+    self.visit_expr(child);
+    let old = self.under_unit;
+    self.under_unit = true;
+    self.visit_expr(sync);
+    self.under_unit = old;
+  }
+
   fn visit_type_ascription(&mut self, this: usize, child: usize, ty: IType) {
     if let Some(aty) = self.under_ty_ascription.clone() {
       self.error_if_not_match_ty_ascription(this, ty.clone(), aty);
@@ -216,4 +266,17 @@ impl Visitor<()> for Depth
     self.surface_expr(child);
     self.visit_expr_switch_ascription(child, None);
   }
+
+  // The condition of a guard needs the values of its expression, even when the guard itself is typed as unit.
+  fn visit_guard(&mut self, _this: usize, child: usize, _condition: syn::Expr) {
+    if self.under_unit {
+      self.under_unit = false;
+      self.surface_expr(child);
+      self.visit_expr_switch_ascription(child, None);
+      self.under_unit = true;
+    }
+    else {
+      self.visit_expr(child);
+    }
+  }
 }
@@ -15,14 +15,17 @@
 use middle::analysis::ast::AGrammar;
 use middle::typing::ast::*;
 use middle::typing::depth::*;
+use middle::typing::action_arity::*;
 
 pub mod ast;
 mod surface;
 mod depth;
 mod type_rewriting;
 mod typing_printer;
+mod action_arity;
 
 pub fn type_inference(agrammar: AGrammar) -> Partial<TGrammar> {
   let grammar = IGrammar::from(agrammar);
   Depth::infer(grammar)
+    .and_then(check_action_arity)
 }
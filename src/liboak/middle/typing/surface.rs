@@ -20,6 +20,7 @@ use middle::typing::ast::*;
 use middle::typing::ast::IType::*;
 use middle::typing::type_rewriting::*;
 use middle::typing::typing_printer::*;
+use syn::parse_quote;
 
 pub struct Surface
 {
@@ -134,7 +135,7 @@ impl Visitor<IType> for Surface
 
   // Axioms
 
-  fn visit_str_literal(&mut self, _this: usize, _lit: String) -> IType {
+  fn visit_str_literal(&mut self, _this: usize, _lit: String, _case_insensitive: bool) -> IType {
     IType::Invisible
   }
 
@@ -142,6 +143,14 @@ impl Visitor<IType> for Surface
     IType::Invisible
   }
 
+  fn visit_end_of_input(&mut self, _this: usize) -> IType {
+    IType::Invisible
+  }
+
+  fn visit_cut(&mut self, _this: usize) -> IType {
+    IType::Invisible
+  }
+
   fn visit_type_ascription(&mut self, _this: usize, _child: usize, ty: IType) -> IType {
     ty
   }
@@ -169,21 +178,48 @@ impl Visitor<IType> for Surface
     IType::Regular(Type::List(child))
   }
 
+  fn visit_count(&mut self, _this: usize, child: usize) -> IType {
+    self.visit_expr(child);
+    IType::Regular(Type::Rust(parse_quote!(usize)))
+  }
+
+  // The values of the separators are discarded, see `Depth::visit_separated_list`.
+  fn visit_separated_list(&mut self, _this: usize, child: usize, _sep: usize, _one_or_more: bool) -> IType {
+    self.visit_expr(child);
+    IType::Regular(Type::List(child))
+  }
+
   fn visit_optional(&mut self, _this: usize, child: usize) -> IType {
     self.visit_expr(child);
     IType::Regular(Type::Optional(child))
   }
 
+  // The value of `sync` is discarded, see `Depth::visit_recover`.
+  fn visit_recover(&mut self, _this: usize, child: usize, _sync: usize) -> IType {
+    self.visit_expr(child);
+    IType::Regular(Type::Optional(child))
+  }
+
   fn visit_spanned_expr(&mut self, _this: usize, child: usize) -> IType {
     self.visit_expr(child);
     IType::Regular(Type::Tuple(vec![self.grammar.span_ty_idx(), child]))
   }
 
+  fn visit_byte_spanned(&mut self, _this: usize, child: usize) -> IType {
+    self.visit_expr(child);
+    IType::Regular(Type::Tuple(vec![self.grammar.byte_range_ty_idx(), child]))
+  }
+
   fn visit_range_expr(&mut self, _this: usize, child: usize) -> IType {
     self.visit_expr(child);
     IType::Regular(Type::Rust(self.grammar.range_type()))
   }
 
+  fn visit_capture(&mut self, _this: usize, child: usize) -> IType {
+    self.visit_expr(child);
+    IType::Regular(Type::Rust(self.grammar.slice_type()))
+  }
+
   fn visit_sequence(&mut self, _this: usize, children: Vec<usize>) -> IType {
     walk_exprs(self, children.clone());
     IType::Regular(Type::Tuple(children))
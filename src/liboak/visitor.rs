@@ -24,7 +24,13 @@ pub trait Visitor<R: Default> : ExprByIndex
     walk_expr(self, this)
   }
 
-  fn visit_str_literal(&mut self, _this: usize, _lit: String) -> R { R::default() }
+  /// Called by `walk_expr` before visiting the expression `this`.
+  fn on_enter(&mut self, _this: usize) {}
+
+  /// Called by `walk_expr` after visiting the expression `this`, with the result of the visit.
+  fn on_exit(&mut self, _this: usize, _result: &R) {}
+
+  fn visit_str_literal(&mut self, _this: usize, _lit: String, _case_insensitive: bool) -> R { R::default() }
   fn visit_non_terminal_symbol(&mut self, _this: usize, _rule: &Ident) -> R { R::default() }
   fn visit_external_non_terminal_symbol(&mut self, _this: usize, _rule: &syn::Path) -> R { R::default() }
   fn visit_atom(&mut self, _this: usize) -> R { R::default() }
@@ -37,6 +43,13 @@ pub trait Visitor<R: Default> : ExprByIndex
     self.visit_atom(this)
   }
 
+  fn visit_token_pattern(&mut self, this: usize, _pattern: syn::Pat) -> R {
+    self.visit_atom(this)
+  }
+
+  fn visit_end_of_input(&mut self, _this: usize) -> R { R::default() }
+  fn visit_cut(&mut self, _this: usize) -> R { R::default() }
+
   fn visit_spanned_expr(&mut self, _this: usize, child: usize) -> R {
     self.visit_expr(child)
   }
@@ -45,9 +58,21 @@ pub trait Visitor<R: Default> : ExprByIndex
     self.visit_expr(child)
   }
 
+  fn visit_byte_spanned(&mut self, _this: usize, child: usize) -> R {
+    self.visit_expr(child)
+  }
+
+  fn visit_capture(&mut self, _this: usize, child: usize) -> R {
+    self.visit_expr(child)
+  }
+
   fn visit_sequence(&mut self, _this: usize, children: Vec<usize>) -> R;
   fn visit_choice(&mut self, _this: usize, children: Vec<usize>) -> R;
 
+  fn visit_longest_choice(&mut self, this: usize, children: Vec<usize>) -> R {
+    self.visit_choice(this, children)
+  }
+
   fn visit_repeat(&mut self, _this: usize, child: usize) -> R {
     self.visit_expr(child)
   }
@@ -60,10 +85,32 @@ pub trait Visitor<R: Default> : ExprByIndex
     self.visit_repeat(this, child)
   }
 
+  fn visit_bounded_repeat(&mut self, this: usize, child: usize, _min: usize, _max: Option<usize>) -> R {
+    self.visit_repeat(this, child)
+  }
+
+  fn visit_repeat_until_eoi(&mut self, this: usize, child: usize) -> R {
+    self.visit_repeat(this, child)
+  }
+
+  /// `count(e)` matches the input as `e*`.
+  fn visit_count(&mut self, this: usize, child: usize) -> R {
+    self.visit_zero_or_more(this, child)
+  }
+
+  fn visit_separated_list(&mut self, _this: usize, child: usize, sep: usize, _one_or_more: bool) -> R {
+    self.visit_expr(sep);
+    self.visit_expr(child)
+  }
+
   fn visit_optional(&mut self, _this: usize, child: usize) -> R {
     self.visit_expr(child)
   }
 
+  fn visit_committed_optional(&mut self, this: usize, child: usize) -> R {
+    self.visit_optional(this, child)
+  }
+
   fn visit_syntactic_predicate(&mut self, _this: usize, child: usize) -> R {
     self.visit_expr(child)
   }
@@ -83,6 +130,27 @@ pub trait Visitor<R: Default> : ExprByIndex
   fn visit_type_ascription(&mut self, _this: usize, child: usize, _ty: IType) -> R {
     self.visit_expr(child)
   }
+
+  fn visit_error_label(&mut self, _this: usize, child: usize, _label: String) -> R {
+    self.visit_expr(child)
+  }
+
+  fn visit_strict(&mut self, _this: usize, child: usize) -> R {
+    self.visit_expr(child)
+  }
+
+  fn visit_guard(&mut self, _this: usize, child: usize, _condition: syn::Expr) -> R {
+    self.visit_expr(child)
+  }
+
+  fn visit_named(&mut self, _this: usize, child: usize, _name: Ident) -> R {
+    self.visit_expr(child)
+  }
+
+  fn visit_recover(&mut self, _this: usize, child: usize, sync: usize) -> R {
+    self.visit_expr(sync);
+    self.visit_expr(child)
+  }
 }
 
 /// We need this macro for factorizing the code since we can not specialize a trait on specific type parameter (we would need to specialize on `()` here).
@@ -102,13 +170,20 @@ macro_rules! unit_visitor_impl {
 pub fn walk_expr<R: Default, V: ?Sized>(visitor: &mut V, this: usize) -> R where
   V: Visitor<R>
 {
-  match visitor.expr_by_index(this) {
-    StrLiteral(lit) => {
-      visitor.visit_str_literal(this, lit)
+  visitor.on_enter(this);
+  let result = match visitor.expr_by_index(this) {
+    StrLiteral(lit, case_insensitive) => {
+      visitor.visit_str_literal(this, lit, case_insensitive)
     }
     AnySingleChar => {
       visitor.visit_any_single_char(this)
     }
+    EndOfInput => {
+      visitor.visit_end_of_input(this)
+    }
+    Cut => {
+      visitor.visit_cut(this)
+    }
     NonTerminalSymbol(rule) => {
       visitor.visit_non_terminal_symbol(this, &rule)
     }
@@ -121,6 +196,9 @@ pub fn walk_expr<R: Default, V: ?Sized>(visitor: &mut V, this: usize) -> R where
     Choice(choices) => {
       visitor.visit_choice(this, choices)
     }
+    LongestChoice(choices) => {
+      visitor.visit_longest_choice(this, choices)
+    }
     ZeroOrMore(child) => {
       visitor.visit_zero_or_more(this, child)
     }
@@ -130,6 +208,15 @@ pub fn walk_expr<R: Default, V: ?Sized>(visitor: &mut V, this: usize) -> R where
     ZeroOrOne(child) => {
       visitor.visit_optional(this, child)
     }
+    CommittedOptional(child) => {
+      visitor.visit_committed_optional(this, child)
+    }
+    BoundedRepeat(child, min, max) => {
+      visitor.visit_bounded_repeat(this, child, min, max)
+    }
+    SeparatedList(child, sep, one_or_more, _) => {
+      visitor.visit_separated_list(this, child, sep, one_or_more)
+    }
     NotPredicate(child) => {
       visitor.visit_not_predicate(this, child)
     }
@@ -151,7 +238,41 @@ pub fn walk_expr<R: Default, V: ?Sized>(visitor: &mut V, this: usize) -> R where
     RangeExpr(child) => {
       visitor.visit_range_expr(this, child)
     }
-  }
+    ByteSpanned(child) => {
+      visitor.visit_byte_spanned(this, child)
+    }
+    Capture(child) => {
+      visitor.visit_capture(this, child)
+    }
+    ErrorLabel(child, label) => {
+      visitor.visit_error_label(this, child, label)
+    }
+    Strict(child) => {
+      visitor.visit_strict(this, child)
+    }
+    Guard(child, condition) => {
+      visitor.visit_guard(this, child, condition)
+    }
+    Named(child, name) => {
+      visitor.visit_named(this, child, name)
+    }
+    Recover(child, sync) => {
+      visitor.visit_recover(this, child, sync)
+    }
+    RepeatUntilEoi(child) => {
+      visitor.visit_repeat_until_eoi(this, child)
+    }
+    Count(child) => {
+      visitor.visit_count(this, child)
+    }
+    TokenPattern(pattern) => {
+      visitor.visit_token_pattern(this, pattern)
+    }
+    Let(_, _, _) => unreachable!(
+      "BUG: the let bindings are expanded into rules before the expressions are visited.")
+  };
+  visitor.on_exit(this, &result);
+  result
 }
 
 pub fn walk_exprs<R: Default, V: ?Sized>(visitor: &mut V, exprs: Vec<usize>) -> Vec<R> where
@@ -159,3 +280,61 @@ pub fn walk_exprs<R: Default, V: ?Sized>(visitor: &mut V, exprs: Vec<usize>) ->
 {
   exprs.into_iter().map(|expr| visitor.visit_expr(expr)).collect()
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// Records the hooks called while visiting the expression `"a" "" "b"`, where the empty literal fails the sequence.
+  struct HooksTrace
+  {
+    exprs: Vec<Expression>,
+    events: Vec<String>
+  }
+
+  impl ExprByIndex for HooksTrace
+  {
+    fn expr_by_index(&self, index: usize) -> Expression {
+      self.exprs[index].clone()
+    }
+  }
+
+  impl Visitor<bool> for HooksTrace
+  {
+    fn on_enter(&mut self, this: usize) {
+      self.events.push(format!("enter {}", this));
+    }
+
+    fn on_exit(&mut self, this: usize, result: &bool) {
+      self.events.push(format!("exit {} {}", this, result));
+    }
+
+    fn visit_str_literal(&mut self, _this: usize, lit: String, _case_insensitive: bool) -> bool {
+      !lit.is_empty()
+    }
+
+    fn visit_sequence(&mut self, _this: usize, children: Vec<usize>) -> bool {
+      children.into_iter().all(|child| self.visit_expr(child))
+    }
+
+    fn visit_choice(&mut self, _this: usize, children: Vec<usize>) -> bool {
+      children.into_iter().any(|child| self.visit_expr(child))
+    }
+  }
+
+  #[test]
+  fn test_hooks_order() {
+    let mut trace = HooksTrace {
+      exprs: vec![
+        StrLiteral(format!("a"), false),
+        StrLiteral(format!(""), false),
+        StrLiteral(format!("b"), false),
+        Sequence(vec![0, 1, 2])],
+      events: vec![]
+    };
+    assert!(!trace.visit_expr(3));
+    // The exit hook is called on the failure of the literal and of the sequence, the last literal is never visited.
+    assert_eq!(trace.events, vec![
+      "enter 3", "enter 0", "exit 0 true", "enter 1", "exit 1 false", "exit 3 false"]);
+  }
+}
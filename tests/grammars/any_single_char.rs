@@ -0,0 +1,63 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+// `.` consumes a Unicode scalar value on a string, whatever its number of bytes.
+oak! {
+  #![start(one, pair, three)]
+
+  one = .
+  pair = . .
+  three = $(. . .)
+}
+
+// `.` consumes a single byte on a slice of bytes.
+mod bytes {
+  use oak::oak;
+
+  oak! {
+    #![start(pair, three)]
+
+    type Stream<'a> = ByteStream<'a>;
+
+    pair = . .
+    three = $(. . .)
+  }
+}
+
+#[test]
+fn test_any_single_char_multibyte() {
+  let state = parse_one("🦀".into_state());
+  assert_eq!(state.current.bytes_offset(), 4);
+  assert_eq!(state.unwrap_data(), '🦀');
+  assert_eq!(full_parse_pair("é€"), Ok(('é', '€')));
+  // 1, 2 and 3 bytes, the captured slice ends on a character boundary.
+  let state = parse_three("aé€b".into_state());
+  assert_eq!(state.current.bytes_offset(), 6);
+  assert_eq!(state.unwrap_data(), "aé€");
+  assert!(full_parse_pair("é").is_err());
+}
+
+#[test]
+fn test_any_single_byte() {
+  let data = "é".as_bytes();
+  assert_eq!(bytes::full_parse_pair(data), Ok(('\u{c3}', '\u{a9}')));
+  let data = "aé€".as_bytes();
+  let state = bytes::parse_three(data.into_state());
+  assert_eq!(state.current.bytes_offset(), 3);
+  assert_eq!(state.unwrap_data(), &data[..3]);
+}
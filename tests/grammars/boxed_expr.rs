@@ -0,0 +1,56 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+use self::Expr::*;
+
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+  Num(u32),
+  Add(Box<Expr>, Box<Expr>),
+  Mul(Box<Expr>, Box<Expr>)
+}
+
+oak! {
+  // `box e` boxes the value of `e`, so an enum variant can directly be used as the semantic action of a recursive rule.
+  sum: Expr
+    = box product "+" box sum > Add
+    / product
+
+  product: Expr
+    = box number "*" box product > Mul
+    / number
+
+  number: Expr = ["0-9"]+ > make_number
+
+  // pair = box (number "," number)  // ERROR: the boxed expression produces 2 values
+
+  fn make_number(digits: Vec<char>) -> Expr {
+    Num(digits.into_iter().collect::<String>().parse().unwrap())
+  }
+}
+
+use oak_runtime::*;
+
+fn num(n: u32) -> Box<Expr> {
+  Box::new(Num(n))
+}
+
+#[test]
+fn test_boxed_expr() {
+  let state = parse_sum("1+2*3+4".into_state());
+  assert!(!state.current.has_next());
+  assert_eq!(state.unwrap_data(),
+    Add(num(1), Box::new(Add(Box::new(Mul(num(2), num(3))), num(4)))));
+}
@@ -0,0 +1,35 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  number = digit+
+  identifier = $(alpha alnum*)
+  hex_number = "0x" $hexdigit+
+  // The rule `space` shadows the built-in class.
+  words = identifier (space identifier)*
+  space = " "
+}
+
+#[test]
+fn test_builtin_class() {
+  assert_eq!(parse_number("042".into_state()).unwrap_data(), vec!['0', '4', '2']);
+  assert_eq!(parse_identifier("x1y".into_state()).unwrap_data(), "x1y");
+  assert!(parse_identifier("1x".into_state()).is_failed());
+  assert_eq!(parse_hex_number("0xfF09".into_state()).unwrap_data(), "fF09");
+  assert!(recognize_words("a b".into_state()).into_full_result().is_ok());
+  assert!(recognize_words("a\tb".into_state()).into_full_result().is_err());
+}
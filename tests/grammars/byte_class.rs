@@ -0,0 +1,61 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(controls, text, frame, nibble)]
+
+  type Stream<'a> = ByteStream<'a>;
+
+  controls = $[0x00-0x1F, 0x7F]+
+  text = $[^0x00-0x1F, 0x7F]+
+
+  // A frame of printable bytes between STX and ETX.
+  frame = [0x02]:(^) $[0x20-0x7E]* [0x03]:(^)
+
+  nibble = [0b0000_0000-0b0000_1111]
+
+  // empty = [0x1F-0x00]    // ERROR: the interval `0x1F-0x00` is empty.
+  // large = [0x00-0x100]   // ERROR: a byte must be between `0x00` and `0xFF`.
+  // With `StrStream`, `[0x00-0x1F]` is rejected: classes of bytes can only be used with `ByteStream`.
+}
+
+#[test]
+fn test_control_bytes() {
+  let data: &[u8] = b"\x00\x1b\x7f\x1fabc";
+  let state = parse_controls(data.into_state());
+  assert_eq!(state.current.bytes_offset(), 4);
+  assert_eq!(state.unwrap_data(), &data[..4]);
+  let state = parse_text(data.into_state());
+  assert!(state.is_failed());
+  let data: &[u8] = b"abc\xff\x0a";
+  assert_eq!(parse_text(data.into_state()).unwrap_data(), &data[..4]);
+}
+
+#[test]
+fn test_frame() {
+  let data: &[u8] = b"\x02hello, world\x03";
+  assert_eq!(full_parse_frame(data), Ok(&b"hello, world"[..]));
+  assert!(full_parse_frame(&b"\x02hello\x0aworld\x03"[..]).is_err());
+  assert!(full_parse_frame(&b"hello\x03"[..]).is_err());
+}
+
+#[test]
+fn test_binary_bounds() {
+  assert_eq!(full_parse_nibble(&[0x0f][..]), Ok('\u{f}'));
+  assert!(full_parse_nibble(&[0x10][..]).is_err());
+}
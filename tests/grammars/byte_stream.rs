@@ -0,0 +1,57 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  type Stream<'a> = ByteStream<'a>;
+
+  gif = "GIF8" ("7a" / "9a") word word > make_size
+
+  word = . . > le_u16
+
+  header = .. "GIF8"
+
+  high_bytes = ["\u{80}-\u{ff}"]+
+
+  fn le_u16(lo: char, hi: char) -> u16 {
+    (lo as u16) | ((hi as u16) << 8)
+  }
+
+  fn make_size(width: u16, height: u16) -> (u16, u16) {
+    (width, height)
+  }
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_byte_stream() {
+  let data: &[u8] = b"GIF89a\x40\x01\xf0\x00";
+  let state = parse_gif(data.into_state());
+  assert!(!state.current.has_next());
+  assert_eq!(state.unwrap_data(), (320, 240));
+  let state = parse_header(data.into_state());
+  assert_eq!(state.unwrap_data(), 0..4);
+  let state = recognize_gif((&b"GIF87b"[..]).into_state());
+  assert!(state.is_failed());
+}
+
+#[test]
+fn test_byte_class() {
+  let data: &[u8] = &[0x80, 0xc3, 0xff, 0x7f];
+  let state = recognize_high_bytes(data.into_state());
+  assert!(state.is_successful());
+  assert_eq!(state.current.bytes_offset(), 3);
+}
@@ -0,0 +1,46 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  // `$e` and `capture(e)` produce the slice of the input matched by `e` instead of its value.
+  pair = identifier "=" capture(["0-9"]+) > make_pair
+  identifier = $(["a-zA-Z_"] ["a-zA-Z0-9_"]*)
+  keyword = $("let" / "fn")
+
+  fn make_pair<'a>(key: &'a str, value: &'a str) -> (&'a str, &'a str) {
+    (key, value)
+  }
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_capture() {
+  let input = String::from("x1=42");
+  let state = parse_pair(input.as_str().into_state());
+  assert!(!state.current.has_next());
+  let (key, value) = state.unwrap_data();
+  assert_eq!(key, "x1");
+  assert_eq!(value, "42");
+}
+
+#[test]
+fn test_capture_is_borrowed() {
+  let input = "fn f";
+  let keyword: &str = parse_keyword(input.into_state()).unwrap_data();
+  assert_eq!(keyword, "fn");
+  assert_eq!(keyword.as_ptr(), input.as_ptr());
+}
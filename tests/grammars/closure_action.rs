@@ -0,0 +1,81 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(double, sum, digit_or_zero, checked_number, half, numbers)]
+
+  number = ["0-9"]+ > to_number
+
+  double: u32 = number > |n| n * 2
+
+  sum = number "+" number > |a, b| -> u32 { a + b }
+
+  // A closure and a function in the branches of a choice.
+  digit_or_zero = ["0-9"] > |c: char| -> u32 { c.to_digit(10).unwrap() }
+                / "_" > zero
+
+  // A fallible closure fails the first branch when the number does not fit in a byte.
+  checked_number = ["0-9"]+ > |digits| -> Result<u8, std::num::ParseIntError> {
+      to_string(digits).parse::<u8>()
+    }
+    / ["0-9"]+ > saturated_byte
+
+  half: u32 = number > |n| (n / 2)
+
+  numbers: Vec<u32> = number ("," number > |n| -> u32 { n / 2 })* > |first, mut rest: Vec<u32>| {
+    rest.insert(0, first);
+    rest
+  }
+
+  fn to_string(raw_text: Vec<char>) -> String {
+    raw_text.into_iter().collect()
+  }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    to_string(raw_text).parse().unwrap()
+  }
+
+  fn zero() -> u32 { 0 }
+
+  fn saturated_byte(_raw_text: Vec<char>) -> u8 {
+    u8::max_value()
+  }
+}
+
+#[test]
+fn test_closure_action() {
+  assert_eq!(parse_double("21".into_state()).unwrap_data(), 42);
+  assert_eq!(parse_sum("20+22".into_state()).unwrap_data(), 42);
+}
+
+#[test]
+fn test_closure_in_choice() {
+  assert_eq!(parse_digit_or_zero("7".into_state()).unwrap_data(), 7);
+  assert_eq!(parse_digit_or_zero("_".into_state()).unwrap_data(), 0);
+}
+
+#[test]
+fn test_fallible_closure_action() {
+  assert_eq!(parse_checked_number("42".into_state()).unwrap_data(), 42);
+  assert_eq!(parse_checked_number("300".into_state()).unwrap_data(), 255);
+}
+
+#[test]
+fn test_closure_with_division() {
+  assert_eq!(parse_half("9".into_state()).unwrap_data(), 4);
+  assert_eq!(parse_numbers("1,4,10".into_state()).unwrap_data(), vec![1, 2, 5]);
+}
@@ -17,6 +17,9 @@ use oak::oak;
 oak! {
   str_literal = "return"
 
+  // Non-ASCII characters are not folded, so "été"i does not match "ÉTÉ".
+  case_insensitive_str_literal = "select"i " " "été"i
+
   sequence = "if" " " "then" " " "else"
 
   any_single_char = . .
@@ -27,12 +30,39 @@ oak! {
 
   repeat = (("a" / "b"+) .)* "c"*
 
+  bounded_repeat = "a"{2} "b"{1,} "c"{0,2} "d"{1,3}
+
+  separated_list = "[" ["0-9"] % "," "]" "(" (["a-z"]+) %% ("," " "?) ")"
+
   predicate = &"a" (!"b" .)+ / &"b" (!"a" .)+
 
+  end_of_input = ("a" / "b" eoi)+ eoi
+
+  // The first choice is compiled into a dispatch on the next character, the second one overlaps and is tried in sequence.
+  dispatch = ("fn" / "if" / "let" / ["0-9"]+:(^) / "-" ["0-9"]:(^))+ ("w" .:(^) / "wx")
+
+  // `.until(e)` is rewritten into `(!e .)*`.
+  until = ("/*" .until("*/") "*/")+
+
   optional = "a"? "b" ("c" . / "d" .)? "z"
 
   char_class = ["a-zA-Z12_"]+ ["\t "]? ["-"]
 
+  // `-` is a character at the start or the end of a class, `]` and `\` need no special treatment inside the string literal.
+  class_edges = ["-+*/"] ["a-z0-9_"]+ ["+*/-"] ["]\\["]+
+
+  // A letter or `_`, followed by a decimal digit or a character that is not a letter.
+  unicode_category = ["\\p{L}_"]+ ["\\p{Nd}\\P{L}"]
+
+  // The escapes of string literals, also in the character classes.
+  escapes = "\x41" ["\u{e9}\u{1F600}-\u{1F64F}"]+ "\t"? "\\\""
+
+  // Raw string literals are not escaped, also in the character classes. `r""` is the empty literal.
+  raw_strings = r"\d+" r#""quoted""# r##"a"#b"## r"" [r"\/"]+
+
+  // `[^]` accepts any character but fails at the end of input.
+  negated_char_class = [^"a-z"]+ [^]
+
   non_terminal = "a" non_terminal_bis+ .
 
   non_terminal_bis = ("b" . / "c" .) (!"d" .)+
@@ -0,0 +1,52 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(if_else, lenient_if_else)]
+
+  // Once `else` is read, the branch is mandatory.
+  if_else = "if " ident committed(" else " ident)
+  lenient_if_else = "if " ident (" else " ident)?
+
+  ident = $["a-z"]+
+}
+
+#[test]
+fn test_committed_absent() {
+  assert_eq!(full_parse_if_else("if a"), Ok(("a", None)));
+}
+
+#[test]
+fn test_committed_present() {
+  assert_eq!(full_parse_if_else("if a else b"), Ok(("a", Some("b"))));
+}
+
+#[test]
+fn test_partial_consumption_fails() {
+  // The optional expression has consumed ` else ` when it fails on `1`, so the whole expression fails.
+  assert!(parse_if_else("if a else 1".into_state()).is_failed());
+  assert_eq!(full_parse_if_else("if a else 1").unwrap_err().offset, 10);
+  // `e?` restores the state before ` else ` and succeeds, the rest of the input is not consumed.
+  assert_eq!(parse_lenient_if_else("if a else 1".into_state()).into_partial_result(), Ok((("a", None), 4)));
+}
+
+#[test]
+fn test_failure_without_consumption() {
+  // ` else ` is not matched at all, `committed(e)` behaves like `e?`.
+  assert_eq!(parse_if_else("if a; b".into_state()).into_partial_result(), Ok((("a", None), 4)));
+}
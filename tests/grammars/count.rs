@@ -0,0 +1,48 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(line, indentation, items)]
+
+  // `count(e)` matches as `e*` but only returns the number of repetitions, the values of `e` are not built.
+  line = indentation ["a-z"]+ > make_line
+  indentation = count(" ")
+  items = count(["a-z"]+ ",") "end"
+
+  // loop = count("a"?) // ERROR: Infinite loop detected.
+
+  fn make_line(depth: usize, word: Vec<char>) -> (usize, String) {
+    (depth, word.into_iter().collect())
+  }
+}
+
+#[test]
+fn test_count_leading_spaces() {
+  assert_eq!(full_parse_line("abc"), Ok((0, format!("abc"))));
+  assert_eq!(full_parse_line("    abc"), Ok((4, format!("abc"))));
+  let depth: usize = full_parse_indentation("  ").unwrap();
+  assert_eq!(depth, 2);
+  assert_eq!(partial_parse_indentation("  \tx"), Ok((2, 2)));
+}
+
+#[test]
+fn test_count_repeated_items() {
+  assert_eq!(full_parse_items("end"), Ok(0));
+  assert_eq!(full_parse_items("a,bc,d,end"), Ok(3));
+  assert!(full_parse_items("a,bc").is_err());
+}
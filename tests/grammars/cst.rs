@@ -0,0 +1,65 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak_runtime::cst::*;
+use oak::oak;
+
+oak! {
+  #![cst]
+
+  sum = spaces number (plus number)* > make_sum
+  plus = "+" spaces
+  number = ["0-9"]+ spaces > to_number
+  spaces = skip([" "]*)
+
+  fn make_sum(first: u32, rest: Vec<u32>) -> u32 {
+    rest.into_iter().fold(first, |a, b| a + b)
+  }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    raw_text.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+#[test]
+fn test_cst() {
+  let input = " 1 + 22 ";
+  let cst = cst_parse_sum(input).unwrap();
+  assert_eq!(format!("{}", cst),
+    "sum 0..8\n\
+    \x20 spaces 0..1\n\
+    \x20   token 0..1\n\
+    \x20 number 1..3\n\
+    \x20   token 1..2\n\
+    \x20   spaces 2..3\n\
+    \x20     token 2..3\n\
+    \x20 plus 3..5\n\
+    \x20   token 3..4\n\
+    \x20   spaces 4..5\n\
+    \x20     token 4..5\n\
+    \x20 number 5..8\n\
+    \x20   token 5..7\n\
+    \x20   spaces 7..8\n\
+    \x20     token 7..8\n");
+  let text: String = cst.tokens().into_iter().map(|token| token.text(input)).collect();
+  assert_eq!(text, input);
+  assert_eq!(full_parse_sum(input), Ok(23));
+}
+
+#[test]
+fn test_cst_failure() {
+  assert!(cst_parse_sum("1 +").is_err());
+  assert!(cst_parse_sum("1 + 2 3").is_err());
+}
@@ -0,0 +1,54 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A custom combinator written with the savepoint API of `ParseState` and called as an external rule.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  group = $balanced ";"
+}
+
+/// Recognizes a (possibly empty) sequence of balanced parentheses, which cannot be expressed with a regular expression.
+pub fn recognize_balanced<'a>(mut state: ParseState<StrStream<'a>, ()>) -> ParseState<StrStream<'a>, ()> {
+  state.discard_data();
+  let mut depth = 0;
+  loop {
+    let mark = state.mark();
+    match state.next() {
+      Some('(') => depth += 1,
+      Some(')') if depth > 0 => depth -= 1,
+      _ => {
+        state = state.restore(mark);
+        break;
+      }
+    }
+  }
+  if depth == 0 {
+    state.success(())
+  }
+  else {
+    state.error("balanced parentheses");
+    state.failure()
+  }
+}
+
+#[test]
+fn test_custom_combinator() {
+  assert_eq!(parse_group("(()());".into_state()).unwrap_data(), "(()())");
+  assert_eq!(parse_group(";".into_state()).unwrap_data(), "");
+  assert!(parse_group("(();".into_state()).is_failed());
+  assert!(parse_group("());".into_state()).is_failed());
+}
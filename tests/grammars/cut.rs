@@ -0,0 +1,61 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![start(term, cut_term, outer, after_choice, value)]
+
+  // Without the cut, `(x` is recognized by the last branch.
+  term = "(" term ")" / ["0-9"]+:(^) / "(" "x"
+  cut_term = "(" ~ cut_term ")" / ["0-9"]+:(^) / "(" "x"
+
+  // The cut of `inner` does not apply to the choice of `outer`.
+  outer = inner / "(" "y"
+  inner = "(" ~ "x" / "z"
+
+  // The cut applies to the choice enclosing `("a" / "b")`.
+  after_choice = ("a" / "b") ~ "c" / "a" "d"
+
+  value = "(" ~ number ")" / number
+  number = ["0-9"]+ > to_number
+
+  use std::str::FromStr;
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    u32::from_str(&*raw_text.into_iter().collect::<String>()).unwrap()
+  }
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_cut_recognizer() {
+  assert!(recognize_term("(x".into_state()).is_successful());
+  assert!(recognize_cut_term("(x".into_state()).is_failed());
+  assert!(recognize_cut_term("((1))".into_state()).is_successful());
+  assert!(recognize_outer("(y".into_state()).is_successful());
+  assert!(recognize_after_choice("bc".into_state()).is_successful());
+  assert!(recognize_after_choice("ad".into_state()).is_failed());
+}
+
+#[test]
+fn test_cut_parser() {
+  assert_eq!(parse_value("(12)".into_state()).unwrap_data(), 12);
+  assert_eq!(parse_value("7".into_state()).unwrap_data(), 7);
+  let state = parse_value("(12".into_state());
+  assert!(state.is_failed());
+  assert_eq!(state.farthest_read.bytes_offset(), 3);
+  assert_eq!(state.expected, vec!["[\"0-9\"]", ")"]);
+}
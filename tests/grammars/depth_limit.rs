@@ -0,0 +1,37 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![depth_limit]
+
+  nested = "(" nested ")" / "x"
+}
+
+#[test]
+fn test_depth_limit() {
+  // The 1000 nested calls of the default limit do not fit in the 2 MiB stack of a test thread in a debug build.
+  let test = std::thread::Builder::new().stack_size(16 << 20).spawn(|| {
+    assert!(recognize_nested("((x))".into_state()).is_successful());
+    let shallow = format!("{}x{}", "(".repeat(500), ")".repeat(500));
+    assert!(recognize_nested(shallow.as_str().into_state()).is_successful());
+    // Fails instead of overflowing the stack.
+    let deep = format!("{}x{}", "(".repeat(100_000), ")".repeat(100_000));
+    assert!(recognize_nested(deep.as_str().into_state()).is_failed());
+    assert!(parse_nested(deep.as_str().into_state()).into_full_result().is_err());
+  }).unwrap();
+  test.join().unwrap();
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+// The grammar is accepted with warnings, which are written to the JSON file during its compilation (with the feature `diagnostics_json`).
+oak! {
+  #![diagnostics_json = "target/diagnostics_json_test.json"]
+  #![warn(left_recursion)]
+
+  list = list "," item > push
+       / item > single
+  item = ["a-za-c"]
+
+  unused = "u"
+
+  fn push(mut list: Vec<char>, item: char) -> Vec<char> {
+    list.push(item);
+    list
+  }
+
+  fn single(item: char) -> Vec<char> { vec![item] }
+}
+
+#[test]
+fn test_grammar() {
+  assert_eq!(full_parse_list("a,b"), Ok(vec!['a', 'b']));
+}
+
+#[cfg(feature = "diagnostics_json")]
+#[test]
+fn test_diagnostics_json() {
+  let json = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/target/diagnostics_json_test.json")).unwrap();
+  for expected in &[
+    "\"kind\": \"left_recursion\"",
+    "\"kind\": \"overlapping_intervals\"",
+    "\"kind\": \"unused_rule\"",
+    "\"level\": \"warning\"",
+    "\"rule\": \"list\"",
+    "\"rule\": \"item\"",
+    "\"rule\": \"unused\""]
+  {
+    assert!(json.contains(expected), "`{}` not found in {}", expected, json);
+  }
+  assert!(!json.contains("\"level\": \"error\""));
+}
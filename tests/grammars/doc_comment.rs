@@ -0,0 +1,47 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  //! A list of numbers, the inner doc comments of the grammar are ignored.
+  #![start(list)]
+
+  /// A non-empty list of numbers separated by commas, such as `1,2,3`.
+  list = number ("," number)* > cons
+
+  /**
+   * A natural number.
+   */
+  number = ["0-9"]+ /* no sign */ > to_number // trailing comment
+
+  fn cons(x: u32, rest: Vec<u32>) -> Vec<u32> {
+    let mut list = vec![x];
+    list.extend(rest);
+    list
+  }
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+#[test]
+fn test_doc_comment() {
+  assert_eq!(full_parse_list("1,2,3"), Ok(vec![1, 2, 3]));
+  assert!(full_parse_list("1,").is_err());
+  assert_eq!(parse_number("42".into_state()).into_partial_result(), Ok((42, 2)));
+}
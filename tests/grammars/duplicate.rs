@@ -0,0 +1,35 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  number = digits > to_number
+
+  // digits = ["0-9"]+        // ERROR: duplicate definition of rule with name `digits`
+  digits = ["0-9"]+
+
+  // fn digits() {}           // ERROR: the rust function `digits` has the same name as a rule
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+#[test]
+fn test_distinct_names() {
+  assert_eq!(full_parse_number("42"), Ok(42));
+}
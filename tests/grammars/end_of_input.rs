@@ -0,0 +1,55 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The positions at the end of the input: reading there fails without panicking, and the errors are located at the length of the input.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(any, pair, twice, word)]
+
+  any = .
+  pair = . .
+  twice = "a"{2}
+  word = ["a-z"]+
+}
+
+#[test]
+fn test_any_at_end_of_input() {
+  assert!(recognize_any("".into_state()).is_failed());
+  let error = full_parse_any("").unwrap_err();
+  assert_eq!((error.offset, error.location.as_str()), (0, "1:1"));
+  let error = full_parse_pair("é").unwrap_err();
+  assert_eq!(error.offset, 2);
+  assert!(error.message.contains("<end-of-file>"));
+}
+
+#[test]
+fn test_bounded_repeat_at_end_of_input() {
+  assert_eq!(full_parse_twice("aa"), Ok(()));
+  assert_eq!(full_parse_twice("a").unwrap_err().offset, 1);
+  assert_eq!(full_parse_twice("aaa").unwrap_err().offset, 2);
+  assert_eq!(partial_parse_twice("aaa"), Ok(((), 2)));
+}
+
+#[test]
+fn test_restore_at_end_of_input() {
+  // The repetition fails after the last letter and restores the state at the end of the input.
+  assert_eq!(partial_parse_word("abc"), Ok((vec!['a', 'b', 'c'], 3)));
+  // The snippet of the error does not cut the multibyte characters.
+  let error = full_parse_word("ab1ééééééé").unwrap_err();
+  assert_eq!(error.offset, 2);
+  assert!(error.message.starts_with("unexpected `1éééé`,"), "{}", error.message);
+}
@@ -0,0 +1,62 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  // The entry rule is the start rule declared with `#[start]` instead of the first rule of the grammar.
+  item = ["a-z"]+
+
+  /// A list of items separated by commas, such as `[a,bc]`.
+  #[start]
+  list = "[" item % "," "]"
+
+  // #[start]
+  // pair = "(" item "," item ")" // ERROR: the entry rule is declared more than once with `#[start]`.
+
+  // #[start(pair)]
+  // pair = "(" item "," item ")" // ERROR: `#[start]` takes no argument, several start rules are declared with `#![start(r1, r2)]`.
+
+  // fn parse() {} // ERROR: the function `parse` is generated for the entry rule `list` declared with `#[start]`.
+}
+
+#[test]
+fn test_entry_rule() {
+  assert_eq!(parse("[a,bc]"), Ok(vec![vec!['a'], vec!['b', 'c']]));
+  assert!(parse("[a,bc").is_err());
+  assert!(parse("a").is_err());
+  assert_eq!(full_parse_list("[]"), parse("[]"));
+}
+
+mod with_start_rules {
+  use oak::oak;
+
+  oak! {
+    // `#[start]` can be combined with the start rules of `#![start(...)]`.
+    #![start(pair)]
+
+    item = ["a-z"]
+    pair = "(" item "," item ")"
+    #[start]
+    list = "[" item* "]"
+  }
+
+  #[test]
+  fn test_entry_and_start_rules() {
+    assert_eq!(parse("[ab]"), Ok(vec!['a', 'b']));
+    assert_eq!(full_parse_pair("(a,b)"), Ok(('a', 'b')));
+  }
+}
@@ -0,0 +1,43 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  paren = "(" expr^"expression after `(`" ")"^"`)`"
+  expr = number ("+" number^"number after `+`")*
+  number = ["0-9"]+
+}
+
+use oak_runtime::*;
+
+fn expected<'a>(state: ParseState<StrStream<'a>, ()>) -> (usize, Vec<&'static str>) {
+  assert!(state.is_failed());
+  (state.farthest_read.bytes_offset(), state.expected)
+}
+
+#[test]
+fn test_error_label() {
+  assert!(recognize_paren("(1+2)".into_state()).is_successful());
+  assert_eq!(expected(recognize_paren("()".into_state())),
+    (1, vec!["expression after `(`"]));
+  assert_eq!(expected(recognize_paren("(1+".into_state())),
+    (3, vec!["number after `+`"]));
+  // The label only replaces the items expected by `")"`.
+  assert_eq!(expected(recognize_paren("(1".into_state())),
+    (2, vec!["[\"0-9\"]", "+", "`)`"]));
+  // The error of `expr` is farther than the position of `)`, so the label of `)` is not reported.
+  assert_eq!(expected(recognize_paren("(1+)".into_state())),
+    (3, vec!["number after `+`"]));
+}
@@ -0,0 +1,57 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![start(byte, checked_byte)]
+
+  // `to_byte` returns a `Result`, so the action fails the branch when the number does not fit in a byte.
+  byte = ["0-9"]+ > to_byte
+       / ["0-9"]+ > saturated_byte
+  checked_byte = ["0-9"]+ > to_byte
+
+  fn to_byte(digits: Vec<char>) -> Result<u8, std::num::ParseIntError> {
+    digits.into_iter().collect::<String>().parse::<u8>()
+  }
+
+  fn saturated_byte(_digits: Vec<char>) -> u8 {
+    u8::max_value()
+  }
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_fallible_action_success() {
+  let state = parse_checked_byte("42".into_state());
+  assert!(!state.current.has_next());
+  assert_eq!(state.unwrap_data(), 42);
+}
+
+#[test]
+fn test_fallible_action_failure() {
+  let state = parse_checked_byte("300".into_state());
+  assert!(state.is_failed());
+  let error = full_parse_checked_byte("300").unwrap_err();
+  assert_eq!(error.offset, 3);
+  assert_eq!(error.message, format!("number too large to fit in target type, unexpected `<end-of-file>`, expecting `[\"0-9\"]`."));
+}
+
+#[test]
+fn test_fallible_action_recovered_by_choice() {
+  let state = parse_byte("300".into_state());
+  assert!(!state.current.has_next());
+  assert_eq!(state.unwrap_data(), 255);
+}
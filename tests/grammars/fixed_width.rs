@@ -0,0 +1,50 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(record, postal_code)]
+
+  // A record `YYYYMMDD` followed by a code of 6 characters, the characters of each field are collected into a `String`.
+  record = date code > make_record
+  date = digit{4}:String digit{2}:String digit{2}:String
+  code: String = alnum{6}
+
+  // The capture of a bounded repetition is the slice of the input it matched.
+  postal_code = $digit{5} ("-" $digit{4})?
+
+  fn make_record(year: String, month: String, day: String, code: String) -> (String, String, String, String) {
+    (year, month, day, code)
+  }
+}
+
+#[test]
+fn test_string_fields() {
+  assert_eq!(full_parse_record("20240131AB12CD"),
+    Ok((format!("2024"), format!("01"), format!("31"), format!("AB12CD"))));
+  // A field is either too short or followed by an unexpected character.
+  assert!(full_parse_record("2024013AB12CD").is_err());
+  assert!(full_parse_record("20240131AB12CDE").is_err());
+}
+
+#[test]
+fn test_captured_fields() {
+  assert_eq!(full_parse_postal_code("12345"), Ok(("12345", None)));
+  assert_eq!(full_parse_postal_code("12345-6789"), Ok(("12345", Some("6789"))));
+  assert!(full_parse_postal_code("1234").is_err());
+  assert!(full_parse_postal_code("12345-678").is_err());
+}
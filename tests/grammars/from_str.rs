@@ -0,0 +1,56 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![derive_from_str(point)]
+
+  point = "(" number "," number ")" > make_point
+  number = ["0-9"]+ > to_number
+
+  #[derive(Debug, PartialEq)]
+  pub struct Point {
+    pub x: u32,
+    pub y: u32
+  }
+
+  fn make_point(x: u32, y: u32) -> Point {
+    Point { x, y }
+  }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    raw_text.into_iter().fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap())
+  }
+}
+
+#[test]
+fn test_from_str_success() {
+  assert_eq!("(1,23)".parse::<Point>(), Ok(Point { x: 1, y: 23 }));
+}
+
+#[test]
+fn test_from_str_error() {
+  let error = "(1;2)".parse::<Point>().unwrap_err();
+  assert_eq!(error.location, format!("1:3"));
+  assert!(error.to_string().starts_with("1:3: unexpected `;2)`"));
+}
+
+#[test]
+fn test_from_str_trailing_input() {
+  // The input must be entirely consumed by the rule.
+  let error = "(1,2)x".parse::<Point>().unwrap_err();
+  assert_eq!(error.location, format!("1:6"));
+  assert_eq!(error.message, format!("unexpected `x`, expecting the end of input."));
+}
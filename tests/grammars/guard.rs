@@ -0,0 +1,60 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  date = year "-" month "-" day && { valid_date(year, month, day) } > make_date
+  year = number
+  month = number
+  day = number
+
+  number = ["0-9"]+ > to_number
+
+  // The guard is evaluated by the recognizer of `short` too.
+  short = word && { word.len() <= 3 }
+  word = $["a-z"]+
+  words = short (" " short)*
+
+  #[derive(Debug, PartialEq)]
+  pub struct Date { year: u32, month: u32, day: u32 }
+
+  fn make_date(year: u32, month: u32, day: u32) -> Date {
+    Date { year, month, day }
+  }
+
+  fn valid_date(year: u32, month: u32, day: u32) -> bool {
+    let days = match month {
+      2 if year % 4 == 0 => 29,
+      2 => 28,
+      4 | 6 | 9 | 11 => 30,
+      _ => 31
+    };
+    month >= 1 && month <= 12 && day >= 1 && day <= days
+  }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    raw_text.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+#[test]
+fn test_guard() {
+  assert_eq!(parse_date("2020-02-29".into_state()).unwrap_data(), Date { year: 2020, month: 2, day: 29 });
+  assert!(parse_date("2021-02-29".into_state()).is_failed());
+  assert!(parse_date("2021-13-01".into_state()).is_failed());
+  assert!(recognize_words("for all".into_state()).into_full_result().is_ok());
+  assert!(recognize_words("for every".into_state()).into_full_result().is_err());
+}
@@ -0,0 +1,55 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+// The rules of the included grammar are merged with the ones of the grammar, as if they were written in place of `#![include("path")]`.
+// A rule defined in both grammars is a duplicate error mentioning the included grammar.
+oak! {
+  #![spacing(spacing)]
+  #![start(assignment)]
+  #![include("tests/grammars/lexical.oak")]
+
+  assignment = "let" identifier "=" number ";"
+
+  // number = ["0-9"] > to_number // ERROR: duplicate definition of rule with name `number` (previous definition of `number` in the included grammar `tests/grammars/lexical.oak` here)
+}
+
+#[test]
+fn test_included_rules() {
+  assert_eq!(full_parse_assignment("let x1 = 42;"), Ok(("x1", 42)));
+  assert_eq!(full_parse_assignment("let y=7 ;"), Ok(("y", 7)));
+  assert!(full_parse_assignment("let 1 = 2;").is_err());
+}
+
+mod shared {
+  use oak::oak;
+
+  oak! {
+    // The same lexical rules are shared by another grammar.
+    #![include("tests/grammars/lexical.oak")]
+    #![start(pair)]
+    #![spacing(spacing)]
+
+    pair = "(" identifier "," number ")"
+    // #![include("tests/grammars/lexical.oak")] // ERROR: the grammar `tests/grammars/lexical.oak` is included more than once.
+  }
+
+  #[test]
+  fn test_shared_rules() {
+    assert_eq!(full_parse_pair("(a, 1)"), Ok(("a", 1)));
+  }
+}
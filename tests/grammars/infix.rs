@@ -0,0 +1,96 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+use self::Expr::*;
+
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+  Number(u32),
+  Binary(Box<Expr>, char, Box<Expr>)
+}
+
+oak! {
+  #![start(expr, value)]
+
+  // The operators of the last level have the highest precedence, `^` is right-associative.
+  expr = infix(number > make_number / "(" expr ")", binary,
+    left(one_of("+-")),
+    left(one_of("*/")),
+    right(one_of("^")))
+
+  number = ["0-9"]+ > to_number
+
+  // A binary function returning `Result` fails the expression, here on a division by zero.
+  value = infix(number / "(" value ")", eval,
+    left(one_of("+-")),
+    left(one_of("*/")))
+
+  fn make_number(n: u32) -> Expr {
+    Number(n)
+  }
+
+  fn binary(lhs: Expr, op: char, rhs: Expr) -> Expr {
+    Binary(Box::new(lhs), op, Box::new(rhs))
+  }
+
+  fn eval(lhs: u32, op: char, rhs: u32) -> Result<u32, String> {
+    match op {
+      '+' => Ok(lhs + rhs),
+      '-' => lhs.checked_sub(rhs).ok_or(format!("negative result")),
+      '*' => Ok(lhs * rhs),
+      _ => lhs.checked_div(rhs).ok_or(format!("division by zero"))
+    }
+  }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    raw_text.into_iter().collect::<String>().parse().unwrap()
+  }
+
+  // fn binary2(lhs: Expr) -> Expr { lhs } // ERROR: `binary2` builds the binary expressions of `infix`, it must have the signature `fn binary2(lhs: T, op: O, rhs: T) -> T`.
+  // bad = infix(number, undeclared, left("+")) // ERROR: `undeclared` must be a function of the grammar.
+  // bad2 = infix(number, eval) // ERROR: `infix(atom, f, left(op), right(op), ...)` must be given [...] at least one level of operators.
+}
+
+fn bin(lhs: Expr, op: char, rhs: Expr) -> Expr {
+  Binary(Box::new(lhs), op, Box::new(rhs))
+}
+
+#[test]
+fn test_precedence() {
+  assert_eq!(full_parse_expr("1+2*3"), Ok(bin(Number(1), '+', bin(Number(2), '*', Number(3)))));
+  assert_eq!(full_parse_expr("1*2+3"), Ok(bin(bin(Number(1), '*', Number(2)), '+', Number(3))));
+  assert_eq!(full_parse_expr("(1+2)*3"), Ok(bin(bin(Number(1), '+', Number(2)), '*', Number(3))));
+  assert_eq!(full_parse_expr("2*3^2"), Ok(bin(Number(2), '*', bin(Number(3), '^', Number(2)))));
+  assert_eq!(full_parse_expr("42"), Ok(Number(42)));
+}
+
+#[test]
+fn test_associativity() {
+  assert_eq!(full_parse_expr("1-2-3"), Ok(bin(bin(Number(1), '-', Number(2)), '-', Number(3))));
+  assert_eq!(full_parse_expr("2^3^2"), Ok(bin(Number(2), '^', bin(Number(3), '^', Number(2)))));
+  assert_eq!(full_parse_expr("1^2^3^4"),
+    Ok(bin(Number(1), '^', bin(Number(2), '^', bin(Number(3), '^', Number(4))))));
+}
+
+#[test]
+fn test_fallible_binary() {
+  assert_eq!(full_parse_value("10-2*3"), Ok(4));
+  assert_eq!(full_parse_value("(7-1)/2/3"), Ok(1));
+  assert!(full_parse_value("1/0").unwrap_err().message.contains("division by zero"));
+  assert!(full_parse_value("1-2").unwrap_err().message.contains("negative result"));
+  assert!(full_parse_expr("1+").is_err());
+}
@@ -0,0 +1,71 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  // The lifetime of the input is named `'src` in the signatures of the generated functions.
+  #![type_aliases]
+  type Stream<'src> = StrStream<'src>;
+
+  program = assignment+
+  assignment = ident "=" expr ";" > make_assignment
+  expr = ident > variable
+       / $["0-9"]+ > number
+  ident = $["a-z"]+
+
+  #[derive(Debug, PartialEq)]
+  pub struct Assignment<'src> {
+    pub name: &'src str,
+    pub value: Expr<'src>
+  }
+
+  #[derive(Debug, PartialEq)]
+  pub enum Expr<'src> {
+    Variable(&'src str),
+    Number(&'src str)
+  }
+
+  fn make_assignment<'src>(name: &'src str, value: Expr<'src>) -> Assignment<'src> {
+    Assignment { name, value }
+  }
+
+  fn variable<'src>(name: &'src str) -> Expr<'src> { Expr::Variable(name) }
+  fn number<'src>(digits: &'src str) -> Expr<'src> { Expr::Number(digits) }
+}
+
+// The AST borrows from the input, which must outlive it.
+fn names<'src>(input: &'src str) -> Vec<&'src str> {
+  let program: ProgramOutput<'src> = full_parse_program(input).unwrap();
+  program.into_iter().map(|assignment| assignment.name).collect()
+}
+
+#[test]
+fn test_borrowed_ast() {
+  let input = String::from("x=1;y=x;");
+  let program = full_parse_program(input.as_str()).unwrap();
+  assert_eq!(program, vec![
+    Assignment { name: "x", value: Expr::Number("1") },
+    Assignment { name: "y", value: Expr::Variable("x") }]);
+  assert_eq!(program[0].name.as_ptr(), input.as_ptr());
+  assert_eq!(names(&input), vec!["x", "y"]);
+}
+
+#[test]
+fn test_rule_function() {
+  let assignment: AssignmentOutput = parse_assignment("a=42;b".into_state()).unwrap_data();
+  assert_eq!(assignment, Assignment { name: "a", value: Expr::Number("42") });
+}
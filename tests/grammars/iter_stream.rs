@@ -0,0 +1,50 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  type Stream = IterStream<std::vec::IntoIter<char>>;
+
+  list = "[" number % ("," "\n"?) "]"
+  number = "0x"i ["0-9a-f"]+ > hex_number
+         / ["0-9"]+ > to_number
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap())
+  }
+
+  fn hex_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().fold(0, |acc, c| acc * 16 + c.to_digit(16).unwrap())
+  }
+}
+
+use oak_runtime::*;
+
+fn iter_stream(input: &str) -> IterStream<std::vec::IntoIter<char>> {
+  IterStream::new(input.chars().collect::<Vec<_>>().into_iter())
+}
+
+#[test]
+fn test_iter_stream() {
+  // The decimal numbers first fail in the first branch of `number`, which backtracks in the buffer of the stream.
+  assert_eq!(full_parse_list(iter_stream("[1,0x10,22]")), Ok(vec![1, 16, 22]));
+}
+
+#[test]
+fn test_iter_stream_error() {
+  let error = full_parse_list(iter_stream("[1,\n2;]")).unwrap_err();
+  assert_eq!(error.location, format!("2:2"));
+  assert_eq!(error.offset, 5);
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(if_kw, if_call, keywords)]
+
+  // `keyword("if")` is `"if"` not followed by an identifier character `["a-zA-Z0-9_"]`.
+  if_kw = keyword("if")
+  if_call = keyword("if") "(" ")"
+  keywords = (keyword("in") / keyword("int"))+
+
+  // if_kw2 = keyword("") // ERROR: `keyword("lit")` must be given a non-empty string literal.
+}
+
+#[test]
+fn test_keyword() {
+  assert!(full_parse_if_kw("if").is_ok());
+  assert!(full_parse_if_call("if()").is_ok());
+  assert!(partial_parse_if_kw("ifx").is_err());
+  assert!(partial_parse_if_kw("if_").is_err());
+  assert!(partial_parse_if_kw("if1").is_err());
+  assert_eq!(partial_parse_if_kw("if(").map(|(_, consumed)| consumed), Ok(2));
+}
+
+#[test]
+fn test_keyword_is_not_a_prefix() {
+  // `keyword("in")` does not match the prefix of `int`, so the second branch is tried.
+  assert_eq!(partial_parse_keywords("int").map(|(_, consumed)| consumed), Ok(3));
+  assert!(full_parse_keywords("inx").is_err());
+}
+
+mod boundary {
+  use oak::oak;
+
+  oak! {
+    // The characters that cannot follow a keyword are the ones of the rule given by `#![keyword_boundary(r)]`.
+    #![keyword_boundary(name_char)]
+    #![spacing(ws)]
+
+    let_stmt = keyword("let") name "=" name
+    name = $(name_char+)
+    name_char = ["a-z-"]
+    ws = [" "]*:(^)
+  }
+
+  #[test]
+  fn test_keyword_boundary() {
+    assert_eq!(full_parse_let_stmt("let x = y"), Ok(("x", "y")));
+    assert!(full_parse_let_stmt("let-x = y").is_err());
+    // `_` is not a character of `name_char`, so `let` is matched but `_x` is not a name.
+    assert!(full_parse_let_stmt("let_x = y").is_err());
+  }
+}
@@ -0,0 +1,100 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![left_recursion]
+
+  expr = expr "+" term > add
+       / expr "-" term > sub
+       / term
+  term = term "*" factor > mul
+       / factor
+  factor = number
+         / "(" expr ")"
+  number = ["0-9"]+ > to_number
+
+  // Indirect left-recursion.
+  a = b "x" / "y"
+  b = a "z"
+
+  use std::str::FromStr;
+
+  fn add(x: i32, y: i32) -> i32 { x + y }
+  fn sub(x: i32, y: i32) -> i32 { x - y }
+  fn mul(x: i32, y: i32) -> i32 { x * y }
+
+  fn to_number(raw_text: Vec<char>) -> i32 {
+    i32::from_str(&*raw_text.into_iter().collect::<String>()).unwrap()
+  }
+}
+
+// With `#![warn(left_recursion)]`, the left-recursive rules are accepted and reported with a warning.
+oak! {
+  #![module(warned)]
+  #![warn(left_recursion)]
+
+  list = list "," item > push
+       / item > single
+  item = ["a-z"]
+
+  fn push(mut list: Vec<char>, item: char) -> Vec<char> {
+    list.push(item);
+    list
+  }
+
+  fn single(item: char) -> Vec<char> { vec![item] }
+}
+
+// With `#![deny(left_recursion)]`, the left-recursive rules are rejected, and `#![left_recursion]` cannot be given.
+// oak! {
+//   #![module(denied)]
+//   #![deny(left_recursion)]
+//
+//   list = list "," item / item   // ERROR: left recursion denied
+//   item = ["a-z"]
+// }
+
+use oak_runtime::*;
+
+fn parse_full(input: &str) -> i32 {
+  let state = parse_expr(input.into_state());
+  assert!(!state.current.has_next(), "`{}` is not fully parsed.", input);
+  state.unwrap_data()
+}
+
+#[test]
+fn test_left_associativity() {
+  assert_eq!(parse_full("10-2-3"), 5);
+  assert_eq!(parse_full("2*3+4*5"), 26);
+  assert_eq!(parse_full("2*(3+4)*5"), 70);
+  assert_eq!(parse_full("7"), 7);
+}
+
+#[test]
+fn test_indirect_left_recursion() {
+  for input in &["y", "yzx", "yzxzx"] {
+    let state = recognize_a((*input).into_state());
+    assert!(state.is_successful());
+    assert!(!state.current.has_next(), "`{}` is not fully recognized.", input);
+  }
+  let state = recognize_a("yz".into_state());
+  assert!(state.current.has_next());
+}
+
+#[test]
+fn test_warned_left_recursion() {
+  assert_eq!(warned::full_parse_list("a,b,c"), Ok(vec!['a', 'b', 'c']));
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(assignment, pair, sum, shadow, assignment_ws)]
+
+  assignment = let ws = [" \t"]*:(^) in ws ident ws "=" ws number ws
+
+  // The same name in another rule is a distinct hidden rule.
+  pair = let ws = " "*:(^) in "(" ws number ws "," ws number ws ")"
+
+  // The bindings can be nested, the body extends over the whole choice.
+  sum = let digit = ["0-9"] in let num = $(digit+) > to_number in num "+" num > add / num
+
+  // In the bound expression, `number` still refers to the rule `number`.
+  shadow = let number = number "!" in number
+
+  // The hidden rule of `ws` in `assignment` is not named `assignment_ws` since this rule exists.
+  assignment_ws = "x"
+
+  // unbound = (let x = "a" in x) x     // ERROR: unknown rule `x`, it is only bound in the body of the let.
+
+  ident = $["a-z"]+
+  number = $["0-9"]+
+
+  fn to_number(raw: &str) -> u32 {
+    raw.parse().unwrap()
+  }
+
+  fn add(x: u32, y: u32) -> u32 {
+    x + y
+  }
+}
+
+#[test]
+fn test_let_binding() {
+  assert_eq!(full_parse_assignment(" x =  12 "), Ok(("x", "12")));
+  assert_eq!(full_parse_assignment("x=12"), Ok(("x", "12")));
+  assert_eq!(full_parse_pair("( 1, 2 )"), Ok(("1", "2")));
+}
+
+#[test]
+fn test_nested_let_binding() {
+  assert_eq!(full_parse_sum("1+22"), Ok(23));
+  assert_eq!(full_parse_sum("7"), Ok(7));
+}
+
+#[test]
+fn test_let_scope() {
+  assert_eq!(full_parse_shadow("12!"), Ok("12"));
+  assert!(full_parse_shadow("12").is_err());
+  assert_eq!(full_parse_assignment_ws("x"), Ok(()));
+}
@@ -0,0 +1,48 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(operator, word)]
+
+  operator = longest(assign / equal / arrow)
+  assign = "=" > assign_op
+  equal = "==" > equal_op
+  arrow = "=>" > arrow_op
+
+  word = longest($"if" / $(["a-z"]+))
+
+  // bad = longest("if")     // ERROR: `longest` expects a choice `e1 / e2 / ...`.
+
+  #[derive(Debug, PartialEq)]
+  pub enum Op { Assign, Equal, Arrow }
+
+  fn assign_op() -> Op { Op::Assign }
+  fn equal_op() -> Op { Op::Equal }
+  fn arrow_op() -> Op { Op::Arrow }
+}
+
+#[test]
+fn test_longest_choice() {
+  // An ordered choice would always take `"="`.
+  assert_eq!(parse_operator("==".into_state()).unwrap_data(), Op::Equal);
+  assert_eq!(parse_operator("=>".into_state()).unwrap_data(), Op::Arrow);
+  assert_eq!(parse_operator("=".into_state()).unwrap_data(), Op::Assign);
+  assert!(parse_operator("!=".into_state()).is_failed());
+  assert_eq!(parse_word("iffy".into_state()).unwrap_data(), "iffy");
+  // The first branch is taken when several branches consume the same input.
+  assert_eq!(parse_word("if".into_state()).unwrap_data(), "if");
+}
@@ -0,0 +1,45 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  // Only `term` is memoized, it is enough to recognize the nested expressions in linear time.
+  #![memoize(term)]
+
+  expr = term "+" expr
+       / term "-" expr
+       / term
+  term = atom "*" term
+       / atom
+  atom = ["0-9"]+:(^)
+       / "(" expr ")"
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_memoize() {
+  let depth = 40;
+  let mut input = String::new();
+  for _ in 0..depth { input.push('('); }
+  input.push('1');
+  for _ in 0..depth { input.push(')'); }
+  let state = recognize_expr(input.as_str().into_state());
+  assert!(state.is_successful());
+  assert!(!state.current.has_next());
+  // At most one entry per position since a single rule is memoized.
+  assert!(state.memo().map_or(0, |memo| memo.len()) > 0);
+  assert!(state.memo().map_or(0, |memo| memo.len()) <= input.len() + 1);
+}
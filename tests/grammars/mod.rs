@@ -25,5 +25,57 @@ pub mod well_formedness;
 pub mod useless_chaining;
 pub mod unreachable_rule;
 pub mod issue94;
+mod packrat;
+mod recognizer;
+mod left_recursion;
+mod unused_rule;
 mod stream_span;
+mod spanned;
+mod byte_stream;
+mod error_label;
+mod cut;
+mod from_str;
+mod no_std;
 mod typing;
+mod spacing;
+mod fallible_action;
+mod iter_stream;
+mod capture;
+mod trace;
+mod boxed_expr;
+mod custom_combinator;
+mod parametric_rule;
+mod depth_limit;
+mod type_aliases;
+mod longest_choice;
+mod guard;
+mod named_expr;
+mod builtin_class;
+mod recover;
+mod memoize;
+mod visitor;
+mod repeat_until_eoi;
+mod module;
+mod token_stream;
+mod rule_function;
+mod end_of_input;
+mod doc_comment;
+mod separated_nonempty;
+mod duplicate;
+mod fixed_width;
+mod diagnostics_json;
+mod committed;
+mod input_lifetime;
+mod let_binding;
+mod any_single_char;
+mod one_of;
+mod byte_class;
+mod entry_rule;
+mod keyword;
+mod trivial_rules;
+mod count;
+mod include;
+mod cst;
+mod parse_all;
+mod infix;
+mod closure_action;
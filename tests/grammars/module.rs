@@ -0,0 +1,46 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+// Both grammars declare the rule `number`, their generated items are declared in distinct modules.
+oak! {
+  #![module(decimal)]
+
+  number = ["0-9"]+ > to_number
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    raw_text.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+oak! {
+  #![module(hexadecimal)]
+  #![type_aliases]
+
+  number = "0x" ["0-9a-f"]+ > to_number
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    u32::from_str_radix(&raw_text.into_iter().collect::<String>(), 16).unwrap()
+  }
+}
+
+#[test]
+fn test_module() {
+  assert_eq!(decimal::parse_number("42".into_state()).unwrap_data(), 42);
+  let n: hexadecimal::NumberOutput = hexadecimal::parse_number("0x2a".into_state()).unwrap_data();
+  assert_eq!(n, 42);
+  assert!(hexadecimal::recognize_number("42".into_state()).is_failed());
+}
@@ -0,0 +1,58 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  // The values are passed to `make_date` according to their names, not their positions.
+  date = iso_date / us_date
+  iso_date = year@number "-" month@number "-" day@number > make_date
+  us_date = month@number "/" day@number "/" year@number > make_date
+  // The unnamed values are passed in order to the other parameters.
+  range = "[" hi@number ".." number "]" > make_range
+  // A misspelled name gives its value to another parameter, which is reported.
+  // typo_date = year@number "-" month@number "-" dya@number > make_date // ERROR: no value is named `day` for the parameter `day` of the semantic action `make_date`, it would take the value named `dya` instead.
+  // The parameters must also be named like the values, even if the values are in order.
+  // typo_range = "[" lo@number ".." high@number "]" > make_range // ERROR: no value is named `hi` for the parameter `hi` of the semantic action `make_range`, it would take the value named `high` instead.
+  // A named expression can be used in a guard.
+  small = n@number && { n < 10 }
+
+  number = ["0-9"]+ > to_number
+
+  #[derive(Debug, PartialEq)]
+  pub struct Date { year: u32, month: u32, day: u32 }
+
+  fn make_date(year: u32, month: u32, day: u32) -> Date {
+    Date { year, month, day }
+  }
+
+  fn make_range(lo: u32, hi: u32) -> (u32, u32) {
+    (lo, hi)
+  }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    raw_text.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+#[test]
+fn test_named_expr() {
+  let expected = Date { year: 2020, month: 4, day: 23 };
+  assert_eq!(parse_date("2020-04-23".into_state()).unwrap_data(), expected);
+  assert_eq!(parse_date("04/23/2020".into_state()).unwrap_data(), expected);
+  assert_eq!(parse_range("[9..1]".into_state()).unwrap_data(), (1, 9));
+  assert_eq!(parse_small("7".into_state()).unwrap_data(), 7);
+  assert!(parse_small("12".into_state()).is_failed());
+}
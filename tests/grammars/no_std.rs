@@ -0,0 +1,60 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![no_std]
+
+  // The generated code uses `alloc::vec::Vec` and `alloc::boxed::Box`.
+  list = "[" number % "," "]"
+  number = ["0-9"]+ > to_number
+  nested = "(" nested ")" > box node / "x" > box leaf
+
+  pub enum Nested {
+    Leaf,
+    Node(Box<Nested>)
+  }
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap())
+  }
+
+  fn node(nested: Box<Nested>) -> Nested {
+    Nested::Node(nested)
+  }
+
+  fn leaf() -> Nested {
+    Nested::Leaf
+  }
+}
+
+use oak_runtime::*;
+
+fn depth(nested: Box<Nested>) -> usize {
+  match *nested {
+    Nested::Leaf => 0,
+    Nested::Node(nested) => 1 + depth(nested)
+  }
+}
+
+#[test]
+fn test_no_std_grammar() {
+  let state = parse_list("[1,22,333]".into_state());
+  assert!(!state.current.has_next());
+  assert_eq!(state.unwrap_data(), vec![1, 22, 333]);
+  let state = parse_nested("((x))".into_state());
+  assert!(!state.current.has_next());
+  assert_eq!(depth(state.unwrap_data()), 2);
+}
@@ -0,0 +1,48 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(expression, operator, sign)]
+
+  expression = number (operator number)*
+  number = $["0-9"]+
+
+  // More than 8 characters, the class is searched by dichotomy.
+  operator = one_of("+-*/%^&|<>=!~")
+  // `-` is a character, not an interval.
+  sign = one_of("+-")+
+
+  // bad = one_of("")     // ERROR: one_of must be given a non-empty string literal.
+}
+
+#[test]
+fn test_one_of() {
+  assert_eq!(full_parse_operator("%"), Ok('%'));
+  assert_eq!(full_parse_operator("~"), Ok('~'));
+  assert!(full_parse_operator("a").is_err());
+  assert!(full_parse_operator("?").is_err());
+  assert_eq!(full_parse_sign("+-+"), Ok(vec!['+', '-', '+']));
+  assert!(full_parse_sign(",").is_err());
+}
+
+#[test]
+fn test_operator_expression() {
+  assert_eq!(full_parse_expression("1+2*3<4"), Ok(("1", vec![('+', "2"), ('*', "3"), ('<', "4")])));
+  // `<=` is not an operator of the expression.
+  assert!(full_parse_expression("1<=2").is_err());
+}
@@ -0,0 +1,65 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![packrat]
+
+  expr = term "+" expr > add
+       / term "-" expr > sub
+       / term
+  term = atom "*" term > mul
+       / atom
+  atom = number
+       / "(" expr ")"
+  number = ["0-9"]+ > to_number
+
+  use std::str::FromStr;
+
+  fn add(x: u32, y: u32) -> u32 { x + y }
+  fn sub(x: u32, y: u32) -> u32 { x - y }
+  fn mul(x: u32, y: u32) -> u32 { x * y }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    u32::from_str(&*raw_text.into_iter().collect::<String>()).unwrap()
+  }
+}
+
+use oak_runtime::*;
+
+fn nested(depth: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..depth { input.push('('); }
+  input.push('1');
+  for _ in 0..depth { input.push(')'); }
+  input
+}
+
+#[test]
+fn test_packrat_recognizer() {
+  // Without memoization, `atom` would be called 6^40 times.
+  let input = nested(40);
+  let state = recognize_expr(input.as_str().into_state());
+  assert!(state.is_successful());
+  assert!(!state.current.has_next());
+  assert!(state.memo().map_or(0, |memo| memo.len()) > 0);
+}
+
+#[test]
+fn test_packrat_parser() {
+  let input = "2*(3+1)-4";
+  let state = parse_expr(input.into_state());
+  assert_eq!(state.unwrap_data(), 4);
+}
@@ -0,0 +1,48 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  numbers = list<number>
+  idents = "[" list<ident> "]"
+  pairs = list<pair<ident, number>>
+
+  list<elem> = elem ("," elem)*
+  pair<key, value> = key "=" value
+
+  number: u32 = ["0-9"]+ > to_number
+  ident: String = ["a-z"]+ > to_string
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().fold(0, |acc, d| acc * 10 + d.to_digit(10).unwrap())
+  }
+
+  fn to_string(chars: Vec<char>) -> String {
+    chars.into_iter().collect()
+  }
+}
+
+#[test]
+fn test_parametric_rule() {
+  assert_eq!(parse_numbers("1,22,3".into_state()).unwrap_data(), (1, vec![22, 3]));
+  assert_eq!(parse_idents("[a,bc]".into_state()).unwrap_data(), (String::from("a"), vec![String::from("bc")]));
+  // The tuple of the first pair is flattened into the tuple of `list`.
+  assert_eq!(parse_pairs("a=1,b=2".into_state()).unwrap_data(),
+    (String::from("a"), 1, vec![(String::from("b"), 2)]));
+  // Each call is expanded into its own rule.
+  assert_eq!(parse_list_number("4".into_state()).unwrap_data(), (4, vec![]));
+  assert!(recognize_pair_ident_number("x=0".into_state()).is_successful());
+}
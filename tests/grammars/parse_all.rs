@@ -0,0 +1,64 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  // A log with one record `name=value` per line.
+  #[start]
+  record = name "=" number "\n" > make_record
+  name = $["a-z"]+
+  number = $["0-9"]+ > to_number
+
+  // all = "x" // ERROR: the parser `parse_all` of this rule conflicts with the function `parse_all` generated for the entry rule `record` declared with `#[start]`.
+
+  // fn parse_all() {} // ERROR: the function `parse_all` is generated for the entry rule `record` declared with `#[start]`.
+
+  fn make_record(name: &str, value: u32) -> (String, u32) {
+    (name.to_string(), value)
+  }
+
+  fn to_number(digits: &str) -> u32 {
+    digits.parse().unwrap()
+  }
+}
+
+#[test]
+fn test_parse_all() {
+  let records: Vec<_> = parse_all("a=1\nbc=22\nd=333\n").collect();
+  assert_eq!(records, vec![
+    Ok((format!("a"), 1)),
+    Ok((format!("bc"), 22)),
+    Ok((format!("d"), 333))]);
+  assert_eq!(parse_all("").next(), None);
+}
+
+#[test]
+fn test_parse_all_error() {
+  let mut records = iter_parse_record("a=1\nb=x\nc=3\n");
+  assert_eq!(records.next(), Some(Ok((format!("a"), 1))));
+  let error = records.next().unwrap().unwrap_err();
+  assert_eq!(error.line_col("a=1\nb=x\nc=3\n"), (2, 3));
+  // The iteration stops after an error.
+  assert_eq!(records.next(), None);
+}
+
+#[test]
+fn test_parse_all_lazy() {
+  // The records before the error are yielded before the error is read.
+  let first: Vec<_> = parse_all("a=1\nb=2\n???").take(2).collect();
+  assert_eq!(first.len(), 2);
+  assert!(first.iter().all(|record| record.is_ok()));
+}
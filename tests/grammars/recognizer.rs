@@ -0,0 +1,41 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![recognizer(date)]
+
+  entry = name ":" date
+  name = ["a-z"]+ > to_string
+  date = number "-" number "-" number
+  number = ["0-9"]+
+
+  fn to_string(raw_text: Vec<char>) -> String {
+    raw_text.into_iter().collect()
+  }
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_recognizer_rule() {
+  // `date` has the unit type, so it does not contribute to the value of `entry`.
+  let state = parse_entry("oak:2020-04-17".into_state());
+  assert!(!state.current.has_next());
+  assert_eq!(state.unwrap_data(), format!("oak"));
+  let state = recognize_date("2020-04-17".into_state());
+  assert!(state.is_successful());
+  assert!(!state.current.has_next());
+}
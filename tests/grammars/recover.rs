@@ -0,0 +1,58 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(stmts, block)]
+
+  stmts = recover(stmt, ";")* eoi
+  stmt = $(["a-z"]+ "=" ["0-9"]+) ";"
+
+  // The first branch recovers from the error of `stmt` before failing on `!`.
+  block = (recover(stmt, ";") "!"):(^) / (["a-z"]+ ";" ["a-z"]+):(^)
+}
+
+#[test]
+fn test_recover() {
+  let state = parse_stmts("a=1;b=;c=2;".into_state());
+  assert!(state.is_successful());
+  assert_eq!(state.recovered_errors().len(), 1);
+  assert_eq!(state.unwrap_data(), vec![Some("a=1"), None, Some("c=2")]);
+
+  let state = parse_stmts("a;b=2;=;".into_state());
+  assert_eq!(state.recovered_errors().len(), 2);
+  assert_eq!(state.unwrap_data(), vec![None, Some("b=2"), None]);
+
+  // Without a synchronization point, the error of the statement is reported.
+  let state = parse_stmts("a=1;b=".into_state());
+  assert!(state.is_failed());
+  assert!(state.recovered_errors().is_empty());
+}
+
+#[test]
+fn test_recover_in_backtracked_branch() {
+  let state = recognize_block("a;b".into_state());
+  assert!(state.is_successful());
+  assert!(state.recovered_errors().is_empty());
+
+  let state = recognize_block("a=1;!".into_state());
+  assert!(state.is_successful());
+  assert!(state.recovered_errors().is_empty());
+
+  let state = recognize_block("a;!".into_state());
+  assert!(state.is_successful());
+  assert_eq!(state.recovered_errors().len(), 1);
+}
@@ -0,0 +1,33 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  items = repeat_until_eoi(item)
+  item = $["a-z"]+ ";"
+}
+
+#[test]
+fn test_repeat_until_eoi() {
+  assert_eq!(parse_items("ab;cd;".into_state()).unwrap_data(), vec!["ab", "cd"]);
+  assert_eq!(parse_items("".into_state()).unwrap_data(), Vec::<&str>::new());
+  // The malformed item in the middle fails the whole expression at its position.
+  let state = parse_items("ab;c3;d;".into_state());
+  assert!(state.is_failed());
+  let error = state.into_full_result().unwrap_err();
+  assert_eq!(error.offset, 4);
+  assert!(error.message.contains("`;`"));
+}
@@ -0,0 +1,61 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  sum = number ("+" number)* > add
+  number = ["0-9"]+ > to_number
+
+  fn add(x: u32, rest: Vec<u32>) -> u32 {
+    rest.into_iter().fold(x, |acc, y| acc + y)
+  }
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+// A hand-written parser of `number ("*" number)*` calling the rule `number` of the grammar.
+fn parse_product<'a>(state: ParseState<StrStream<'a>, ()>) -> ParseState<StrStream<'a>, u32> {
+  let state = parse_number(state);
+  if state.is_failed() {
+    return state;
+  }
+  let (mut state, mut product) = state.extract_data();
+  while state.consume_prefix("*") {
+    let next = parse_number(state);
+    if next.is_failed() {
+      return next;
+    }
+    let (next, n) = next.extract_data();
+    state = next;
+    product *= n;
+  }
+  state.success(product)
+}
+
+#[test]
+fn test_sub_rule() {
+  // `number` is not a start rule but its function can be called on its own, it consumes `42`.
+  assert_eq!(parse_number("42+1".into_state()).into_partial_result(), Ok((42, 2)));
+  assert!(recognize_number("+1".into_state()).is_failed());
+}
+
+#[test]
+fn test_hand_written_parser() {
+  assert_eq!(parse_product("2*3*7+1".into_state()).into_partial_result(), Ok((42, 5)));
+  assert!(parse_product("2*".into_state()).is_failed());
+}
@@ -0,0 +1,58 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![start(row, lenient_row)]
+
+  row = separated_nonempty(number, ",")
+
+  // For comparison, the trailing separator of `e %% sep` is not consumed.
+  lenient_row = number %% "," ","?
+
+  number = ["0-9"]+ > to_number
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+#[test]
+fn test_row() {
+  assert_eq!(full_parse_row("1,22,333"), Ok(vec![1, 22, 333]));
+  assert!(full_parse_row("1,,2").is_err());
+}
+
+#[test]
+fn test_empty_input() {
+  assert_eq!(full_parse_row("").unwrap_err().offset, 0);
+}
+
+#[test]
+fn test_single_element() {
+  assert_eq!(full_parse_row("42"), Ok(vec![42]));
+}
+
+#[test]
+fn test_trailing_separator() {
+  // The error is located just after the trailing separator, where a number is expected.
+  let error = full_parse_row("1,2,").unwrap_err();
+  assert_eq!(error.offset, 4);
+  assert!(error.message.contains("expecting"));
+  assert!(parse_row("1,2,".into_state()).is_failed());
+  assert_eq!(full_parse_lenient_row("1,2,"), Ok(vec![1, 2]));
+}
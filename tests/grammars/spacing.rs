@@ -0,0 +1,100 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![spacing(spacing)]
+
+  // The spacing rule is called between the elements of every sequence, but not inside `strict(e)`.
+  program = spacing assignment
+  assignment = "let" identifier "=" number ";" > make_assignment
+  identifier = strict(["a-z"] ["a-z0-9_"]*) > make_identifier
+  number = ["0-9"]+ > to_number
+  spacing = [" \n\t"]*:(^)
+
+  fn make_assignment(name: String, value: u32) -> (String, u32) {
+    (name, value)
+  }
+
+  fn make_identifier(first: char, rest: Vec<char>) -> String {
+    Some(first).into_iter().chain(rest.into_iter()).collect()
+  }
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap())
+  }
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_implicit_spacing() {
+  for input in &["let x1 = 12;", "  let x1=12 ;", "let\n  x1\t=\n12\n;"] {
+    let state = parse_program(input.into_state());
+    assert!(!state.current.has_next(), "input `{}` not entirely consumed.", input);
+    assert_eq!(state.unwrap_data(), (format!("x1"), 12));
+  }
+}
+
+#[test]
+fn test_strict_expression() {
+  // No spacing is allowed inside the identifier.
+  let state = parse_program("let x 1 = 12;".into_state());
+  assert!(state.is_failed());
+}
+
+mod expression {
+  use oak::oak;
+
+  oak! {
+    // A token-based expression grammar: the spacing rule skips the whitespaces and the comments between the tokens.
+    #![spacing(skip_ws)]
+
+    expr = skip_ws sum skip_ws
+    sum = product (sum_op product)* > fold_ops
+    product = atom (product_op atom)* > fold_ops
+    atom = number / "(" sum ")"
+    sum_op = "+" > plus / "-" > minus
+    product_op = "*" > times
+    number = strict(["0-9"]+) > to_number
+    skip_ws = ([" \n\t"] / strict("#" (!"\n" .)*))*:(^)
+
+    fn plus() -> char { '+' }
+    fn minus() -> char { '-' }
+    fn times() -> char { '*' }
+
+    fn to_number(digits: Vec<char>) -> i64 {
+      digits.into_iter().fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap() as i64)
+    }
+
+    fn fold_ops(first: i64, rest: Vec<(char, i64)>) -> i64 {
+      rest.into_iter().fold(first, |acc, (op, x)| match op {
+        '+' => acc + x,
+        '-' => acc - x,
+        _ => acc * x
+      })
+    }
+  }
+
+  #[test]
+  fn test_whitespace_insensitive_expression() {
+    for input in &["1+2*3", " 1 + 2 * 3 ", "1\n+\t2 # two\n* 3", "(1)+((2 )*3)"] {
+      assert_eq!(full_parse_expr(*input), Ok(7), "input `{}`", input);
+    }
+    assert_eq!(full_parse_expr("(1 + 2) * 3 - 4"), Ok(5));
+    // The digits of a number cannot be separated.
+    assert!(full_parse_expr("1 2 + 3").is_err());
+  }
+}
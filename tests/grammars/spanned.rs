@@ -0,0 +1,48 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+use oak_runtime::*;
+
+oak! {
+  #![start(ident, idents, end_span)]
+
+  // `spanned(e)` gives the range of bytes matched by `e` along with its value.
+  ident = spanned(["a-zé"]+) > make_ident
+  idents = (" "* ident)+
+  end_span = "a" spanned(eoi)
+
+  #[derive(Debug, PartialEq)]
+  pub struct Ident {
+    pub span: Range<usize>,
+    pub name: String
+  }
+
+  fn make_ident(span: Range<usize>, name: Vec<char>) -> Ident {
+    Ident { span, name: name.into_iter().collect() }
+  }
+}
+
+#[test]
+fn test_spanned() {
+  assert_eq!(parse_ident("abc".into_state()).unwrap_data(), Ident { span: 0..3, name: format!("abc") });
+  let idents = parse_idents("été  a".into_state()).unwrap_data();
+  assert_eq!(idents[0].span, 0..5);
+  assert_eq!(idents[1].span, 7..8);
+}
+
+#[test]
+fn test_spanned_end_of_input() {
+  assert_eq!(parse_end_span("a".into_state()).unwrap_data(), 1..1);
+}
@@ -27,6 +27,13 @@ oak! {
 
   expr3 = .. ("a" / "b") "c"
 
+  // Spans are computed in bytes.
+  bytes_span = "é" (.. "èa" "ê")
+
+  end_span = "a" (.. eoi)
+
+  range = "é" (... "è" .)
+
   pub struct Expr {
     pub full_sp: Span,
     pub span_a: Span,
@@ -62,6 +69,14 @@ fn test_state_expr3<'a>(state: ParseState<StrStream<'a>, Span>) {
   assert_eq!(state.unwrap_data(), make_span(0,2))
 }
 
+#[test]
+fn test_bytes_span() {
+  assert_eq!(parse_bytes_span("éèaê".into_state()).unwrap_data(), make_span(2, 7));
+  assert_eq!(parse_end_span("a".into_state()).unwrap_data(), make_span(1, 1));
+  let range = parse_range("éèa".into_state()).unwrap_data();
+  assert_eq!(range.start.bytes_offset()..range.end.bytes_offset(), 2..5);
+}
+
 #[test]
 fn test_stream_span() {
   let state = parse_expr("abcb".into_state());
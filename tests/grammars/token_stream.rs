@@ -0,0 +1,111 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+// A lexer produces the tokens from the characters, which are then parsed by a second grammar.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+  Num(u32),
+  Plus,
+  Minus,
+  Times,
+  LParen,
+  RParen
+}
+
+impl MatchLiteral for Token {
+  fn match_literal(&self, literal: &str) -> bool {
+    match (self, literal) {
+        (&Token::Plus, "+")
+      | (&Token::Minus, "-")
+      | (&Token::Times, "*")
+      | (&Token::LParen, "(")
+      | (&Token::RParen, ")") => true,
+      _ => false
+    }
+  }
+}
+
+oak! {
+  #![module(lexer)]
+
+  tokens = spacing (token spacing)*
+  token = ["0-9"]+ > number
+        / "+" > plus
+        / "-" > minus
+        / "*" > times
+        / "(" > lparen
+        / ")" > rparen
+  spacing = [" \n\t"]*:(^)
+
+  fn number(digits: Vec<char>) -> Token {
+    Token::Num(digits.into_iter().collect::<String>().parse().unwrap())
+  }
+
+  fn plus() -> Token { Token::Plus }
+  fn minus() -> Token { Token::Minus }
+  fn times() -> Token { Token::Times }
+  fn lparen() -> Token { Token::LParen }
+  fn rparen() -> Token { Token::RParen }
+}
+
+oak! {
+  #![module(parser)]
+
+  type Stream<'a> = SliceStream<'a, Token>;
+  type Atom = Token;
+
+  sum = product (token(Token::Plus | Token::Minus) product)* > fold_sum
+  product = factor ("*" factor)* > fold_product
+  factor = token(Token::Num(_)) > value
+         / "(" sum ")"
+
+  fn value(token: Token) -> i32 {
+    match token {
+      Token::Num(n) => n as i32,
+      _ => unreachable!("`factor` only matches numbers.")
+    }
+  }
+
+  fn fold_sum(first: i32, rest: Vec<(Token, i32)>) -> i32 {
+    rest.into_iter().fold(first, |acc, (op, x)| if op == Token::Plus { acc + x } else { acc - x })
+  }
+
+  fn fold_product(first: i32, rest: Vec<i32>) -> i32 {
+    rest.into_iter().fold(first, |acc, x| acc * x)
+  }
+}
+
+fn eval(input: &str) -> Result<i32, ParseError> {
+  let tokens = lexer::full_parse_tokens(input)?;
+  parser::full_parse_sum(SliceStream::new(&tokens))
+}
+
+#[test]
+fn test_token_stream() {
+  assert_eq!(lexer::full_parse_tokens("1 + (2)"),
+    Ok(vec![Token::Num(1), Token::Plus, Token::LParen, Token::Num(2), Token::RParen]));
+  assert_eq!(eval("1 + 2 * 3"), Ok(7));
+  assert_eq!(eval("10 - (2 + 3) * 2"), Ok(0));
+}
+
+#[test]
+fn test_token_stream_error() {
+  // The offset of the error is the index of the token.
+  let error = eval("1 + * 2").unwrap_err();
+  assert_eq!(error.offset, 2);
+  assert_eq!(error.location, format!("token 2"));
+}
@@ -0,0 +1,55 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![trace]
+
+  sum = number "+" number
+  number = ["0-9"]+
+}
+
+use oak_runtime::*;
+use oak_runtime::trace::{capture_trace, record_trace};
+
+#[test]
+fn test_trace() {
+  let trace = capture_trace(|| {
+    let state = parse_sum("1+a".into_state());
+    assert!(state.is_failed());
+  });
+  assert_eq!(trace,
+    "parse_sum at 1:1\n\
+    \x20 parse_number at 1:1\n\
+    \x20 parse_number succeeded at 1:2\n\
+    \x20 parse_number at 1:3\n\
+    \x20 parse_number failed\n\
+    parse_sum failed\n");
+}
+
+#[test]
+fn test_record_trace() {
+  let (state, roots) = record_trace(|| parse_sum("1+a".into_state()));
+  assert!(state.is_failed());
+  assert_eq!(roots.len(), 1);
+  assert!(!roots[0].is_successful());
+  let last_call = roots[0].children.last().unwrap();
+  assert_eq!(last_call.rule, "parse_number");
+  assert_eq!(last_call.start, "1:3");
+  assert_eq!(format!("{}", roots[0]),
+    "parse_sum at 1:1: failed\n\
+    \x20 parse_number at 1:1: succeeded at 1:2\n\
+    \x20 parse_number at 1:3: failed\n");
+}
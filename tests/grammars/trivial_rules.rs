@@ -0,0 +1,43 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![warn(trivial_rules)]
+  #![start(args, nested, comma)]
+  #![spacing(ws)]
+
+  // `lparen` and `rparen` only match a literal, but they are called often enough to keep their names.
+  args = lparen (arg % comma) rparen
+  nested = lparen lparen lparen rparen rparen rparen
+  arg = ["a-z"]+
+  lparen = "("
+  rparen = ")"
+  // A trivial start rule or spacing rule is not reported since it cannot be inlined.
+  comma = ","
+  ws = [" "]*:(^)
+
+  // number = digit_char+
+  // digit_char = ["0-9"]  // Warning: rule `digit_char` only matches `["0-9"]` and is called 1 time(s).
+}
+
+#[test]
+fn test_trivial_rules() {
+  assert_eq!(full_parse_args("(a,bc)"), Ok(vec![vec!['a'], vec!['b', 'c']]));
+  assert!(full_parse_nested("((( )))").is_ok());
+  assert!(full_parse_comma(",").is_ok());
+}
@@ -0,0 +1,40 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![type_aliases]
+
+  pair = number "," word
+  number = ["0-9"]+ > to_number
+  word = $["a-z"]+
+  comma_list = "," number_list
+  number_list = number % ","
+
+  fn to_number(digits: Vec<char>) -> u32 {
+    digits.into_iter().fold(0, |acc, d| acc * 10 + d.to_digit(10).unwrap())
+  }
+}
+
+#[test]
+fn test_type_aliases() {
+  let pair: PairOutput = parse_pair("12,ab".into_state()).unwrap_data();
+  assert_eq!(pair, (12, "ab"));
+  let number: NumberOutput = 3;
+  assert_eq!(number, 3u32);
+  let list: NumberListOutput = parse_comma_list(",1,2".into_state()).unwrap_data();
+  assert_eq!(list, vec![1, 2]);
+}
@@ -29,11 +29,84 @@ oak! {
   // rule2 = "a" > test3 // Fail due to unit type
   // rule3 = "a" > test4 // Fail due to unit type
 
+  // rule6 = identifier number > test5   // ERROR: `test5` takes 1 parameter but its expression produces 2 values
+  rule7 = identifier "=" number > test6
+
   type MyUnit = ();
   fn test2() -> MyUnit {}
   fn test3() -> () {}
   fn test4() {}
+  fn test5(x: Vec<char>) -> Vec<char> { x }
+  fn test6(x: Vec<char>, _y: Vec<char>) -> Vec<char> { x }
 
   rule4 = r#""foo""#
   rule5 = r##"foo #"# bar"##
+
+  // `&e` and its alias `peek(e)` consume nothing and produce no value, whatever the type of `e`.
+  peek1 = &identifier identifier
+  peek2 = peek(identifier) identifier
+  peek3 = peek(number)
+
+  // A repetition ascribed with a Rust type collects its values into this type instead of a `Vec`.
+  word1 = ["a-z"]+:String
+  word2: String = ["a-z"]*
+  letters = ["a-z"]{2,3}:std::collections::BTreeSet<char>
+
+  // An optional expression of type `(^)` is typed `(^)`, but it is typed `Option<()>` if `e` is typed `()` because it tells whether `e` matched.
+  opt1 = ("x" !"y")? number
+  opt2 = (&"x" identifier)? number
+  opt3 = unit_rule? number
+  unit_rule = "x":()
+
+  // `skip(e)` is typed `()` and removes the value of `e` from the enclosing tuple, whatever the type of `e`.
+  skip1 = skip(ws) number skip(ws)
+  skip2 = identifier skip("=") skip(number ",") number
+  skip3 = skip(identifier)
+  ws = [" "]*
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_peek_type() {
+  let state = parse_peek1("ab".into_state());
+  assert_eq!(state.unwrap_data(), vec!['a', 'b']);
+  let state = parse_peek2("ab".into_state());
+  assert_eq!(state.unwrap_data(), vec!['a', 'b']);
+  let state = parse_peek3("1".into_state());
+  assert!(state.current.has_next());
+  let () = state.unwrap_data();
+}
+
+#[test]
+fn test_repeat_into_container() {
+  let word: String = parse_word1("abc".into_state()).unwrap_data();
+  assert_eq!(word, "abc");
+  let word: String = parse_word2("".into_state()).unwrap_data();
+  assert_eq!(word, "");
+  let letters = parse_letters("bab".into_state()).unwrap_data();
+  assert_eq!(letters.into_iter().collect::<Vec<_>>(), vec!['a', 'b']);
+}
+
+#[test]
+fn test_unit_optional_type() {
+  let digits: Vec<char> = parse_opt1("x1".into_state()).unwrap_data();
+  assert_eq!(digits, vec!['1']);
+  let (_, digits): (Option<Vec<char>>, Vec<char>) = parse_opt2("1".into_state()).unwrap_data();
+  assert_eq!(digits, vec!['1']);
+  let (matched, digits): (Option<()>, Vec<char>) = parse_opt3("x1".into_state()).unwrap_data();
+  assert_eq!(matched, Some(()));
+  assert_eq!(digits, vec!['1']);
+}
+
+#[test]
+fn test_skip_type() {
+  let digits: Vec<char> = parse_skip1(" 12 ".into_state()).unwrap_data();
+  assert_eq!(digits, vec!['1', '2']);
+  let (name, digits): (Vec<char>, Vec<char>) = parse_skip2("x=1,2".into_state()).unwrap_data();
+  assert_eq!(name, vec!['x']);
+  assert_eq!(digits, vec!['2']);
+  let state = parse_skip3("abc".into_state());
+  assert!(!state.current.has_next());
+  let () = state.unwrap_data();
 }
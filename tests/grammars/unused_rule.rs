@@ -0,0 +1,53 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak::oak;
+
+oak! {
+  #![start(list, pair)]
+
+  list = "[" item % "," "]"
+  item = ["a-z"]+
+
+  pair = "(" item "," item ")"
+
+  // unused1 = "a" unused2     // Warning: unused rule
+  // unused2 = "b" unused1 / "b"  // Warning: unused rule
+}
+
+use oak_runtime::*;
+
+#[test]
+fn test_start_rules() {
+  assert!(recognize_list("[a,bc]".into_state()).is_successful());
+  assert!(recognize_pair("(a,b)".into_state()).is_successful());
+}
+
+#[test]
+fn test_start_rules_entry_functions() {
+  // Each start rule has its own entry function which checks that the full input is consumed.
+  assert_eq!(full_parse_list("[a,bc]"), Ok(vec![vec!['a'], vec!['b', 'c']]));
+  assert_eq!(full_parse_pair("(a,b)"), Ok((vec!['a'], vec!['b'])));
+  assert_eq!(full_parse_list("[a](a,b)").unwrap_err().location, format!("1:4"));
+  assert!(full_parse_pair("[a]").is_err());
+}
+
+#[test]
+fn test_start_rules_partial_entry_functions() {
+  // The partial entry function returns the number of bytes consumed instead of requiring the end of input.
+  assert_eq!(partial_parse_list("[a,bc]"), Ok((vec![vec!['a'], vec!['b', 'c']], 6)));
+  assert_eq!(partial_parse_list("[a](a,b)"), Ok((vec![vec!['a']], 3)));
+  assert_eq!(partial_parse_pair("(a,b)rest"), Ok(((vec!['a'], vec!['b']), 5)));
+  assert!(partial_parse_pair("[a]").is_err());
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use oak_runtime::*;
+use oak::oak;
+
+oak! {
+  #![visitor]
+
+  program = (expr ";")* > make_program
+
+  expr = number > make_number
+       / "(" expr "+" expr ")" > make_add
+       / "[" expr % "," "]" > make_list
+       / "-" expr > make_neg
+
+  number = ["0-9"]+ > to_number
+
+  pub struct Program { pub exprs: Vec<Expr> }
+
+  pub enum Expr {
+    Number(u32),
+    Add(Box<Expr>, Box<Expr>),
+    List(Vec<Expr>),
+    Neg { operand: Box<Expr> }
+  }
+
+  fn make_program(exprs: Vec<Expr>) -> Program { Program { exprs } }
+  fn make_number(n: u32) -> Expr { Expr::Number(n) }
+  fn make_add(a: Expr, b: Expr) -> Expr { Expr::Add(Box::new(a), Box::new(b)) }
+  fn make_list(exprs: Vec<Expr>) -> Expr { Expr::List(exprs) }
+  fn make_neg(e: Expr) -> Expr { Expr::Neg { operand: Box::new(e) } }
+
+  fn to_number(raw_text: Vec<char>) -> u32 {
+    raw_text.into_iter().collect::<String>().parse().unwrap()
+  }
+}
+
+/// Sums the numbers of a program, the default methods visit the sub-expressions.
+struct Sum(u32);
+
+impl Visitor for Sum {
+  fn visit_expr(&mut self, node: &Expr) {
+    if let Expr::Number(n) = *node {
+      self.0 += n;
+    }
+    walk_expr(self, node)
+  }
+}
+
+#[test]
+fn test_visitor() {
+  let program = parse_program("(1+2);[3,-(4+5)];6;".into_state()).unwrap_data();
+  let mut sum = Sum(0);
+  sum.visit_program(&program);
+  assert_eq!(sum.0, 21);
+}
@@ -27,6 +27,16 @@ oak! {
   // d = "a" . / d1           // ERROR: left recursion
   // d1 = d
 
+  // The semantic actions do not consume input, they are transparent for the detection of left-recursion.
+  // d2 = "a" . / d3          // ERROR: left recursion
+  // d3 = d2 > id
+
+  // d4 = "a" . / d5 > id2    // ERROR: left recursion
+  // d5 = (d4 > id2) "b"
+
+  d6 = "a" d7 / "b" > zero    // OK
+  d7 = d6 > succ
+
   // e = "a" . / e1           // ERROR: left recursion
   // e1 = "b" . / e / "c" .
 
@@ -41,26 +51,52 @@ oak! {
   h = . / ("a" "b")+ !h1 .    // OK
   h1 = . / "a"? h / "c" .
 
+  // `i1` calls itself before consuming input, this is reported as left recursion rather than as a loop.
   // i = . i1               // ERROR: left recursion
   // i1 = i1+
 
-  // j = . j1               // ERROR: left recursion
-  // j1 = ("" !j)+
+  // `.` is consumed before `j` is called again, so it is not left-recursive, but the body of the repetition never consumes.
+  // j = . j1
+  // j1 = ("" !j)+          // ERROR: loop repeat
+
+  // The nullability of a rule can only be known through a cycle of rules, it is propagated by the fixpoint.
+  // The branch `""` of `z1` makes `z3`, and thus the body of `z2`, succeed without consuming input.
+  // z1 = "a" z2 / ""
+  // z2 = z3*               // ERROR: loop repeat
+  // z3 = z4
+  // z4 = &"b" z5
+  // z5 = z1
+
+  // The rules of the cycle always consume input since `y1` starts with `"a"`.
+  y1 = "a" y2               // OK
+  y2 = y3*
+  y3 = "b" / skip(&"c" y4)
+  y4 = y5
+  y5 = y1
 
   // m = !""                // ERROR: never succeed
   // n = (!"")*             // ERROR: never succeed
+  // m1 = "a" !"" "b" .     // ERROR: never succeed, with a note that `"b" .` is unreachable
 
   // w = !(.*)              // ERROR: never succeed
 
   // o = (!.)*              // ERROR: loop repeat
 
-  // p = ("a" / "b" / "")+  // ERROR loop repeat
+  // p = ("a" / "b" / "")+  // ERROR loop repeat, with a note on the branch `""`
+
+  // The empty literal never consumes input, so it does not make the body of a repetition consume.
+  // l1 = ("" "a"?)*        // ERROR: loop repeat
+  // l2 = ("" / "a")+       // ERROR: loop repeat, with a note on the branch `""`
+  // l3 = ("")*             // ERROR: loop repeat
+  l4 = ("" "a")*            // OK
+  l5 = ("a" / "")?          // OK
+  l6 = "a" ("b" / "") "c"   // OK
 
   // q = &["a-z"]           // ERROR: loop repeat
   // q2 = q+
 
   // r = ["a-z"] / "A"*     // ERROR: loop repeat
-  // r2 = r+
+  // r2 = r+                // ERROR: loop repeat, with a note on the branch `"A"*`
 
   s = &["a-z"] "a" / "A"+     // OK
   s2 = s*
@@ -71,17 +107,52 @@ oak! {
 
   // v = "a" .+ / "" / "Z" .+  // ERROR: unreachable branch
 
-
-  // u1 = ("x"?)? // Warning useless chaining
+  // A leading not-predicate can make a sequence fail even if the rest of the sequence always succeeds.
+  k1 = !"if" .* / "if" .*    // OK
+  k2 = !kw ["a-z"]*:(^) / kw // OK
+  kw = "if" / "else"
+  // k3 = (!"if" .* / .*) / "a"   // ERROR: unreachable branch (the inner choice never fails)
+
+  x1 = ["a-z"]+:(^) / "if"   // Warning: unreachable branch (the FIRST set of "if" is included in the one of ["a-z"]+)
+  x2 = "a" / "ab"            // Warning: unreachable branch
+  x3 = "ab" / "a"            // OK
+  x4 = (["a-z"] "b"?):(^) / "c"  // Warning: unreachable branch
+  x5 = "for" / "foreach"     // Warning: shadowed branch ("foreach" always starts with "for")
+  x6 = "foreach" / "for"     // OK
+  x7 = .* "x"                // Warning: never matches ("x" is consumed by .*)
+  x8 = ["a-z"]* "if"         // Warning: never matches
+  x9 = ["a-z"]* "1"          // OK
+  x10 = ["\\p{L}"]:(^) / "1"  // OK (the FIRST set of a Unicode category is over-approximated)
+  x11 = [^"\\p{L}"]+ "a"      // OK
+  x12 = (["a-z"]+ && { true }):(^) / "if"  // OK (the guard can fail)
+  x13 = checked_word:(^) / "if"           // OK (the action can fail)
+  checked_word = ["a-z"]+ > checked
+
+  // c1 = ["z-a"]             // ERROR: empty interval
+  // c2 = ["\u{1F64F}-\u{1F600}"] // ERROR: empty interval
+  c3 = ["a-ca-z"]             // Warning: `a-c` is already in the interval `a-z`
+  c7 = ["a-mk-z"]             // Warning: overlapping intervals
+  c8 = ["aa"]                 // Warning: the character `a` appears more than once
+  c9 = ["a-za-z"]             // Warning: the interval `a-z` appears more than once
+  c10 = ["a-zc-e0-9"]         // Warning: `c-e` is already in the interval `a-z`
+  c11 = ["0-9x5"]             // Warning: `5` is already in the interval `0-9`
+  c4 = ["a-z-"]               // OK
+  c5 = ["-+*/"] ["+*/-"]      // OK
+  // c6 = ["+-*/"]            // ERROR: empty interval (`-` between `+` and `*` is an interval)
+
+  u1 = ("x"?)? // Warning useless chaining
   // u2 = ("x"?)+ // ERROR: loop repeat
   // u3 = ("x"?)* // ERROR: loop repeat
-  // u4 = ("x"+)? // Warning useless chaining
-  // u5 = ("x"+)* // Warning useless chaining
-  // u6 = ("x"+)+ // Warning useless chaining
-  // u7 = ("x"*)? // Warning useless chaining
+  u4 = ("x"+)? // Warning useless chaining
+  u5 = ("x"+)* // Warning useless chaining
+  u6 = ("x"+)+ // Warning useless chaining
+  u7 = ("x"*)? // Warning useless chaining
   // u8 = ("x"*)+ // ERROR: loop repeat
   // u9 = ("x"*)* // ERROR: loop repeat
 
+  u10 = ("x"? "y")?     // OK
+  u11 = (["x"]+ > id)?  // OK
+
   // v1 = (&"x")+  // ERROR: loop repeat
   // v2 = (&"x")*  // ERROR: loop repeat
   // v3 = (&"x")?  // ERROR: always succeed, never consume
@@ -94,4 +165,10 @@ oak! {
   w4 = !("x"+)
   // w5 = !("x"*)  // ERROR: never succeed
   // w6 = !("x"?)  // ERROR: never succeed
+  // w7 = !(!"")   // ERROR: never succeed (the not-predicate is vacuously true, it is noted on the error of `!""`)
+
+  fn id(x: Vec<char>) -> Vec<char> { x }
+  fn checked(x: Vec<char>) -> Result<Vec<char>, String> { Ok(x) }
+  fn zero() -> u8 { 0 }
+  fn succ(x: u8) -> u8 { x + 1 }
 }
@@ -0,0 +1,72 @@
+// Copyright 2026 agent
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks that the code generated in recognizer mode (`#![recognizer]`) does not allocate, except for the errors.
+//! It is a test crate on its own because it replaces the global allocator.
+
+extern crate oak;
+extern crate oak_runtime;
+
+use oak::oak;
+use oak_runtime::*;
+
+use std::alloc::{GlobalAlloc, System, Layout};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+// Only the allocations of the current thread are counted, the test harness allocates in its own threads meanwhile.
+thread_local! {
+  static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+oak! {
+  #![recognizer]
+
+  words = (["a-z"]+ / " ")*
+}
+
+/// The allocations done by `recognize_words` on `count` repetitions of the input.
+fn allocations(count: usize) -> usize {
+  let input = "peg grammar ".repeat(count);
+  let state = input.as_str().into_state();
+  let before = ALLOCATIONS.with(Cell::get);
+  let state = recognize_words(state);
+  let after = ALLOCATIONS.with(Cell::get);
+  assert!(state.is_successful());
+  assert!(!state.current.has_next());
+  after - before
+}
+
+// The only allocation is the buffer of the items expected at the farthest position, done on the first error.
+// The buffer is reused when the errors move forward, so the number of allocations does not depend on the size of the input.
+#[test]
+fn test_recognizer_allocation_independent_of_input() {
+  assert_eq!(allocations(1), 1);
+  assert_eq!(allocations(100000), 1);
+}
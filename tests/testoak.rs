@@ -20,6 +20,8 @@
 extern crate oak;
 extern crate oak_runtime;
 extern crate term;
+extern crate alloc;
+extern crate core;
 
 use oak_runtime::*;
 use oak_runtime::ParseResult::*;
@@ -59,6 +61,8 @@ fn test_data_directory()
     |s| calc3::recognize_program(s)));
   test_engine.register("combinators", Some(format!("str_literal")), Box::new(
     |s| combinators::recognize_str_literal(s)));
+  test_engine.register("combinators", Some(format!("case_insensitive_str_literal")), Box::new(
+    |s| combinators::recognize_case_insensitive_str_literal(s)));
   test_engine.register("combinators", Some(format!("sequence")), Box::new(
     |s| combinators::recognize_sequence(s)));
   test_engine.register("combinators", Some(format!("any_single_char")), Box::new(
@@ -67,12 +71,32 @@ fn test_data_directory()
     |s| combinators::recognize_choice(s)));
   test_engine.register("combinators", Some(format!("repeat")), Box::new(
     |s| combinators::recognize_repeat(s)));
+  test_engine.register("combinators", Some(format!("bounded_repeat")), Box::new(
+    |s| combinators::recognize_bounded_repeat(s)));
+  test_engine.register("combinators", Some(format!("separated_list")), Box::new(
+    |s| combinators::recognize_separated_list(s)));
   test_engine.register("combinators", Some(format!("syntactic_predicate")), Box::new(
     |s| combinators::recognize_predicate(s)));
+  test_engine.register("combinators", Some(format!("end_of_input")), Box::new(
+    |s| combinators::recognize_end_of_input(s)));
+  test_engine.register("combinators", Some(format!("dispatch")), Box::new(
+    |s| combinators::recognize_dispatch(s)));
+  test_engine.register("combinators", Some(format!("until")), Box::new(
+    |s| combinators::recognize_until(s)));
   test_engine.register("combinators", Some(format!("optional")), Box::new(
     |s| combinators::recognize_optional(s)));
   test_engine.register("combinators", Some(format!("char_class")), Box::new(
     |s| combinators::recognize_char_class(s)));
+  test_engine.register("combinators", Some(format!("unicode_category")), Box::new(
+    |s| combinators::recognize_unicode_category(s)));
+  test_engine.register("combinators", Some(format!("escapes")), Box::new(
+    |s| combinators::recognize_escapes(s)));
+  test_engine.register("combinators", Some(format!("class_edges")), Box::new(
+    |s| combinators::recognize_class_edges(s)));
+  test_engine.register("combinators", Some(format!("raw_strings")), Box::new(
+    |s| combinators::recognize_raw_strings(s)));
+  test_engine.register("combinators", Some(format!("negated_char_class")), Box::new(
+    |s| combinators::recognize_negated_char_class(s)));
   test_engine.register("combinators", Some(format!("non_terminal")), Box::new(
     |s| combinators::recognize_non_terminal(s)));
   test_engine.run();